@@ -1,7 +1,7 @@
 use bevy::asset::AssetServer;
-use bevy::prelude::{BackgroundColor, Color, EntityCommands};
+use bevy::prelude::{BackgroundColor, Color, EntityCommands, PartialReflect};
 use crate::injector::Injector;
-use crate::prelude::{Extractor, FromStrTyped, ValueStorage};
+use crate::prelude::{parse_typed, Extractor, FromStrTyped, ValueStorage};
 use crate::xml_component::XmlComponent;
 
 #[derive(Default, Debug, Clone)]
@@ -11,7 +11,7 @@ pub struct BackgroundColorParser {
 
 fn set_value_safe(color: &mut Color, name: &str, value: &ValueStorage) {
     if name == "value" {
-        *color = *value.read::<Color>();
+        value.write_to(color);
     }
 }
 
@@ -43,4 +43,20 @@ impl Injector for ColorInjector {
     fn inject_value(&self, name: &str, value: &ValueStorage, extractor: &mut Extractor, _: &AssetServer) {
         extractor.extract::<BackgroundColor, _>(|c| set_value_safe(&mut c.0, name, value));
     }
+
+    fn extract_value(&self, name: &str, extractor: &Extractor) -> Option<Box<dyn PartialReflect>> {
+        if name == "value" {
+            return Some(Box::new(extractor.get::<BackgroundColor>()?.0));
+        }
+
+        None
+    }
+
+    fn parse_value(&self, name: &str, value: &str) -> Option<Result<Box<dyn PartialReflect>, String>> {
+        if name == "value" {
+            return parse_typed::<Color>(value);
+        }
+
+        None
+    }
 }
\ No newline at end of file
@@ -1,8 +1,8 @@
 use std::str::FromStr;
 use bevy::asset::AssetServer;
-use bevy::prelude::{Color, EntityCommands, Image, ImageNode, NodeImageMode, Rect};
+use bevy::prelude::{Color, EntityCommands, Handle, Image, ImageNode, NodeImageMode, PartialReflect, Rect};
 use crate::injector::Injector;
-use crate::prelude::{Extractor, FromStrTyped, ValueStorage};
+use crate::prelude::{parse_typed, Extractor, FromStrTyped, ValueStorage};
 use crate::xml_component::XmlComponent;
 use crate::raw_handle::RawHandle;
 
@@ -11,14 +11,38 @@ impl Injector for ImageNodeInjector {
     fn inject_value(&self,name: &str, value: &ValueStorage, extractor: &mut Extractor, server: &AssetServer) {
         extractor.extract::<ImageNode, _>(|c| {
             match name {
-                "flip_x" => c.flip_x = *value.read::<bool>(),
-                "flip_y" => c.flip_y = *value.read::<bool>(),
-                "color"  => c.color  = *value.read::<Color>(),
-                "image"  => c.image  = server.load(value.read::<String>()),
+                "flip_x" => value.write_to(&mut c.flip_x),
+                "flip_y" => value.write_to(&mut c.flip_y),
+                "color"  => value.write_to(&mut c.color),
+                "image"  => match value.try_read::<Handle<Image>>() {
+                    Some(handle) => c.image = handle.clone(),
+                    None => if let Some(handle) = value.load(server) {
+                        c.image = handle;
+                    },
+                },
                 _ => {},
             }
         });
     }
+
+    fn extract_value(&self, name: &str, extractor: &Extractor) -> Option<Box<dyn PartialReflect>> {
+        let c = extractor.get::<ImageNode>()?;
+        Some(match name {
+            "flip_x" => Box::new(c.flip_x),
+            "flip_y" => Box::new(c.flip_y),
+            "color"  => Box::new(c.color),
+            _ => return None,
+        })
+    }
+
+    fn parse_value(&self, name: &str, value: &str) -> Option<Result<Box<dyn PartialReflect>, String>> {
+        match name {
+            "flip_x" | "flip_y" => parse_typed::<bool>(value),
+            "color"  => parse_typed::<Color>(value),
+            "image"  => parse_typed::<String>(value),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -34,10 +58,12 @@ pub struct ImageNodeParser {
 impl XmlComponent for ImageNodeParser {
     fn write_value(&mut self, name: &str, value: &ValueStorage) {
         match name {
-            "flip_x" => self.flip_x = *value.read::<bool>(),
-            "flip_y" => self.flip_y = *value.read::<bool>(),
-            "color"  => self.color  = *value.read::<Color>(),
-            "image"  => self.image  = RawHandle::new(value.read::<String>().clone()),
+            "flip_x" => value.write_to(&mut self.flip_x),
+            "flip_y" => value.write_to(&mut self.flip_y),
+            "color"  => value.write_to(&mut self.color),
+            "image"  => if let Some(path) = value.read::<String>() {
+                self.image = RawHandle::new(path.clone());
+            },
             _ => {},
         }
     }
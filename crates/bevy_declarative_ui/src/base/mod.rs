@@ -179,20 +179,25 @@ impl FromStrTyped for OverflowClipBox {
 impl_is_typed!(Val);
 impl FromStrTyped for Val{
     fn from_str_typed(s: &str) -> Result<Val, String> {
+        let number = |value: &str, variant: fn(f32) -> Val| value
+            .parse::<f32>()
+            .map(variant)
+            .map_err(|_| format!("Invalid number in value: {}", s));
+
         if s == "auto" {
             Ok(Val::Auto)
         } else if let Some(px) = s.strip_suffix("px") {
-            return Ok(px.parse().ok().map(Val::Px).unwrap());
+            number(px, Val::Px)
         } else if let Some(pc) = s.strip_suffix('%') {
-            return Ok(pc.parse().ok().map(Val::Percent).unwrap());
+            number(pc, Val::Percent)
         } else if let Some(inner) = s.strip_prefix("vw(").and_then(|s| s.strip_suffix(")")) {
-            return Ok(inner.parse().ok().map(Val::Vw).unwrap());
+            number(inner, Val::Vw)
         } else if let Some(inner) = s.strip_prefix("vh(").and_then(|s| s.strip_suffix(")")) {
-            return Ok(inner.parse().ok().map(Val::Vh).unwrap());
+            number(inner, Val::Vh)
         } else if let Some(inner) = s.strip_prefix("vmin(").and_then(|s| s.strip_suffix(")")) {
-            return Ok(inner.parse().ok().map(Val::VMin).unwrap());
+            number(inner, Val::VMin)
         } else if let Some(inner) = s.strip_prefix("vmax(").and_then(|s| s.strip_suffix(")")) {
-            return Ok(inner.parse().ok().map(Val::VMax).unwrap());
+            number(inner, Val::VMax)
         }
         else {
             Err(format!("Unknown value: {}", s))
@@ -214,7 +219,7 @@ impl FromStrTyped for UiRect {
             2 => Ok(UiRect::new(result[0].clone()?, result[1].clone()?, result[0].clone()?, result[1].clone()?)),
             3 => Ok(UiRect::new(result[0].clone()?, result[1].clone()?, result[2].clone()?, result[1].clone()?)),
             4 => Ok(UiRect::new(result[0].clone()?, result[1].clone()?, result[2].clone()?, result[3].clone()?)),
-            _ => panic!("todo"),
+            _ => Err(format!("Expected 1 to 4 values: {}", s)),
         }
     }
 }
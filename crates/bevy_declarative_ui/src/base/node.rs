@@ -1,7 +1,7 @@
 use std::str::FromStr;
 use bevy::prelude::*;
 use crate::injector::Injector;
-use crate::prelude::{Extractor, FromStrTyped, ValueStorage};
+use crate::prelude::{parse_typed, Extractor, FromStrTyped, ValueStorage};
 use crate::xml_component::XmlComponent;
 
 pub struct NodeInjector;
@@ -9,6 +9,34 @@ impl Injector for NodeInjector {
     fn inject_value(&self, name: &str, value: &ValueStorage, extractor: &mut Extractor, _: &AssetServer) {
         extractor.extract::<Node, _>(|node| set_value_safe(node, name, value));
     }
+
+    fn extract_value(&self, name: &str, extractor: &Extractor) -> Option<Box<dyn PartialReflect>> {
+        get_value(extractor.get::<Node>()?, name)
+    }
+
+    fn parse_value(&self, name: &str, value: &str) -> Option<Result<Box<dyn PartialReflect>, String>> {
+        match name {
+            "overflow_clip_visual_box" => parse_typed::<OverflowClipBox>(value),
+            "overflow_x" | "overflow_y" => parse_typed::<OverflowAxis>(value),
+            "display"                  => parse_typed::<Display>(value),
+            "box_sizing"               => parse_typed::<BoxSizing>(value),
+            "position_type"            => parse_typed::<PositionType>(value),
+            "aspect_ratio"             => Some(f32::from_str_typed(value).map(|value| Box::new(Some(value)) as Box<dyn PartialReflect>)),
+            "align_items"              => parse_typed::<AlignItems>(value),
+            "justify_items"            => parse_typed::<JustifyItems>(value),
+            "align_self"               => parse_typed::<AlignSelf>(value),
+            "justify_self"             => parse_typed::<JustifySelf>(value),
+            "align_content"            => parse_typed::<AlignContent>(value),
+            "justify_content"          => parse_typed::<JustifyContent>(value),
+            "margin" | "padding" | "border" => parse_typed::<UiRect>(value),
+            "flex_direction"           => parse_typed::<FlexDirection>(value),
+            "flex_wrap"                => parse_typed::<FlexWrap>(value),
+            "overflow_clip_margin" | "flex_grow" | "flex_shrink" => parse_typed::<f32>(value),
+            "left" | "right" | "top" | "bottom" | "width" | "height" | "min_width" | "min_height" | "max_width"
+            | "max_height" | "flex_basis" | "row_gap" | "column_gap" => parse_typed::<Val>(value),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Default, Debug, Clone)]
@@ -60,47 +88,90 @@ fn set_value(node: &mut Node, name:&str, value:&str) -> bool {
 
 fn set_value_safe(node: &mut Node, name:&str, value: &ValueStorage) {
     match name {
-        "overflow_clip_visual_box" => node.overflow_clip_margin.visual_box = *value.read::<OverflowClipBox>(),
-        "overflow_clip_margin"     => node.overflow_clip_margin.margin     = *value.read::<f32>(),
-        "overflow_x"               => node.overflow.x      = *value.read::<OverflowAxis>(),
-        "overflow_y"               => node.overflow.y      = *value.read::<OverflowAxis>(),
-        "display"                  => node.display         = *value.read::<Display>(),
-        "box_sizing"               => node.box_sizing      = *value.read::<BoxSizing>(),
-        "position_type"            => node.position_type   = *value.read::<PositionType>(),
-
-        "left"                     => node.left            = *value.read::<Val>(),
-        "right"                    => node.right           = *value.read::<Val>(),
-        "top"                      => node.top             = *value.read::<Val>(),
-        "bottom"                   => node.bottom          = *value.read::<Val>(),
-        "width"                    => node.width           = *value.read::<Val>(),
-        "height"                   => node.height          = *value.read::<Val>(),
-        "min_width"                => node.min_width       = *value.read::<Val>(),
-        "min_height"               => node.min_height      = *value.read::<Val>(),
-        "max_width"                => node.max_width       = *value.read::<Val>(),
-        "max_height"               => node.max_height      = *value.read::<Val>(),
-        "aspect_ratio"             => node.aspect_ratio    = *value.read::<Option<f32>>(),
-
-        "align_items"              => node.align_items     = *value.read::<AlignItems>(),
-        "justify_items"            => node.justify_items   = *value.read::<JustifyItems>(),
-        "align_self"               => node.align_self      = *value.read::<AlignSelf>(),
-        "justify_self"             => node.justify_self    = *value.read::<JustifySelf>(),
-        "align_content"            => node.align_content   = *value.read::<AlignContent>(),
-        "justify_content"          => node.justify_content = *value.read::<JustifyContent>(),
-
-        "margin"                   => node.margin          = *value.read::<UiRect>(),
-        "padding"                  => node.padding         = *value.read::<UiRect>(),
-        "border"                   => node.border          = *value.read::<UiRect>(),
-        "flex_direction"           => node.flex_direction  = *value.read::<FlexDirection>(),
-        "flex_wrap"                => node.flex_wrap       = *value.read::<FlexWrap>(),
-        "flex_grow"                => node.flex_grow       = *value.read::<f32>(),
-        "flex_shrink"              => node.flex_shrink     = *value.read::<f32>(),
-        "flex_basis"               => node.flex_basis      = *value.read::<Val>(),
-        "row_gap"                  => node.row_gap         = *value.read::<Val>(),
-        "column_gap"               => node.column_gap      = *value.read::<Val>(),
+        "overflow_clip_visual_box" => value.write_to(&mut node.overflow_clip_margin.visual_box),
+        "overflow_clip_margin"     => value.write_to(&mut node.overflow_clip_margin.margin),
+        "overflow_x"               => value.write_to(&mut node.overflow.x),
+        "overflow_y"               => value.write_to(&mut node.overflow.y),
+        "display"                  => value.write_to(&mut node.display),
+        "box_sizing"               => value.write_to(&mut node.box_sizing),
+        "position_type"            => value.write_to(&mut node.position_type),
+
+        "left"                     => value.write_to(&mut node.left),
+        "right"                    => value.write_to(&mut node.right),
+        "top"                      => value.write_to(&mut node.top),
+        "bottom"                   => value.write_to(&mut node.bottom),
+        "width"                    => value.write_to(&mut node.width),
+        "height"                   => value.write_to(&mut node.height),
+        "min_width"                => value.write_to(&mut node.min_width),
+        "min_height"               => value.write_to(&mut node.min_height),
+        "max_width"                => value.write_to(&mut node.max_width),
+        "max_height"               => value.write_to(&mut node.max_height),
+        "aspect_ratio"             => value.write_to(&mut node.aspect_ratio),
+
+        "align_items"              => value.write_to(&mut node.align_items),
+        "justify_items"            => value.write_to(&mut node.justify_items),
+        "align_self"               => value.write_to(&mut node.align_self),
+        "justify_self"             => value.write_to(&mut node.justify_self),
+        "align_content"            => value.write_to(&mut node.align_content),
+        "justify_content"          => value.write_to(&mut node.justify_content),
+
+        "margin"                   => value.write_to(&mut node.margin),
+        "padding"                  => value.write_to(&mut node.padding),
+        "border"                   => value.write_to(&mut node.border),
+        "flex_direction"           => value.write_to(&mut node.flex_direction),
+        "flex_wrap"                => value.write_to(&mut node.flex_wrap),
+        "flex_grow"                => value.write_to(&mut node.flex_grow),
+        "flex_shrink"              => value.write_to(&mut node.flex_shrink),
+        "flex_basis"               => value.write_to(&mut node.flex_basis),
+        "row_gap"                  => value.write_to(&mut node.row_gap),
+        "column_gap"               => value.write_to(&mut node.column_gap),
         _ => {},
     }
 }
 
+fn get_value(node: &Node, name: &str) -> Option<Box<dyn PartialReflect>> {
+    Some(match name {
+        "overflow_clip_visual_box" => Box::new(node.overflow_clip_margin.visual_box),
+        "overflow_clip_margin"     => Box::new(node.overflow_clip_margin.margin),
+        "overflow_x"               => Box::new(node.overflow.x),
+        "overflow_y"               => Box::new(node.overflow.y),
+        "display"                  => Box::new(node.display),
+        "box_sizing"               => Box::new(node.box_sizing),
+        "position_type"            => Box::new(node.position_type),
+
+        "left"                     => Box::new(node.left),
+        "right"                    => Box::new(node.right),
+        "top"                      => Box::new(node.top),
+        "bottom"                   => Box::new(node.bottom),
+        "width"                    => Box::new(node.width),
+        "height"                   => Box::new(node.height),
+        "min_width"                => Box::new(node.min_width),
+        "min_height"               => Box::new(node.min_height),
+        "max_width"                => Box::new(node.max_width),
+        "max_height"               => Box::new(node.max_height),
+        "aspect_ratio"             => Box::new(node.aspect_ratio),
+
+        "align_items"              => Box::new(node.align_items),
+        "justify_items"            => Box::new(node.justify_items),
+        "align_self"               => Box::new(node.align_self),
+        "justify_self"             => Box::new(node.justify_self),
+        "align_content"            => Box::new(node.align_content),
+        "justify_content"          => Box::new(node.justify_content),
+
+        "margin"                   => Box::new(node.margin),
+        "padding"                  => Box::new(node.padding),
+        "border"                   => Box::new(node.border),
+        "flex_direction"           => Box::new(node.flex_direction),
+        "flex_wrap"                => Box::new(node.flex_wrap),
+        "flex_grow"                => Box::new(node.flex_grow),
+        "flex_shrink"              => Box::new(node.flex_shrink),
+        "flex_basis"               => Box::new(node.flex_basis),
+        "row_gap"                  => Box::new(node.row_gap),
+        "column_gap"               => Box::new(node.column_gap),
+        _ => return None,
+    })
+}
+
 impl XmlComponent for NodeParser {
     fn write_value(&mut self, name: &str, value: &ValueStorage) {
         set_value_safe(&mut self.node, name, value)
@@ -1,13 +1,31 @@
 use bevy::asset::AssetServer;
-use bevy::prelude::{EntityCommands, Text};
+use bevy::prelude::{EntityCommands, PartialReflect, Text};
 use crate::injector::Injector;
-use crate::prelude::{Extractor, ValueStorage};
+use crate::prelude::{parse_typed, Extractor, ValueStorage};
 use crate::xml_component::XmlComponent;
 
 pub struct TextInjector;
 impl Injector for TextInjector {
     fn inject_value(&self, name: &str, value: &ValueStorage, extractor: &mut Extractor, _: &AssetServer) {
-        extractor.extract::<Text, _>(|c| { set_value(&mut c.0, name, value.read::<String>()); })
+        if name == "text" {
+            extractor.extract::<Text, _>(|c| value.write_to(&mut c.0));
+        }
+    }
+
+    fn extract_value(&self, name: &str, extractor: &Extractor) -> Option<Box<dyn PartialReflect>> {
+        if name == "text" {
+            return Some(Box::new(extractor.get::<Text>()?.0.clone()));
+        }
+
+        None
+    }
+
+    fn parse_value(&self, name: &str, value: &str) -> Option<Result<Box<dyn PartialReflect>, String>> {
+        if name == "text" {
+            return parse_typed::<String>(value);
+        }
+
+        None
     }
 }
 
@@ -28,7 +46,9 @@ fn set_value(string: &mut String, name: &str, value: &str) -> bool {
 
 impl XmlComponent for TextParser {
     fn write_value(&mut self, name: &str, value: &ValueStorage) {
-        set_value(&mut self.value, name, value.read::<String>());
+        if name == "text" {
+            value.write_to(&mut self.value);
+        }
     }
 
     fn insert_to(&self, entity: &mut EntityCommands, _: &AssetServer) {
@@ -1,7 +1,7 @@
 use bevy::asset::AssetServer;
-use bevy::prelude::{Color, EntityCommands, TextColor};
+use bevy::prelude::{Color, EntityCommands, PartialReflect, TextColor};
 use crate::injector::Injector;
-use crate::prelude::{Extractor, FromStrTyped, ValueStorage};
+use crate::prelude::{parse_typed, Extractor, FromStrTyped, ValueStorage};
 use crate::xml_component::XmlComponent;
 
 pub struct TextColorInjector;
@@ -9,6 +9,22 @@ impl Injector for TextColorInjector {
     fn inject_value(&self, name: &str, value: &ValueStorage, extractor: &mut Extractor, _: &AssetServer) {
         extractor.extract::<TextColor, _>(|c| set_value_safe(&mut c.0, name, value));
     }
+
+    fn extract_value(&self, name: &str, extractor: &Extractor) -> Option<Box<dyn PartialReflect>> {
+        if name == "value" {
+            return Some(Box::new(extractor.get::<TextColor>()?.0));
+        }
+
+        None
+    }
+
+    fn parse_value(&self, name: &str, value: &str) -> Option<Result<Box<dyn PartialReflect>, String>> {
+        if name == "value" {
+            return parse_typed::<Color>(value);
+        }
+
+        None
+    }
 }
 
 #[derive(Default, Debug, Clone)]
@@ -18,7 +34,7 @@ pub struct TextColorParser {
 
 fn set_value_safe(color: &mut Color, name: &str, value: &ValueStorage) {
     if name == "value" {
-        *color = *value.read::<Color>();
+        value.write_to(color);
     }
 }
 
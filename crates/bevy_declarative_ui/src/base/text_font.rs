@@ -1,9 +1,9 @@
 use std::str::FromStr;
 use bevy::asset::AssetServer;
-use bevy::prelude::{EntityCommands, Font, TextFont};
+use bevy::prelude::{EntityCommands, Font, PartialReflect, TextFont};
 use bevy::text::{FontSmoothing, LineHeight};
 use crate::injector::Injector;
-use crate::prelude::{Extractor, FromStrTyped, ValueStorage};
+use crate::prelude::{parse_typed, Extractor, FromStrTyped, ValueStorage};
 use crate::xml_component::XmlComponent;
 use crate::raw_handle::RawHandle;
 
@@ -12,14 +12,36 @@ impl Injector for TextFontInjector {
     fn inject_value(&self, name: &str, value: &ValueStorage, extractor: &mut Extractor, server: &AssetServer) {
         extractor.extract::<TextFont, _>(|c| {
             match name {
-                "font"           => c.font           = value.load::<Font>(server),
-                "font_size"      => c.font_size      = *value.read::<f32>(),
-                "line_height"    => c.line_height    = *value.read::<LineHeight>(),
-                "font_smoothing" => c.font_smoothing = *value.read::<FontSmoothing>(),
+                "font"           => if let Some(font) = value.load::<Font>(server) {
+                    c.font = font;
+                },
+                "font_size"      => value.write_to(&mut c.font_size),
+                "line_height"    => value.write_to(&mut c.line_height),
+                "font_smoothing" => value.write_to(&mut c.font_smoothing),
                 _ => {}
             }
         });
     }
+
+    fn extract_value(&self, name: &str, extractor: &Extractor) -> Option<Box<dyn PartialReflect>> {
+        let c = extractor.get::<TextFont>()?;
+        Some(match name {
+            "font_size"      => Box::new(c.font_size),
+            "line_height"    => Box::new(c.line_height),
+            "font_smoothing" => Box::new(c.font_smoothing),
+            _ => return None,
+        })
+    }
+
+    fn parse_value(&self, name: &str, value: &str) -> Option<Result<Box<dyn PartialReflect>, String>> {
+        match name {
+            "font"           => parse_typed::<String>(value),
+            "font_size"      => parse_typed::<f32>(value),
+            "line_height"    => parse_typed::<LineHeight>(value),
+            "font_smoothing" => parse_typed::<FontSmoothing>(value),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -44,10 +66,12 @@ impl Default for TextFontParser {
 impl XmlComponent for TextFontParser {
     fn write_value(&mut self, name: &str, value: &ValueStorage) {
         match name {
-            "font"           => self.font           = RawHandle::new(value.read::<String>().clone()),
-            "font_size"      => self.font_size      = *value.read::<f32>(),
-            "line_height"    => self.line_height    = *value.read::<LineHeight>(),
-            "font_smoothing" => self.font_smoothing = *value.read::<FontSmoothing>(),
+            "font"           => if let Some(path) = value.read::<String>() {
+                self.font = RawHandle::new(path.clone());
+            },
+            "font_size"      => value.write_to(&mut self.font_size),
+            "line_height"    => value.write_to(&mut self.line_height),
+            "font_smoothing" => value.write_to(&mut self.font_smoothing),
             _ => {}
         }
     }
@@ -11,8 +11,8 @@ pub struct TextLayoutParser {
 
 fn set_value_safe(c: &mut TextLayout, name: &str, value: &ValueStorage) {
     match name {
-        "justify"   => c.justify   = *value.read::<JustifyText>(),
-        "linebreak" => c.linebreak = *value.read::<LineBreak>(),
+        "justify"   => value.write_to(&mut c.justify),
+        "linebreak" => value.write_to(&mut c.linebreak),
         _ => {},
     }
 }
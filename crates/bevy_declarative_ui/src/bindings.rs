@@ -0,0 +1,641 @@
+use std::any::TypeId;
+use std::sync::{Arc, Mutex};
+use bevy::ecs::archetype::{ArchetypeGeneration, ArchetypeId};
+use bevy::ecs::component::Tick;
+use bevy::prelude::*;
+use bevy::reflect::{ReflectMut, ReflectRef, TypeRegistration, TypeRegistry};
+use bevy_declarative_ui_parser::values::{AttributeValue, InterpolationPart, TemplateBinding};
+use bevy_declarative_ui_parser::values::bindings::BindingMode;
+use bevy_declarative_ui_parser::values::bindings::filter::{Filter, Filters};
+use bevy_declarative_ui_parser::values::bindings::path::{BindingPath, PathAccess, PathSegment};
+use crate::converters::display;
+use crate::parser::{fallback, AttributeBinding, CompiledProperty};
+use crate::prelude::*;
+
+///Place the bound value is read from and written to
+#[derive(Clone)]
+pub(crate) enum BindingSource {
+    Resource {
+        resource: ReflectResource,
+        path:     BindingPath,
+    },
+    Component {
+        component: ReflectComponent,
+        source:    Arc<SourceQuery>,
+        path:      BindingPath,
+    },
+    ///Field of the item behind the enclosing template instance
+    Item(BindingPath),
+    ///Text mixed with bindings, read through [`BindingSource::render`]
+    Interpolated(Arc<[InterpolationSource]>),
+    ///`<LocalResources>` property of the document, or the `<GlobalResources>` one of its layout
+    Property(TypeId),
+}
+
+pub(crate) enum InterpolationSource {
+    Text(String),
+    Binding {
+        source:    BindingSource,
+        converter: Option<Arc<dyn UiConverter>>,
+        ///Raw `Fallback` parameter, rendered while the source is missing
+        fallback:  Option<String>,
+    },
+}
+
+///Finds the entity a component binding reads from
+pub(crate) struct SourceQuery {
+    target:  TypeId,
+    filters: Vec<SourceFilter>,
+    cache:   Mutex<SourceCache>,
+}
+
+///Archetypes with the target component, only the ones created since `generation` are checked again
+struct SourceCache {
+    generation: ArchetypeGeneration,
+    archetypes: Vec<ArchetypeId>,
+    ///Last selected entity, reused while it passes the filters
+    entity:     Option<Entity>,
+}
+
+///Query filter resolved against the type registry
+enum SourceFilter {
+    With(TypeId),
+    Without(TypeId),
+    Changed(TypeId),
+    Added(TypeId),
+    Or(Vec<SourceFilter>),
+}
+
+impl SourceFilter {
+    fn matches(&self, world: &World, entity: EntityRef, last_run: Tick, this_run: Tick) -> bool {
+        let ticks = |type_id: &TypeId| world.components().get_id(*type_id)
+            .and_then(|id| entity.get_change_ticks_by_id(id));
+
+        match self {
+            SourceFilter::With(type_id)    => entity.contains_type_id(*type_id),
+            SourceFilter::Without(type_id) => !entity.contains_type_id(*type_id),
+            SourceFilter::Changed(type_id) => ticks(type_id).is_some_and(|ticks| ticks.is_changed(last_run, this_run)),
+            SourceFilter::Added(type_id)   => ticks(type_id).is_some_and(|ticks| ticks.is_added(last_run, this_run)),
+            SourceFilter::Or(filters)      => filters.iter().any(|filter| filter.matches(world, entity, last_run, this_run)),
+        }
+    }
+
+    ///`Changed` and `Added` filters decide when the source is read, not which entity it is
+    fn is_change(&self) -> bool {
+        match self {
+            SourceFilter::Changed(_) | SourceFilter::Added(_) => true,
+            SourceFilter::Or(filters) => filters.iter().any(SourceFilter::is_change),
+            _ => false,
+        }
+    }
+}
+
+impl SourceQuery {
+    fn new(target: TypeId, filters: Vec<SourceFilter>) -> Self {
+        Self {
+            target,
+            filters,
+            cache: Mutex::new(SourceCache {
+                generation: ArchetypeGeneration::initial(),
+                archetypes: Vec::new(),
+                entity:     None,
+            }),
+        }
+    }
+
+    ///First entity which has the target component and passes the `With` and `Without` filters
+    fn entity(&self, world: &World) -> Option<Entity> {
+        let target = world.components().get_id(self.target)?;
+        let Ok(mut cache) = self.cache.lock() else {
+            return None;
+        };
+
+        let cached = cache.entity.and_then(|entity| world.get_entity(entity).ok());
+        if let Some(entity) = cached && entity.contains_id(target) && self.selects(world, entity) {
+            return Some(entity.id());
+        }
+
+        let archetypes = world.archetypes();
+        let created: Vec<ArchetypeId> = archetypes[cache.generation..].iter()
+            .filter(|archetype| archetype.contains(target))
+            .map(|archetype| archetype.id())
+            .collect();
+        cache.archetypes.extend(created);
+        cache.generation = archetypes.generation();
+
+        cache.entity = cache.archetypes.iter()
+            .filter_map(|id| archetypes.get(*id))
+            .flat_map(|archetype| archetype.entities().iter().map(|entity| entity.id()))
+            .find(|entity| self.selects(world, world.entity(*entity)));
+        cache.entity
+    }
+
+    fn selects(&self, world: &World, entity: EntityRef) -> bool {
+        let (last_run, this_run) = (world.last_change_tick(), world.read_change_tick());
+        self.filters.iter()
+            .filter(|filter| !filter.is_change())
+            .all(|filter| filter.matches(world, entity, last_run, this_run))
+    }
+
+    ///Whether the `Changed` and `Added` filters pass this frame
+    fn is_changed(&self, world: &World, entity: Entity) -> bool {
+        let (last_run, this_run) = (world.last_change_tick(), world.read_change_tick());
+        let entity = world.entity(entity);
+        self.filters.iter()
+            .filter(|filter| filter.is_change())
+            .all(|filter| filter.matches(world, entity, last_run, this_run))
+    }
+}
+
+impl BindingSource {
+    pub fn read<'w>(&self, world: &'w World, context: &UiContext) -> Option<&'w dyn PartialReflect> {
+        match self {
+            BindingSource::Resource { resource, path } => {
+                let resource = resource.reflect(world).ok()?;
+                path_element(path, resource.as_partial_reflect())
+            }
+            BindingSource::Component { component, source, path } => {
+                let component = component.reflect(world.entity(source.entity(world)?))?;
+                path_element(path, component.as_partial_reflect())
+            }
+            BindingSource::Item(path) => {
+                let (source, index) = item_source(world, context)?;
+                let item = list_element(source.read(world, context)?, index)?;
+                path_element(path, item)
+            }
+            BindingSource::Interpolated(_) => None,
+            BindingSource::Property(key) => property(world, context, *key).map(|storage| storage.storage.as_partial_reflect()),
+        }
+    }
+
+    ///The source exists but its `Changed` or `Added` filters did not pass, the attribute keeps its value
+    pub fn is_unchanged(&self, world: &World) -> bool {
+        match self {
+            BindingSource::Component { source, .. } => source.entity(world)
+                .is_some_and(|entity| !source.is_changed(world, entity)),
+            _ => false,
+        }
+    }
+
+    ///Text of an interpolated source, `None` for other sources or if a binding without fallback is missing
+    pub fn render(&self, world: &World, context: &UiContext, server: &AssetServer) -> Option<String> {
+        let BindingSource::Interpolated(parts) = self else {
+            return None;
+        };
+
+        parts.iter().try_fold(String::new(), |mut text, part| {
+            match part {
+                InterpolationSource::Text(value) => text.push_str(value),
+                InterpolationSource::Binding { source, converter, fallback } => {
+                    let value = source.read(world, context).and_then(|value| match converter {
+                        Some(converter) => converter.convert(value, server).map(|value| display(value.as_ref())),
+                        None => Some(display(value)),
+                    });
+                    text.push_str(&value.or_else(|| fallback.clone())?);
+                }
+            }
+            Some(text)
+        })
+    }
+
+    pub fn write(&self, world: &mut World, context: &UiContext, value: &dyn PartialReflect) -> bool {
+        self.with_mut(world, context, &mut |target| target.try_apply(value).is_ok())
+    }
+
+    fn with_mut(&self, world: &mut World, context: &UiContext, f: &mut dyn FnMut(&mut dyn PartialReflect) -> bool) -> bool {
+        match self {
+            BindingSource::Resource { resource, path } => {
+                let Ok(mut resource) = resource.reflect_mut(world) else {
+                    return false;
+                };
+                path_element_mut(path, resource.as_partial_reflect_mut())
+                    .is_some_and(&mut *f)
+            }
+            BindingSource::Component { component, source, path } => {
+                let Some(entity) = source.entity(world) else {
+                    return false;
+                };
+                let mut entity = world.entity_mut(entity);
+                let Some(mut component) = component.reflect_mut(&mut entity) else {
+                    return false;
+                };
+                path_element_mut(path, component.as_partial_reflect_mut())
+                    .is_some_and(&mut *f)
+            }
+            BindingSource::Item(path) => {
+                let Some((source, index)) = item_source(world, context) else {
+                    return false;
+                };
+                source.with_mut(world, context, &mut |list| {
+                    list_element_mut(list, index)
+                        .and_then(|item| path_element_mut(path, item))
+                        .is_some_and(&mut *f)
+                })
+            }
+            BindingSource::Interpolated(_) => false,
+            BindingSource::Property(key) => property_mut(world, context, *key).is_some_and(|storage| {
+                storage.storage.set_changed();
+                f(storage.storage.as_partial_reflect_mut())
+            }),
+        }
+    }
+
+    ///Number of elements in the list or array behind the source
+    pub fn len(&self, world: &World, context: &UiContext) -> Option<usize> {
+        match self.read(world, context)?.reflect_ref() {
+            ReflectRef::List(list)   => Some(list.len()),
+            ReflectRef::Array(array) => Some(array.len()),
+            _ => None,
+        }
+    }
+}
+
+#[allow(deprecated)]
+fn property<'w>(world: &'w World, context: &UiContext, key: TypeId) -> Option<&'w Storage> {
+    world.get::<UiResources>(context.root_entity())
+        .and_then(|resources| resources.get_property(key))
+        .or_else(|| world.get_resource::<GlobalResources>()?.storage.get(&context.layout_handle.id())?.get_property(key))
+}
+
+#[allow(deprecated)]
+fn property_mut<'w>(world: &'w mut World, context: &UiContext, key: TypeId) -> Option<&'w mut Storage> {
+    let local = world.get::<UiResources>(context.root_entity())
+        .is_some_and(|resources| resources.get_property(key).is_some());
+
+    if local {
+        world.get_mut::<UiResources>(context.root_entity())?.into_inner().get_property_mut(key)
+    } else {
+        world.get_resource_mut::<GlobalResources>()?.into_inner().storage.get_mut(&context.layout_handle.id())?.get_property_mut(key)
+    }
+}
+
+fn item_source(world: &World, context: &UiContext) -> Option<(BindingSource, usize)> {
+    let instance = world.get::<TemplateInstance>(context.subtree_root())?;
+    let templates = world.get::<ItemTemplates>(instance.owner)?;
+    let source = templates.hosted.get(instance.template)?.source.clone()?;
+    Some((source, instance.index))
+}
+
+fn list_element(value: &dyn PartialReflect, index: usize) -> Option<&dyn PartialReflect> {
+    match value.reflect_ref() {
+        ReflectRef::List(list)   => list.get(index),
+        ReflectRef::Array(array) => array.get(index),
+        _ => None,
+    }
+}
+
+fn list_element_mut(value: &mut dyn PartialReflect, index: usize) -> Option<&mut dyn PartialReflect> {
+    match value.reflect_mut() {
+        ReflectMut::List(list)   => list.get_mut(index),
+        ReflectMut::Array(array) => array.get_mut(index),
+        _ => None,
+    }
+}
+
+///`None` if a segment does not exist, an index is out of bounds or an optional segment is `None`
+fn path_element<'r>(path: &BindingPath, value: &'r dyn PartialReflect) -> Option<&'r dyn PartialReflect> {
+    path.segments.iter().try_fold(value, |value, segment| segment_element(segment, value))
+}
+
+fn path_element_mut<'r>(path: &BindingPath, value: &'r mut dyn PartialReflect) -> Option<&'r mut dyn PartialReflect> {
+    path.segments.iter().try_fold(value, |value, segment| segment_element_mut(segment, value))
+}
+
+fn segment_element<'r>(segment: &PathSegment, value: &'r dyn PartialReflect) -> Option<&'r dyn PartialReflect> {
+    let element = match (&segment.access, value.reflect_ref()) {
+        (PathAccess::Field(name),       ReflectRef::Struct(value))      => value.field(name),
+        (PathAccess::Field(name),       ReflectRef::Enum(value))        => value.field(name),
+        (PathAccess::TupleIndex(index), ReflectRef::TupleStruct(value)) => value.field(*index),
+        (PathAccess::TupleIndex(index), ReflectRef::Tuple(value))       => value.field(*index),
+        (PathAccess::TupleIndex(index), ReflectRef::Enum(value))        => value.field_at(*index),
+        (PathAccess::Index(index),      _)                              => list_element(value, *index),
+        (PathAccess::Key(key),          ReflectRef::Map(map))           => map.get(key.as_partial_reflect()),
+        _ => None,
+    }?;
+
+    if !segment.optional {
+        return Some(element);
+    }
+
+    match element.reflect_ref() {
+        ReflectRef::Enum(option) if option.variant_name() == "Some" => option.field_at(0),
+        _ => None,
+    }
+}
+
+fn segment_element_mut<'r>(segment: &PathSegment, value: &'r mut dyn PartialReflect) -> Option<&'r mut dyn PartialReflect> {
+    let element = match (&segment.access, value.reflect_mut()) {
+        (PathAccess::Field(name),       ReflectMut::Struct(value))      => value.field_mut(name),
+        (PathAccess::Field(name),       ReflectMut::Enum(value))        => value.field_mut(name),
+        (PathAccess::TupleIndex(index), ReflectMut::TupleStruct(value)) => value.field_mut(*index),
+        (PathAccess::TupleIndex(index), ReflectMut::Tuple(value))       => value.field_mut(*index),
+        (PathAccess::TupleIndex(index), ReflectMut::Enum(value))        => value.field_at_mut(*index),
+        (PathAccess::Index(index),      ReflectMut::List(list))         => list.get_mut(*index),
+        (PathAccess::Index(index),      ReflectMut::Array(array))       => array.get_mut(*index),
+        (PathAccess::Key(key),          ReflectMut::Map(map))           => map.get_mut(key.as_partial_reflect()),
+        _ => None,
+    }?;
+
+    if !segment.optional {
+        return Some(element);
+    }
+
+    match element.reflect_mut() {
+        ReflectMut::Enum(option) if option.variant_name() == "Some" => option.field_at_mut(0),
+        _ => None,
+    }
+}
+
+pub(crate) struct BoundAttribute {
+    pub injector:    Arc<Box<dyn Injector>>,
+    pub attribute:   String,
+    pub source:      BindingSource,
+    pub mode:        BindingMode,
+    pub converter:   Option<Arc<dyn UiConverter>>,
+    pub fallback:    Option<Arc<dyn PartialReflect>>,
+    ///Fallback is injected and no source value was read since
+    pub fallen_back: bool,
+    ///Last source value that was synchronized in either direction
+    pub last:        Option<Box<dyn PartialReflect>>,
+}
+
+#[derive(Component, Default)]
+pub(crate) struct UiBindings(pub Vec<BoundAttribute>);
+
+///Source, mode and converter of a resolved binding
+pub(crate) type ResolvedBinding = (BindingSource, BindingMode, Option<Arc<dyn UiConverter>>);
+
+///Resolves binding targets against the reflected types of the app
+pub(crate) struct BindingResolver<'a> {
+    pub registry:   &'a TypeRegistry,
+    pub library:    &'a XmlLibrary,
+    ///Paths declared with `<Use>`
+    pub usings:     &'a [String],
+    pub properties: &'a [CompiledProperty],
+}
+
+impl<'a> BindingResolver<'a> {
+    pub fn resolve(&self, binding: &AttributeBinding) -> Option<ResolvedBinding> {
+        let AttributeValue::Interpolated(parts) = &binding.value else {
+            return self.resolve_value(&binding.attribute, &binding.value);
+        };
+
+        let parts = parts.iter()
+            .map(|part| match part {
+                InterpolationPart::Text(text) => Some(InterpolationSource::Text(text.clone())),
+                InterpolationPart::Binding(value) => {
+                    let (source, _, converter) = self.resolve_value(&binding.attribute, value)?;
+                    let fallback = fallback(value).map(str::to_string);
+                    Some(InterpolationSource::Binding { source, converter, fallback })
+                }
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        //Interpolated text is re-rendered whenever any of its parts changes
+        Some((BindingSource::Interpolated(parts.into()), BindingMode::Read, None))
+    }
+
+    fn resolve_value(&self, attribute: &str, value: &AttributeValue) -> Option<ResolvedBinding> {
+        match value {
+            AttributeValue::Resource(value) => {
+                let params = &value.base_params;
+                let source = self.resource(&params.target, &params.path)?;
+                let converter = self.converter(&value.additional_params.converter)?;
+                Some((source, value.additional_params.mode, converter))
+            }
+            AttributeValue::Component(value) => {
+                let params = &value.base_params;
+                let source = self.component(&params.target, &params.path, &value.additional_params.filters)?;
+                let converter = self.converter(&value.additional_params.converter)?;
+                Some((source, value.additional_params.mode, converter))
+            }
+            AttributeValue::Item(value) => {
+                let path = value.base_params.path.clone();
+                let converter = self.converter(&value.additional_params.converter)?;
+                Some((BindingSource::Item(path), value.additional_params.mode, converter))
+            }
+            AttributeValue::Property(name) => {
+                let Some(property) = self.properties.iter().find(|property| property.name == *name) else {
+                    error!("[Binding] Property '{}' is not declared", name);
+                    return None;
+                };
+                Some((BindingSource::Property(property.key), BindingMode::Read, None))
+            }
+            _ => {
+                error!("[Binding] Unsupported binding on attribute '{}'", attribute);
+                None
+            }
+        }
+    }
+
+    ///Source of `<If test>` or `<Switch value>`, the mode of the binding is ignored
+    pub fn resolve_condition(&self, value: &AttributeValue) -> Option<(BindingSource, Option<Arc<dyn UiConverter>>)> {
+        self.resolve_value("test", value).map(|(source, _, converter)| (source, converter))
+    }
+
+    pub fn resolve_template(&self, source: &TemplateBinding) -> Option<BindingSource> {
+        match source {
+            TemplateBinding::Resource(value) => self.resource(&value.base_params.target, &value.base_params.path),
+            TemplateBinding::Component(value) => {
+                let params = &value.base_params;
+                self.component(&params.target, &params.path, &value.additional_params.filters)
+            }
+        }
+    }
+
+    ///`None` if the converter is not registered, `Some(None)` if the binding has no converter
+    fn converter(&self, name: &Option<String>) -> Option<Option<Arc<dyn UiConverter>>> {
+        let Some(name) = name else {
+            return Some(None);
+        };
+
+        match self.library.get_converter(name) {
+            Some(converter) => Some(Some(converter)),
+            None => {
+                error!("[Binding] Converter '{}' is not registered", name);
+                None
+            }
+        }
+    }
+
+    fn resource(&self, target: &str, path: &BindingPath) -> Option<BindingSource> {
+        let Some(registration) = self.registration(target) else {
+            error!("[Binding] Type '{}' is not registered", target);
+            return None;
+        };
+
+        let Some(resource) = registration.data::<ReflectResource>() else {
+            error!("[Binding] Type '{}' does not reflect Resource", target);
+            return None;
+        };
+
+        Some(BindingSource::Resource {
+            resource: resource.clone(),
+            path:     path.clone(),
+        })
+    }
+
+    fn component(&self, target: &str, path: &BindingPath, filters: &Filters) -> Option<BindingSource> {
+        let Some(registration) = self.registration(target) else {
+            error!("[Binding] Type '{}' is not registered", target);
+            return None;
+        };
+
+        let Some(component) = registration.data::<ReflectComponent>() else {
+            error!("[Binding] Type '{}' does not reflect Component", target);
+            return None;
+        };
+
+        let filters = filters.iter()
+            .map(|filter| self.filter(filter))
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(BindingSource::Component {
+            component: component.clone(),
+            source:    Arc::new(SourceQuery::new(registration.type_id(), filters)),
+            path:      path.clone(),
+        })
+    }
+
+    fn filter(&self, filter: &Filter) -> Option<SourceFilter> {
+        let (name, filter): (_, fn(TypeId) -> SourceFilter) = match filter {
+            Filter::With(name)    => (name, SourceFilter::With),
+            Filter::Without(name) => (name, SourceFilter::Without),
+            Filter::Changed(name) => (name, SourceFilter::Changed),
+            Filter::Added(name)   => (name, SourceFilter::Added),
+            Filter::Or(filters)   => {
+                let filters = filters.iter()
+                    .map(|filter| self.filter(filter))
+                    .collect::<Option<Vec<_>>>()?;
+                return Some(SourceFilter::Or(filters));
+            }
+        };
+
+        let Some(registration) = self.registration(name) else {
+            error!("[Binding] Filter type '{}' is not registered", name);
+            return None;
+        };
+        Some(filter(registration.type_id()))
+    }
+
+    ///Looks the type up by its full path, through the `<Use>` paths, then by its short path
+    fn registration(&self, target: &str) -> Option<&'a TypeRegistration> {
+        if let Some(registration) = self.registry.get_with_type_path(target) {
+            return Some(registration);
+        }
+
+        self.usings.iter()
+            .find_map(|using| {
+                let path = match using.strip_suffix("::*") {
+                    Some(module) => format!("{module}::{target}"),
+                    None if using.rsplit("::").next() == Some(target) => using.clone(),
+                    None => return None,
+                };
+                self.registry.get_with_type_path(&path)
+            })
+            .or_else(|| self.registry.get_with_short_type_path(target))
+    }
+}
+
+fn clone_value(value: &dyn PartialReflect) -> Option<Box<dyn PartialReflect>> {
+    match value.reflect_clone() {
+        Ok(value) => Some(value.into_partial_reflect()),
+        Err(error) => {
+            error!("[Binding] {}", error);
+            None
+        }
+    }
+}
+
+fn is_same(value: &dyn PartialReflect, last: &Option<Box<dyn PartialReflect>>) -> bool {
+    last.as_ref()
+        .and_then(|last| value.reflect_partial_eq(last.as_ref()))
+        .unwrap_or(false)
+}
+
+pub(crate) fn sync_bindings(
+    world:    &mut World,
+    entities: &mut QueryState<Entity, (With<UiBindings>, With<UiContext>)>,
+) {
+    let server: AssetServer = world.resource::<AssetServer>().clone();
+    let entities: Vec<Entity> = entities.iter(world).collect();
+
+    for entity in entities {
+        let context: UiContext = world.get::<UiContext>(entity).unwrap().clone();
+        let mut bindings = std::mem::take(&mut world.get_mut::<UiBindings>(entity).unwrap().bypass_change_detection().0);
+
+        bindings.retain_mut(|binding| {
+            let readable = matches!(binding.mode, BindingMode::Read | BindingMode::ReadWrite | BindingMode::ReadOnce);
+            let writable = matches!(binding.mode, BindingMode::Write | BindingMode::ReadWrite);
+
+            if readable && !binding.source.is_unchanged(world) {
+                let rendered = binding.source.render(world, &context, &server);
+                let value = match &rendered {
+                    Some(text) => Some(text as &dyn PartialReflect),
+                    None => binding.source.read(world, &context),
+                };
+                if value.is_none() && !binding.fallen_back {
+                    if let Some(fallback) = binding.fallback.clone() {
+                        let mut extractor: Extractor = Extractor::new(world, entity);
+                        binding.injector.inject_value(&binding.attribute, &ValueStorage::reflect(fallback.as_ref()), &mut extractor, &server);
+                        binding.fallen_back = true;
+                        binding.last = None;
+                    }
+                    return true;
+                }
+
+                let value = value
+                    .filter(|value| !is_same(*value, &binding.last))
+                    .and_then(clone_value);
+
+                if let Some(value) = value {
+                    let converted = match &binding.converter {
+                        Some(converter) => converter.convert(value.as_ref(), &server),
+                        None => clone_value(value.as_ref()),
+                    };
+
+                    let Some(converted) = converted else {
+                        error!("[Binding] Could not convert '{}' for attribute '{}'", value.reflect_type_path(), binding.attribute);
+                        binding.last = Some(value);
+                        return true;
+                    };
+
+                    let mut extractor: Extractor = Extractor::new(world, entity);
+                    let storage: ValueStorage = ValueStorage::reflect(converted.as_ref());
+                    binding.injector.inject_value(&binding.attribute, &storage, &mut extractor, &server);
+                    binding.last = Some(value);
+                    binding.fallen_back = false;
+
+                    //The update was skipped, the fallback keeps the attribute in a known state
+                    if storage.is_mismatched() && let Some(fallback) = binding.fallback.clone() {
+                        binding.injector.inject_value(&binding.attribute, &ValueStorage::reflect(fallback.as_ref()), &mut extractor, &server);
+                        binding.fallen_back = true;
+                    }
+
+                    return binding.mode != BindingMode::ReadOnce;
+                }
+            }
+
+            if writable {
+                let extractor: Extractor = Extractor::new(world, entity);
+                let value = binding.injector.extract_value(&binding.attribute, &extractor)
+                    .and_then(|value| match &binding.converter {
+                        Some(converter) => converter.convert_back(value.as_ref()),
+                        None => Some(value),
+                    })
+                    .filter(|value| !is_same(value.as_ref(), &binding.last));
+
+                if let Some(value) = value
+                    && binding.source.write(world, &context, value.as_ref()) {
+                    binding.last = Some(value);
+                }
+            }
+
+            true
+        });
+
+        if let Some(mut target) = world.get_mut::<UiBindings>(entity) {
+            target.bypass_change_detection().0 = bindings;
+        }
+    }
+}
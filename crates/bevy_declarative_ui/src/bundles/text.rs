@@ -1,4 +1,4 @@
-use bevy::prelude::{EntityCommands, AssetServer};
+use bevy::prelude::{EntityCommands, AssetServer, PartialReflect};
 use crate::prelude::{
     XmlComponent,
     Extractor,
@@ -22,6 +22,13 @@ impl Injector for TextBundleInjector {
         TextColorInjector.inject_value(name, value, extractor, server);
         TextFontInjector.inject_value(name, value, extractor, server);
     }
+
+    fn parse_value(&self, name: &str, value: &str) -> Option<Result<Box<dyn PartialReflect>, String>> {
+        TextInjector.parse_value(name, value)
+            .or_else(|| TextFontInjector.parse_value(name, value))
+            .or_else(|| TextColorInjector.parse_value(name, value))
+            .or_else(|| NodeInjector.parse_value(name, value))
+    }
 }
 
 #[derive(Default, Debug, Clone)]
@@ -52,20 +59,9 @@ impl XmlComponent for TextBundleParser {
     }
 
     fn parse_attribute(&mut self, name: &str, value: &str) -> bool {
-        if self.text_parser.parse_attribute(name, value) {
-            true
-        }
-        else if self.text_font_parser.parse_attribute(name, value) {
-            true
-        }
-        else if self.text_color_parser.parse_attribute(name, value) {
-            true
-        }
-        else if self.node_parser.parse_attribute(name, value) {
-            true
-        }
-        else {
-            false
-        }
+        self.text_parser.parse_attribute(name, value)
+            || self.text_font_parser.parse_attribute(name, value)
+            || self.text_color_parser.parse_attribute(name, value)
+            || self.node_parser.parse_attribute(name, value)
     }
 }
\ No newline at end of file
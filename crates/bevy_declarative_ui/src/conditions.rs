@@ -0,0 +1,103 @@
+use std::sync::Arc;
+use bevy::prelude::*;
+use bevy::reflect::ReflectRef;
+use bevy_declarative_ui_parser::ConditionTest;
+use crate::bindings::BindingSource;
+use crate::converters::display;
+use crate::prelude::*;
+
+///Display of an `<If>`, `<Else>` or `<Case>` container, kept while the container is hidden with `Display::None`
+#[derive(Component, Clone, Copy, Default, Debug)]
+#[require(Node)]
+pub struct ConditionalDisplay(Option<Display>);
+
+impl ConditionalDisplay {
+    pub const fn is_shown(&self) -> bool {
+        self.0.is_none()
+    }
+
+    ///Hides the node or restores the display it had before it was hidden
+    pub fn show(&mut self, display: &mut Display, shown: bool) {
+        match (shown, self.0) {
+            (true, Some(previous)) => {
+                *display = previous;
+                self.0 = None;
+            }
+            (false, None) => {
+                self.0 = Some(*display);
+                *display = Display::None;
+            }
+            _ => {}
+        }
+    }
+}
+
+///Whether `<If>` shows its container: `true` and `Some` values do
+pub fn condition_is_true(value: &dyn PartialReflect) -> bool {
+    if let Some(value) = value.try_downcast_ref::<bool>() {
+        return *value;
+    }
+
+    match value.reflect_ref() {
+        ReflectRef::Enum(option) if option.reflect_type_ident() == Some("Option") => option.variant_name() == "Some",
+        _ => false,
+    }
+}
+
+///Whether `<Case equals>` shows its container, the value is compared as displayed text
+pub fn condition_equals(value: &dyn PartialReflect, equals: &str) -> bool {
+    display(value) == equals
+}
+
+///Condition of a spawned container
+#[derive(Component)]
+pub(crate) struct UiCondition {
+    pub source:    BindingSource,
+    pub converter: Option<Arc<dyn UiConverter>>,
+    pub test:      ConditionTest,
+}
+
+impl UiCondition {
+    ///A missing source hides `<If>` and `<Case>` and shows `<Else>`
+    fn holds(&self, world: &World, context: &UiContext, server: &AssetServer) -> bool {
+        let value = self.source.read(world, context);
+        let converted = match (&self.converter, value) {
+            (Some(converter), Some(value)) => converter.convert(value, server),
+            _ => None,
+        };
+        let value = match &self.converter {
+            Some(_) => converted.as_deref(),
+            None => value,
+        };
+
+        match &self.test {
+            ConditionTest::True => value.is_some_and(condition_is_true),
+            ConditionTest::False => !value.is_some_and(condition_is_true),
+            ConditionTest::Equals(equals) => value.is_some_and(|value| condition_equals(value, equals)),
+        }
+    }
+}
+
+pub(crate) fn sync_conditions(
+    world:    &mut World,
+    entities: &mut QueryState<(Entity, &UiCondition, &UiContext, &ConditionalDisplay)>,
+) {
+    let server: AssetServer = world.resource::<AssetServer>().clone();
+    let changes: Vec<(Entity, bool)> = entities.iter(world)
+        .filter_map(|(entity, condition, context, display)| {
+            let shown = condition.holds(world, context, &server);
+            (shown != display.is_shown()).then_some((entity, shown))
+        })
+        .collect();
+
+    for (entity, shown) in changes {
+        let mut entity = world.entity_mut(entity);
+        let Some(mut display) = entity.get::<ConditionalDisplay>().copied() else {
+            continue;
+        };
+        if let Some(mut node) = entity.get_mut::<Node>() {
+            display.show(&mut node.display, shown);
+        }
+        entity.insert(display);
+    }
+}
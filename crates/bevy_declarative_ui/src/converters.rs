@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+use crate::XmlLibrary;
+
+///Converts bound values between the source type and the attribute type
+pub trait UiConverter: Send + Sync + 'static {
+    ///Source value to attribute value
+    fn convert(&self, value: &dyn PartialReflect, server: &AssetServer) -> Option<Box<dyn PartialReflect>>;
+
+    ///Attribute value back to source value, used by `Write` and `ReadWrite` bindings
+    fn convert_back(&self, _value: &dyn PartialReflect) -> Option<Box<dyn PartialReflect>> {
+        None
+    }
+}
+
+///Conversions used by the generated bindings, the converter is referenced by its type name.
+///`None` if the converter rejects the value or returns another type
+pub trait ConvertValue {
+    fn convert_to<T: FromReflect>(&self, converter: &impl UiConverter, server: &AssetServer) -> Option<T>;
+    fn convert_from<T: FromReflect>(&self, converter: &impl UiConverter) -> Option<T>;
+}
+
+impl<V: PartialReflect> ConvertValue for V {
+    fn convert_to<T: FromReflect>(&self, converter: &impl UiConverter, server: &AssetServer) -> Option<T> {
+        let value = converter.convert(self.as_partial_reflect(), server).and_then(|value| T::from_reflect(value.as_ref()));
+        if value.is_none() {
+            error!("Could not convert '{}' to '{}'", self.reflect_type_path(), std::any::type_name::<T>());
+        }
+        value
+    }
+
+    fn convert_from<T: FromReflect>(&self, converter: &impl UiConverter) -> Option<T> {
+        let value = converter.convert_back(self.as_partial_reflect()).and_then(|value| T::from_reflect(value.as_ref()));
+        if value.is_none() {
+            error!("Could not convert '{}' back to '{}'", self.reflect_type_path(), std::any::type_name::<T>());
+        }
+        value
+    }
+}
+
+pub fn add_converters(library: &mut XmlLibrary) {
+    library.add_converter("BoolToDisplay",    BoolToDisplay);
+    library.add_converter("BoolToVisibility", BoolToVisibility);
+    library.add_converter("Percent",          Percent);
+    library.add_converter("ToText",           ToText::default());
+    library.add_converter("ImagePath",        ImagePath);
+}
+
+///`true` -> `Display::Flex`, `false` -> `Display::None`
+#[derive(Default)]
+pub struct BoolToDisplay;
+
+impl UiConverter for BoolToDisplay {
+    fn convert(&self, value: &dyn PartialReflect, _: &AssetServer) -> Option<Box<dyn PartialReflect>> {
+        let display = if *value.try_downcast_ref::<bool>()? { Display::Flex } else { Display::None };
+        Some(Box::new(display))
+    }
+
+    fn convert_back(&self, value: &dyn PartialReflect) -> Option<Box<dyn PartialReflect>> {
+        Some(Box::new(*value.try_downcast_ref::<Display>()? != Display::None))
+    }
+}
+
+///`true` -> `Visibility::Inherited`, `false` -> `Visibility::Hidden`
+#[derive(Default)]
+pub struct BoolToVisibility;
+
+impl UiConverter for BoolToVisibility {
+    fn convert(&self, value: &dyn PartialReflect, _: &AssetServer) -> Option<Box<dyn PartialReflect>> {
+        let visibility = if *value.try_downcast_ref::<bool>()? { Visibility::Inherited } else { Visibility::Hidden };
+        Some(Box::new(visibility))
+    }
+
+    fn convert_back(&self, value: &dyn PartialReflect) -> Option<Box<dyn PartialReflect>> {
+        Some(Box::new(*value.try_downcast_ref::<Visibility>()? != Visibility::Hidden))
+    }
+}
+
+///Number -> `Val::Percent`
+#[derive(Default)]
+pub struct Percent;
+
+impl UiConverter for Percent {
+    fn convert(&self, value: &dyn PartialReflect, _: &AssetServer) -> Option<Box<dyn PartialReflect>> {
+        Some(Box::new(Val::Percent(as_f64(value)? as f32)))
+    }
+
+    fn convert_back(&self, value: &dyn PartialReflect) -> Option<Box<dyn PartialReflect>> {
+        match value.try_downcast_ref::<Val>()? {
+            Val::Percent(percent) => Some(Box::new(*percent)),
+            _ => None,
+        }
+    }
+}
+
+///Number -> `String`, formatted with `{}` or `{:.N}` placeholder
+pub struct ToText {
+    format: String,
+}
+
+impl ToText {
+    pub fn new(format: impl Into<String>) -> Self {
+        Self { format: format.into() }
+    }
+}
+
+impl Default for ToText {
+    fn default() -> Self {
+        Self::new("{}")
+    }
+}
+
+impl UiConverter for ToText {
+    fn convert(&self, value: &dyn PartialReflect, _: &AssetServer) -> Option<Box<dyn PartialReflect>> {
+        let number = as_f64(value)?;
+        let Some(start) = self.format.find('{') else {
+            return Some(Box::new(self.format.clone()));
+        };
+        let end = start + self.format[start..].find('}')?;
+
+        let text = match self.format[start + 1..end].strip_prefix(":.") {
+            Some(precision) => format!("{number:.0$}", precision.parse::<usize>().ok()?),
+            None => format!("{number}"),
+        };
+        Some(Box::new(format!("{}{}{}", &self.format[..start], text, &self.format[end + 1..])))
+    }
+}
+
+///Asset path -> `Handle<Image>`
+#[derive(Default)]
+pub struct ImagePath;
+
+impl UiConverter for ImagePath {
+    fn convert(&self, value: &dyn PartialReflect, server: &AssetServer) -> Option<Box<dyn PartialReflect>> {
+        let path = value.try_downcast_ref::<String>()?;
+        Some(Box::new(server.load::<Image>(path.clone())))
+    }
+}
+
+///Text of a bound value inside an interpolated attribute
+pub(crate) fn display(value: &dyn PartialReflect) -> String {
+    macro_rules! display {
+        ($($ty:ty),*) => {
+            $(if let Some(value) = value.try_downcast_ref::<$ty>() {
+                return value.to_string();
+            })*
+        };
+    }
+
+    display!(String, bool, char, f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+    format!("{value:?}")
+}
+
+fn as_f64(value: &dyn PartialReflect) -> Option<f64> {
+    macro_rules! numbers {
+        ($($ty:ty),*) => {
+            $(if let Some(value) = value.try_downcast_ref::<$ty>() {
+                return Some(*value as f64);
+            })*
+        };
+    }
+
+    numbers!(f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+    None
+}
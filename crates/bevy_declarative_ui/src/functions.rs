@@ -124,8 +124,8 @@ impl UiFunctions {
         self.events.insert(name, Box::new(handler));
     }
 
-    pub fn get_event_handler(&self, name: &str) -> Option<&Box<dyn EventHandler>> {
-        self.events.get(name)
+    pub fn get_event_handler(&self, name: &str) -> Option<&dyn EventHandler> {
+        self.events.get(name).map(Box::as_ref)
     }
 }
 
@@ -143,9 +143,3 @@ pub trait AttributeCallback: Send + Sync + 'static {
     fn insert_callback(&self, entity: &mut EntityCommands);
     fn type_id(&self) -> TypeId;
 }
-
-#[derive(Debug, Clone)]
-pub(crate) struct AttributeProperty {
-    pub(crate) attribute: String,
-    pub(crate) property: String,
-}
\ No newline at end of file
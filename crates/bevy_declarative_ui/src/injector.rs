@@ -1,16 +1,17 @@
-use std::any::TypeId;
-use std::collections::HashMap;
-use std::sync::Arc;
 use bevy::asset::AssetServer;
-use bevy::prelude::Component;
+use bevy::prelude::PartialReflect;
 use crate::prelude::{Extractor, ValueStorage};
 
 pub trait Injector: Send + Sync + 'static {
     fn inject_value(&self, name: &str, value: &ValueStorage, extractor: &mut Extractor, server: &AssetServer);
-}
 
-#[derive(Component, Default)]
-pub(crate) struct ValueInjectors {
-    //1 - Property itself, 2 - Component, 3 - name of attribute
-    pub injectors: HashMap<TypeId, Vec<(Arc<Box<dyn Injector>>, String)>>,
-}
\ No newline at end of file
+    ///Current value of the attribute, used by `Write` and `ReadWrite` bindings
+    fn extract_value(&self, _name: &str, _extractor: &Extractor) -> Option<Box<dyn PartialReflect>> {
+        None
+    }
+
+    ///Parses the binding fallback into the value `inject_value` expects, `None` for unknown attributes
+    fn parse_value(&self, _name: &str, _value: &str) -> Option<Result<Box<dyn PartialReflect>, String>> {
+        None
+    }
+}
@@ -1,27 +1,55 @@
- use std::any::TypeId;
-use std::collections::{HashMap, HashSet};
+use std::any::TypeId;
 use std::fmt::{Debug, Formatter};
 use bevy::prelude::*;
-use bevy_declarative_ui_parser::{Resources, UiNode};
-use bevy_declarative_ui_parser::attribute_value::AttributeValue;
+use bevy_declarative_ui_parser::{condition_attribute, Condition, UiNode, XmlLayoutError};
+use bevy_declarative_ui_parser::into::Tag;
+use bevy_declarative_ui_parser::values::{AttributeValue, InterpolationPart, TemplateBinding};
+use std::collections::HashMap;
+use std::sync::Arc;
 use crate::prelude::*;
-use crate::resources::Storage;
-use crate::xml_parser::LayoutPath;
 use crate::XmlLibrary;
 
 #[derive(Default)]
 pub struct CompiledLayout {
     pub(crate) root:      CompiledNode,
-    pub(crate) local:     UiResources,
-    pub(crate) global:    UiResources,
-    pub(crate) types:     HashMap<String, TypeId>,
-    pub(crate) templates: HashMap<String, Template>,
+    pub(crate) templates: Vec<Template>,
+    ///Paths declared with `<Use>`, sorted
+    pub(crate) usings:    Vec<String>,
+    ///Properties with a storage registered for the layout
+    pub(crate) properties: Vec<CompiledProperty>,
+}
+
+#[allow(deprecated)]
+pub(crate) struct CompiledProperty {
+    pub name:    String,
+    pub key:     TypeId,
+    pub global:  bool,
+    pub factory: StorageFactory,
+    pub r#type:  String,
+    pub value:   String,
+}
+
+impl CompiledProperty {
+    ///Storage holding the declared value, `None` if its type is not registered
+    #[allow(deprecated)]
+    pub fn create(&self, library: &XmlLibrary) -> Option<Storage> {
+        let Some(parser) = library.types.get(self.r#type.as_str()) else {
+            error!("[Property] Type '{}' of property '{}' is not registered", self.r#type, self.name);
+            return None;
+        };
+
+        let mut storage = (self.factory)();
+        parser.write_to_storage(&self.value, &mut MutValueStorage::new(&mut storage));
+        Some(Storage::new(storage, false))
+    }
 }
 
 pub(crate) struct Template {
-    pub allowed_containers: HashSet<String>,
-    //pub local: UiResources,
-    pub root: CompiledNode
+    pub id:     String,
+    ///Id of the container which hosts the instances
+    pub owner:  String,
+    pub source: TemplateBinding,
+    pub root:   CompiledNode,
 }
 
 impl Debug for CompiledLayout {
@@ -32,19 +60,28 @@ impl Debug for CompiledLayout {
 
 #[derive(Debug)]
 pub(crate) struct Component {
-    pub value: Box<dyn XmlComponent>,
-    pub properties: Vec<AttributeProperty>
+    pub value:    Box<dyn XmlComponent>,
+    pub bindings: Vec<AttributeBinding>,
 }
 
 impl Clone for Component {
     fn clone(&self) -> Self {
         Self {
-            value: dyn_clone::clone_box(&*self.value),
-            properties: self.properties.clone(),
+            value:    dyn_clone::clone_box(&*self.value),
+            bindings: self.bindings.clone(),
         }
     }
 }
 
+///Attribute whose value is resolved at runtime
+#[derive(Debug, Clone)]
+pub(crate) struct AttributeBinding {
+    pub attribute: String,
+    pub value:     AttributeValue,
+    ///Parsed `Fallback` parameter, applied while the source is missing
+    pub fallback:  Option<Arc<dyn PartialReflect>>,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct Function {
     pub value: String,
@@ -57,16 +94,32 @@ pub(crate) enum FunctionType {
     CallFunction(String),
 }
 
+impl Function {
+    ///Accepts `name` and `name(args)`
+    fn parse(value: &str) -> Self {
+        match value.strip_suffix(')').and_then(|value| value.split_once('(')) {
+            Some((name, args)) => Function {
+                value: name.trim().to_string(),
+                kind:  FunctionType::CallFunction(args.trim().to_string()),
+            },
+            None => Function {
+                value: value.trim().to_string(),
+                kind:  FunctionType::Value,
+            },
+        }
+    }
+}
+
 #[derive(Default, Debug)]
 pub(crate) struct CompiledNode {
     pub(crate) components: Vec<Component>,
-
     pub(crate) containers: Vec<CompiledNode>,
-    pub(crate) properties: HashMap<String, String>,
 
     /// Name -> Function
     pub(crate) functions:  HashMap<String, Function>,
-    pub(crate) id: Option<String>,
+    pub(crate) id:         String,
+    ///Shows the container of `<If>`, `<Else>` and `<Case>`
+    pub(crate) condition:  Option<Condition>,
 }
 
 impl Clone for CompiledNode {
@@ -74,16 +127,16 @@ impl Clone for CompiledNode {
         Self {
             components: self.components.clone(),
             containers: self.containers.clone(),
-            properties: self.properties.clone(),
             functions:  self.functions.clone(),
-            id: self.id.clone(),
+            id:         self.id.clone(),
+            condition:  self.condition.clone(),
         }
     }
 }
 
 pub(crate) struct LayoutCompiler<'a> {
     library: &'a XmlLibrary,
-    layout: &'a XmlLayout,
+    layout:  &'a XmlLayout,
 }
 
 impl<'a> LayoutCompiler<'a> {
@@ -91,66 +144,108 @@ impl<'a> LayoutCompiler<'a> {
         Self { library, layout }
     }
 
-    fn compile_container(&self, node: &UiNode) -> CompiledNode {
-        let mut compiled_node: CompiledNode = CompiledNode::default();
+    fn compile_container(&self, node: &UiNode) -> Result<CompiledNode, XmlLayoutError> {
+        let mut compiled_node: CompiledNode = CompiledNode {
+            id:        node.id.to_string(),
+            condition: node.condition.clone(),
+            ..default()
+        };
+        //Read by the parser, e.g. `test` of `<If>`
+        let condition = condition_attribute(&node.tag.name);
 
-        node.children.iter().for_each(|node| {
-            self.compile_node(&node, &mut compiled_node);
-        });
+        //Classes are resolved first, the inline attributes win over them
+        for tag in &node.styled_components(&self.layout.styles) {
+            compiled_node.components.push(self.compile_component(tag)?);
+        }
+
+        for node in &node.children {
+            compiled_node.containers.push(self.compile_container(node)?);
+        }
 
         node.tag.attributes.iter().for_each(|attr| {
-            match attr.name.as_str() {
-                "id" => compiled_node.id = Some(attr.value.read_value()),
-                val if self.library.functions.contains_key(val) => {
-                    let function = match &attr.value {
-                        AttributeValue::Binding(value) => Function {
-                            value: value.clone(),
-                            kind: FunctionType::Value,
-                        },
-                        AttributeValue::CallFunction { name, args } => {
-                            Function {
-                                value: name.clone(),
-                                kind: FunctionType::CallFunction(args.join("")),
-                            }
-                        },
-                        _ => panic!("Unsupported binding type"),
-                    };
-                    compiled_node.functions.insert(attr.name.to_string(), function);
+            match (attr.name.as_str(), &attr.value) {
+                ("id", _) | ("class", _) => {},
+                (name, _) if condition == Some(name) => {},
+                (name, AttributeValue::Value(value)) if self.library.functions.contains_key(name) => {
+                    compiled_node.functions.insert(attr.name.to_string(), Function::parse(value));
+                },
+                (name, _) if self.library.functions.contains_key(name) => {
+                    error!("[Container] Function attribute '{}' expects a function name", name);
                 },
                 _ => error!("[Container] Unknown attribute: {}", attr.name),
             }
         });
 
-        compiled_node
+        Ok(compiled_node)
     }
 
-    fn compile_component(&self, node: &UiNode) -> Component {
-        let name = &node.tag.name;
-        let mut properties: Vec<AttributeProperty> = Vec::new();
-        let mut component: Box<dyn XmlComponent> = self.library.get_component(name);
-        node.tag.attributes.iter().for_each(|attr| {
-
+    fn compile_component(&self, tag: &Tag) -> Result<Component, XmlLayoutError> {
+        let name = &tag.name;
+        let mut bindings: Vec<AttributeBinding> = Vec::new();
+        let Some(mut component) = self.library.get_component(tag) else {
+            return Err(XmlLayoutError::UnknownComponent {
+                file:      self.layout.path.clone(),
+                name:      name.clone(),
+                namespace: tag.namespace.as_deref().unwrap_or(XmlLibrary::DEFAULT_NAMESPACE).to_string(),
+            });
+        };
+        tag.attributes.iter().for_each(|attr| {
             let value = match &attr.value {
-                AttributeValue::Value(value) => &value,
-                AttributeValue::Local(value) | AttributeValue::Global(value) => {
-                    properties.push(AttributeProperty {
+                AttributeValue::Value(value) => value,
+                AttributeValue::Asset(asset) => &asset.path,
+                AttributeValue::Interpolated(parts) => {
+                    //The parts render text, their fallbacks included
+                    let fallbacks = parts.iter().filter_map(|part| match part {
+                        InterpolationPart::Binding(value) => fallback(value),
+                        InterpolationPart::Text(_) => None,
+                    });
+                    let invalid = std::iter::once("").chain(fallbacks).find_map(|fallback| {
+                        match component.as_injector().parse_value(&attr.name, fallback) {
+                            Some(Ok(value)) if value.try_downcast_ref::<String>().is_some() => None,
+                            Some(Ok(_)) => Some(format!("Attribute '{}' does not hold text, it can not be interpolated", attr.name)),
+                            Some(Err(error)) => Some(format!("Invalid fallback '{}' of attribute '{}': {}", fallback, attr.name, error)),
+                            None => Some(format!("Attribute '{}' does not support interpolated values", attr.name)),
+                        }
+                    });
+                    if let Some(message) = invalid {
+                        error!("[{}] {}", name, message);
+                        return;
+                    }
+
+                    bindings.push(AttributeBinding {
                         attribute: attr.name.clone(),
-                        property:  value.clone(),
+                        value:     attr.value.clone(),
+                        fallback:  None,
                     });
+                    return;
+                }
+                AttributeValue::Item(_) | AttributeValue::Resource(_) | AttributeValue::Component(_) | AttributeValue::Property(_) => {
+                    let fallback = match fallback(&attr.value) {
+                        None => None,
+                        Some(fallback) => match component.as_injector().parse_value(&attr.name, fallback) {
+                            Some(Ok(value)) => Some(Arc::from(value)),
+                            Some(Err(error)) => {
+                                error!("[{}] Invalid fallback '{}' of attribute '{}': {}", name, fallback, attr.name, error);
+                                return;
+                            }
+                            None => {
+                                error!("[{}] Attribute '{}' does not support fallback values", name, attr.name);
+                                return;
+                            }
+                        },
+                    };
 
-                    if let Some(prop) = self.layout.local.get(&value) {
-                        &prop.value
-                    }
-                    else if let Some(prop) = self.layout.global.get(&value) {
-                        &prop.value
-                    }
-                    else {
-                        dbg!(&self.layout.local);
-                        dbg!(&self.layout.global);
-                        panic!("TODO: {}", &value);
-                    }
+                    bindings.push(AttributeBinding {
+                        attribute: attr.name.clone(),
+                        value:     attr.value.clone(),
+                        fallback,
+                    });
+                    return;
+                }
+                AttributeValue::Template(_) => {
+                    error!("[{}] Template binding is only allowed in ItemTemplate source: {}", name, attr.name);
+                    return;
                 }
-                other => panic!("Unsupported binding type: {other:?}"),
             };
 
             if !component.parse_attribute(&attr.name, value) {
@@ -158,197 +253,243 @@ impl<'a> LayoutCompiler<'a> {
             }
         });
 
-        Component {
+        Ok(Component {
             value: component,
-            properties,
-        }
+            bindings,
+        })
     }
 
-    fn compile_node(&self, node: &UiNode, compiled_node: &mut CompiledNode) {
-        if node.tag.is_container {
-            compiled_node.containers.push(self.compile_container(node))
-        }
-        else {
-            compiled_node.components.push(self.compile_component(node))
-        }
-    }
+    ///Fails on the first tag which is not registered in the library
+    pub fn compile(&self) -> Result<CompiledLayout, XmlLayoutError> {
+        let mut compiled_layout: CompiledLayout = CompiledLayout::default();
 
-    fn get_types(&self) -> HashMap<String, TypeId> {
-        let mut map: HashMap<String, TypeId> = HashMap::new();
-        self.layout.local.iter().for_each(|(n, _)| {
-            let (type_id, _) = self.library.storages.get(self.layout.path.current.as_str())
-                .expect(&format!("Path '{}' not found", self.layout.path.current))
-                .get(n)
-                .expect(&format!("Type '{}' not found on layout: {}", n, self.layout.path.current));
-            map.insert(n.to_string(), *type_id);
-        });
+        //The parser wraps the document into a single `Root` container
+        if let Some(root) = self.layout.root_nodes.first() {
+            compiled_layout.root = self.compile_container(root)?;
+        }
 
-        self.layout.global.iter().for_each(|(n, _)| {
-            let (type_id, _) = self.library.storages.get(self.layout.path.global.as_str())
-                .expect(&format!("Path '{}' not found", self.layout.path.global))
-                .get(n)
-                .expect(&format!("Type '{}' not found on layout: {}", n, self.layout.path.global));
-            map.insert(n.to_string(), *type_id);
-        });
+        for template in &self.layout.templates {
+            let Some(root) = template.nodes.first() else {
+                continue;
+            };
 
-        map
-    }
+            compiled_layout.templates.push(Template {
+                id:     template.id.to_string(),
+                owner:  template.owner.to_string(),
+                source: template.source.clone(),
+                root:   self.compile_container(root)?,
+            });
+        }
 
-    fn compile_resources(&self, resources: &mut UiResources, path: &str, raw_resources: &Resources, is_inherit: bool) {
-        raw_resources.iter().for_each(|(k, v)| {
-            let (id, factory) = self.library.storages.get(path)
-                .expect(&format!("Path '{}' not found", path))
-                .get(k.as_str()).unwrap();
-            let mut storage: Storage = Storage::new(factory(), is_inherit);
-            if !is_inherit {
-                let parser: &Box<dyn IsTyped> = self.library.types.get(v.type_.as_str())
-                    .expect(&format!("Type '{}' on path '{}' not found", v.type_, path));
-                parser.write_to_storage(v.value.as_str(), &mut MutValueStorage::new(&mut storage.storage));
+        self.layout.properties.iter().for_each(|property| {
+            match self.library.get_property(&self.layout.name, &property.name) {
+                Some((key, factory)) => compiled_layout.properties.push(CompiledProperty {
+                    name:    property.name.clone(),
+                    key,
+                    global:  property.global,
+                    factory,
+                    r#type:  property.r#type.clone(),
+                    value:   property.value.clone(),
+                }),
+                None => error!("[Property] Property '{}' is not registered for layout '{}'", property.name, self.layout.name),
             }
-            resources.add_property_internal(*id, storage);
         });
-    }
 
-    fn compile_local_resources(&self, local: &Resources, global: &Resources) -> UiResources {
-        let mut resources: UiResources = UiResources::default();
-        self.compile_resources(&mut resources, &self.layout.path.global, global, true);
-        self.compile_resources(&mut resources, &self.layout.path.current, local, false);
-        resources
+        compiled_layout.usings = self.layout.usings.iter().map(|using| using.trim().to_string()).collect();
+        compiled_layout.usings.sort();
+        Ok(compiled_layout)
     }
+}
 
-    fn compile_global_resources(&self, global: &Resources) -> UiResources {
-        let mut resources: UiResources = UiResources::default();
-        self.compile_resources(&mut resources, &self.layout.path.global, global, false);
-        resources
-    }
-
-    pub fn compile(&self) -> CompiledLayout {
-        let mut compiled_layout: CompiledLayout = CompiledLayout::default();
-        compiled_layout.types = self.get_types();
-        compiled_layout.global = self.compile_global_resources(&self.layout.global);
-        compiled_layout.local = self.compile_local_resources(&self.layout.local, &self.layout.global);
-
-        self.layout.templates.iter().for_each(|template| {
-            let mut layout = XmlLayout {
-                path: LayoutPath {
-                    current: format!("{}::{}", self.layout.path.current, template.name),
-                    global: self.layout.path.global.clone(),
-                },
-                local: template.resources.clone(),
-                root_nodes: template.nodes.clone(),
-                global: self.layout.global.clone(),
-                templates: vec![],
-            };
-            let compiled_template: CompiledLayout = LayoutCompiler::new(self.library, &mut layout).compile();
-            compiled_layout.types.extend(compiled_template.types);
-            compiled_layout.templates.insert(template.name.clone(), Template {
-                allowed_containers: template.containers.clone(),
-                //local: compiled_template.local,
-                root: compiled_template.root,
-            });
-        });
-
-        let mut root_node: CompiledNode = CompiledNode::default();
-
-        self.layout.root_nodes.iter().for_each(|node| {
-            self.compile_node(node, &mut root_node);
-        });
-
-        compiled_layout.root = root_node;
-        compiled_layout
+pub(crate) fn fallback(value: &AttributeValue) -> Option<&str> {
+    match value {
+        AttributeValue::Item(value)      => value.additional_params.fallback.as_deref(),
+        AttributeValue::Resource(value)  => value.additional_params.fallback.as_deref(),
+        AttributeValue::Component(value) => value.additional_params.fallback.as_deref(),
+        _ => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use bevy::prelude::Color;
-    use bevy_declarative_ui_parser::LayoutReader;
-    use crate::parser::{CompiledLayout, LayoutCompiler};
-    use crate::prelude::{TypedStorage, XmlLayout};
-    use crate::resources::PropertyType;
-    use crate::xml_parser::LayoutPath;
+    use bevy_declarative_ui_parser::{LayoutReader, XmlLayoutError};
+    use bevy_declarative_ui_parser::values::{AttributeValue, TemplateBinding};
+    use crate::parser::{CompiledLayout, FunctionType, LayoutCompiler};
+    use crate::prelude::XmlLayout;
     use crate::XmlLibrary;
 
-    #[derive(Default)]
-    struct Font;
-    impl PropertyType for Font {
-        type Type = String;
-    }
+    const CORRECT_XML: &str = r#"
+    <Layout>
+        <Use>
+            game::Globals
+        </Use>
+        <Node width="100%" height="100%"/>
+        <Container id="PlayerList">
+            <Node width="50%" height="{Resource Target=Globals, Path=height}"/>
+            <Text text="Player list:"/>
+        </Container>
+        <ItemTemplate source="{Resource Players, Path=online}">
+            <Container>
+                <Node width="10%" height="10%"/>
+                <Text text="{Item name}"/>
+            </Container>
+        </ItemTemplate>
+        <Container id="Footer">
+            <ImageNode image="{Asset icons/exit.png}"/>
+        </Container>
+    </Layout>
+"#;
 
-    #[derive(Default)]
-    struct FontColor;
-    impl PropertyType for FontColor {
-        type Type = Color;
+    #[test]
+    fn compile_layout() {
+        let layout = LayoutReader::new(CORRECT_XML, "layout.xml").parse().unwrap();
+        let layout = XmlLayout {
+            templates:  layout.templates,
+            root_nodes: layout.root_nodes,
+            usings:     layout.usings,
+            ..Default::default()
+        };
+        let library = XmlLibrary::default();
+        let compiled_layout: CompiledLayout = LayoutCompiler::new(&library, &layout).compile().unwrap();
+
+        let root = &compiled_layout.root;
+        assert_eq!(root.id, "Root");
+        assert_eq!(root.components.len(), 1);
+        assert_eq!(root.containers.len(), 2);
+
+        let player_list = &root.containers[0];
+        assert_eq!(player_list.id, "PlayerList");
+        assert_eq!(player_list.components.len(), 2);
+        assert_eq!(player_list.components[0].bindings.len(), 1);
+        assert_eq!(player_list.components[0].bindings[0].attribute, "height");
+        assert!(matches!(player_list.components[0].bindings[0].value, AttributeValue::Resource(_)));
+
+        assert_eq!(compiled_layout.templates.len(), 1);
+        let template = &compiled_layout.templates[0];
+        assert_eq!(template.owner, "Root");
+        assert!(matches!(template.source, TemplateBinding::Resource(_)));
+        assert_eq!(template.root.components.len(), 2);
+        assert!(matches!(template.root.components[1].bindings[0].value, AttributeValue::Item(_)));
+
+        assert_eq!(compiled_layout.usings, vec!["game::Globals".to_string()]);
     }
 
-    #[derive(Default)]
-    struct FontSize;
-    impl PropertyType for FontSize {
-        type Type = f32;
+    #[test]
+    fn compile_fallback() {
+        let xml = r#"
+        <Layout>
+            <Node width="{Resource Globals, Path=width, Fallback=10px}" height="{Resource Globals, Path=height, Fallback=tall}"/>
+        </Layout>
+        "#;
+        let layout = LayoutReader::new(xml, "layout.xml").parse().unwrap();
+        let layout = XmlLayout {
+            templates:  layout.templates,
+            root_nodes: layout.root_nodes,
+            usings:     layout.usings,
+            ..Default::default()
+        };
+        let library = XmlLibrary::default();
+        let compiled_layout: CompiledLayout = LayoutCompiler::new(&library, &layout).compile().unwrap();
+
+        let bindings = &compiled_layout.root.components[0].bindings;
+        assert_eq!(bindings.len(), 1);
+        assert_eq!(bindings[0].attribute, "width");
+        let fallback = bindings[0].fallback.as_ref().unwrap();
+        assert_eq!(fallback.try_downcast_ref::<bevy::prelude::Val>(), Some(&bevy::prelude::Val::Px(10.0)));
     }
 
-    #[derive(Default)]
-    struct Text;
-    impl PropertyType for Text {
-        type Type = String;
+    #[test]
+    fn compile_interpolation() {
+        let xml = r#"
+        <Layout>
+            <Text text="HP: {Resource Stats, Path=hp, Fallback=-}"/>
+            <Node width="{Resource Stats, Path=width}px"/>
+        </Layout>
+        "#;
+        let layout = LayoutReader::new(xml, "layout.xml").parse().unwrap();
+        let layout = XmlLayout {
+            templates:  layout.templates,
+            root_nodes: layout.root_nodes,
+            usings:     layout.usings,
+            ..Default::default()
+        };
+        let library = XmlLibrary::default();
+        let compiled_layout: CompiledLayout = LayoutCompiler::new(&library, &layout).compile().unwrap();
+
+        let components = &compiled_layout.root.components;
+        assert_eq!(components[0].bindings.len(), 1);
+        assert!(matches!(components[0].bindings[0].value, AttributeValue::Interpolated(_)));
+        assert!(components[1].bindings.is_empty());
     }
 
-    const CORRECT_XML: &str = r#"
-    <Layout>
-        <GlobalResources>
-            <Property name="Font" type="String" value="fonts/arial.ttf"/>
-            <Property name="FontColor" type="Color" value="Red"/>
-            <Property name="FontSize" type="f32" value="12"/>
-        </GlobalResources>
-
-        <LocalResources>
-            <Property name="Text" type="String" value="Hello, world!"/>
-        </LocalResources>
-
-        <Template name="button" container="button_container_small; button_container_big">
-            <Container on_click="print_message">
-                <Node width="10%" height="10%"/>
-                <Container>
-                    <Node width="100px" height="100px"/>
-                    <BackgroundColor value="Green"/>
-                    <Button/>
-                </Container>
+    #[test]
+    fn compile_styles() {
+        let xml = r#"
+        <Layout>
+            <Styles>
+                <Style class="panel">
+                    <Node width="10px" height="10px"/>
+                    <Text text="{Resource Stats, Path=title}"/>
+                </Style>
+                <Style class="wide">
+                    <Node width="50%"/>
+                </Style>
+            </Styles>
+            <Container class="panel wide">
+                <Node height="20px"/>
             </Container>
-        </Template>
+        </Layout>
+        "#;
+        let layout = LayoutReader::new(xml, "layout.xml").parse().unwrap();
+        let layout = XmlLayout {
+            root_nodes: layout.root_nodes,
+            styles:     layout.styles,
+            ..Default::default()
+        };
+        let library = XmlLibrary::default();
+        let compiled_layout: CompiledLayout = LayoutCompiler::new(&library, &layout).compile().unwrap();
+
+        //`Node` is merged with the inline one, `Text` only comes from the class
+        let panel = &compiled_layout.root.containers[0];
+        assert_eq!(panel.components.len(), 2);
+        assert_eq!(panel.components[0].bindings.len(), 1);
+        assert_eq!(panel.components[0].bindings[0].attribute, "text");
+        assert!(panel.components[1].bindings.is_empty());
+    }
 
-        <Node width="100%" height="100%"/>
-        <Container id="button_container_small">
-            <Node width="50%" height="50%"/>
-        </Container>
-        <Container id="button_container_big">
-            <Node width="50%" height="50%"/>
-        </Container>
-    </Layout>
-"#;
     #[test]
-    fn compile_layout() {
-        let layout = LayoutReader::new(&CORRECT_XML, "").read().unwrap();
-        let mut layout = XmlLayout {
-            path: LayoutPath {
-                current: "layout".to_string(),
-                global: "layout".to_string(),
-            },
-            local: layout.local,
-            global: layout.global,
-            templates: layout.templates,
+    fn compile_unknown_component() {
+        let xml = r#"
+        <Layout>
+            <Slider value="1"/>
+        </Layout>
+        "#;
+        let layout = LayoutReader::new(xml, "layout.xml").parse().unwrap();
+        let layout = XmlLayout {
+            templates:  layout.templates,
             root_nodes: layout.root_nodes,
+            usings:     layout.usings,
+            path:       "layout.xml".to_string(),
+            ..Default::default()
         };
-        let mut library = XmlLibrary::default();
-        library.add_property::<Font>("layout", "Font", || Box::<TypedStorage<String>>::new(TypedStorage::default()));
-        library.add_property::<FontColor>("layout", "FontColor", || Box::<TypedStorage<Color>>::new(TypedStorage::default()));
-        library.add_property::<FontSize>("layout", "FontSize", || Box::<TypedStorage<f32>>::new(TypedStorage::default()));
-        library.add_property::<Text>("layout", "Text", || Box::<TypedStorage<String>>::new(TypedStorage::default()));
-        let compiled_layout: CompiledLayout = LayoutCompiler::new(&library, &mut layout).compile();
-
-        assert_eq!(compiled_layout.global.read_property::<Font>(), "fonts/arial.ttf");
-        assert_eq!(compiled_layout.global.read_property::<FontSize>(), &12.0);
-        assert_eq!(compiled_layout.global.read_property::<FontColor>(), &Color::srgb(1.0, 0.0, 0.0));
-
-        assert_eq!(compiled_layout.local.read_property::<Text>(), &"Hello, world!");
+        let library = XmlLibrary::default();
+        let error = LayoutCompiler::new(&library, &layout).compile().unwrap_err();
+
+        assert!(matches!(
+            error,
+            XmlLayoutError::UnknownComponent { ref file, ref name, ref namespace }
+                if file == "layout.xml" && name == "Slider" && namespace == "bevy"
+        ));
+    }
+
+    #[test]
+    fn parse_function() {
+        let function = super::Function::parse("print_message");
+        assert_eq!(function.value, "print_message");
+        assert!(matches!(function.kind, FunctionType::Value));
+
+        let function = super::Function::parse("print(Hello, world)");
+        assert_eq!(function.value, "print");
+        assert!(matches!(function.kind, FunctionType::CallFunction(args) if args == "Hello, world"));
     }
-}
\ No newline at end of file
+}
@@ -1,9 +1,12 @@
+//Layout properties are superseded by resource bindings, the shim keeps the old API working
+#![allow(deprecated)]
+
 use std::default::Default;
 use std::any::{type_name, Any, TypeId};
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 use bevy::asset::AssetId;
-use bevy::prelude::{Component, Resource};
+use bevy::prelude::{Component, PartialReflect, Resource};
 use crate::prelude::XmlLayout;
 
 pub trait UntypedStorage: Send + Sync + 'static   {
@@ -12,6 +15,8 @@ pub trait UntypedStorage: Send + Sync + 'static   {
     fn changed(&self) -> bool;
     fn checked(&mut self);
     fn set_changed(&mut self);
+    fn as_partial_reflect(&self) -> &dyn PartialReflect;
+    fn as_partial_reflect_mut(&mut self) -> &mut dyn PartialReflect;
 }
 
 #[derive(Default, Clone)]
@@ -31,7 +36,7 @@ impl<T: 'static> TypedStorage<T> {
     }
 }
 
-impl<T: 'static + Send + Sync> UntypedStorage for TypedStorage<T> {
+impl<T: PartialReflect + Send + Sync + 'static> UntypedStorage for TypedStorage<T> {
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -51,6 +56,14 @@ impl<T: 'static + Send + Sync> UntypedStorage for TypedStorage<T> {
     fn set_changed(&mut self) {
         self.changed = true;
     }
+
+    fn as_partial_reflect(&self) -> &dyn PartialReflect {
+        &self.value
+    }
+
+    fn as_partial_reflect_mut(&mut self) -> &mut dyn PartialReflect {
+        &mut self.value
+    }
 }
 
 pub(crate) struct Storage {
@@ -82,21 +95,23 @@ fn downcast_mut<T: 'static>(b: &mut Box<dyn UntypedStorage>) -> &mut TypedStorag
     b.as_any_mut().downcast_mut::<TypedStorage<T>>().unwrap()
 }
 
-fn downcast<T: 'static>(b: &Box<dyn UntypedStorage>) -> &TypedStorage<T> {
+fn downcast<T: 'static>(b: &dyn UntypedStorage) -> &TypedStorage<T> {
     b.as_any().downcast_ref::<TypedStorage<T>>().unwrap()
 }
 
+#[deprecated(note = "Layout properties are kept for compatibility, bind a resource with `{Resource Type, Path=field}` instead")]
 pub trait PropertyType: Default {
-    type Type: Default + Send + Sync + 'static;
+    type Type: PartialReflect + Default + Send + Sync + 'static;
 }
 
+#[deprecated(note = "Layout properties are kept for compatibility, bind a resource with `{Resource Type, Path=field}` instead")]
 pub type StorageFactory = fn() -> Box<dyn UntypedStorage>;
 
-
+///Properties of `<GlobalResources>`, shared by every document of a layout
+#[deprecated(note = "Layout properties are kept for compatibility, bind a resource with `{Resource Type, Path=field}` instead")]
 #[derive(Resource, Default)]
 pub struct GlobalResources {
     pub(crate) storage: HashMap<AssetId<XmlLayout>, UiResources>,
-    pub(crate) changed: bool,
 }
 
 impl GlobalResources {
@@ -105,6 +120,8 @@ impl GlobalResources {
     }
 }
 
+///Properties of `<LocalResources>`, inserted on the document entity
+#[deprecated(note = "Layout properties are kept for compatibility, bind a resource with `{Resource Type, Path=field}` instead")]
 #[derive(Component, Default)]
 pub struct UiResources {
     pub(crate) properties: HashMap<TypeId, Storage>,
@@ -115,6 +132,10 @@ impl UiResources {
         self.properties.get(&type_id)
     }
 
+    pub(crate) fn get_property_mut(&mut self, type_id: TypeId) -> Option<&mut Storage> {
+        self.properties.get_mut(&type_id)
+    }
+
     pub(crate) fn add_property_internal(&mut self, type_id: TypeId, storage: Storage) {
         self.properties.insert(type_id, storage);
     }
@@ -126,6 +147,7 @@ impl UiResources {
         self.properties.insert(TypeId::of::<Key>(), storage);
     }
 
+    #[cfg(test)]
     pub(crate) fn take_changed(&mut self) -> Vec<(TypeId, Storage)>{
         let changed_keys: Vec<TypeId> = self
             .properties
@@ -165,9 +187,9 @@ impl UiResources {
         let untyped_storage: &mut Storage = self.properties.get_mut(&TypeId::of::<Key>()).unwrap();
         debug_assert!(untyped_storage.is_inherit, "For non inherit properties use 'set_property()' instead'");
         let storage: &mut TypedStorage<Key::Type> = downcast_mut::<Key::Type>(&mut untyped_storage.storage);
-        if value.is_some() {
+        if let Some(value) = value {
             untyped_storage.overridden = true;
-            storage.set(value.unwrap());
+            storage.set(value);
         }
         else {
             untyped_storage.overridden = false;
@@ -177,7 +199,7 @@ impl UiResources {
 
     pub fn read_property<Key: 'static + PropertyType>(&self) -> &Key::Type {
         let untyped_storage: &Storage = self.properties.get(&TypeId::of::<Key>()).unwrap();
-        &downcast::<Key::Type>(&untyped_storage.storage).value
+        &downcast::<Key::Type>(untyped_storage.storage.as_ref()).value
     }
 }
 
@@ -219,8 +241,8 @@ mod tests {
         assert_eq!(resources.read_property::<Left>(), &Val::Px(42.0));
         assert_eq!(resources.read_property::<Visible>(), &Display::Flex);
 
-        assert_eq!(downcast::<Display>(&resources.get_property(TypeId::of::<Visible>()).unwrap().storage).value, Display::Flex);
-        assert_eq!(downcast::<Val>(&resources.get_property(TypeId::of::<Left>()).unwrap().storage).value, Val::Px(42.0));
+        assert_eq!(downcast::<Display>(resources.get_property(TypeId::of::<Visible>()).unwrap().storage.as_ref()).value, Display::Flex);
+        assert_eq!(downcast::<Val>(resources.get_property(TypeId::of::<Left>()).unwrap().storage.as_ref()).value, Val::Px(42.0));
     }
 
     #[test]
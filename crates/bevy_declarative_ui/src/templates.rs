@@ -1,90 +1,146 @@
-use std::collections::HashMap;
-use bevy::prelude::{Bundle, Component, Entity};
-use crate::commands::{UiContext};
-use crate::prelude::UiResources;
-
-#[derive(Bundle)]
-pub struct UiTemplateBundle {
-    pub(crate) context:    UiContext,
-    pub(crate) resources:  UiResources,
-    pub(crate) tag:        Template,
-}
-
-#[derive(Component)]
-pub struct Template;
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+use crate::bindings::{BindingResolver, BindingSource};
+use crate::commands::spawn_layout;
+use crate::prelude::*;
+use crate::Layouts;
 
+///Item templates whose instances are spawned inside this container
 #[derive(Component, Default)]
-pub struct Templates {
-    ///Entity with (RootId, Resources)
-    pub(crate) spawned: HashMap<String, Entity>,
-    pub(crate) queue: Vec<TemplateRequest>
+pub struct ItemTemplates {
+    pub(crate) hosted: Vec<HostedTemplate>,
 }
 
-impl Templates {
-    pub fn spawned(&self) -> &HashMap<String, Entity> {
-        &self.spawned
+impl ItemTemplates {
+    ///Ids of the hosted templates
+    pub fn templates(&self) -> impl Iterator<Item = &str> {
+        self.hosted.iter().map(|template| template.id.as_str())
     }
 
-    pub fn spawn(&mut self, name: &str, template: impl IntoTemplate) {
-        if self.spawned.contains_key(name) {
-            panic!("Template \"{}\" already exists", name.to_string());
-        }
-        let mut template: TemplateRequest = template.into_template();
-        template.instance_name = name.to_string();
-        self.queue.push(template);
+    ///Root entities of the spawned instances, in item order
+    pub fn instances(&self, template: &str) -> &[Entity] {
+        self.hosted.iter()
+            .find(|hosted| hosted.id == template)
+            .map(|hosted| hosted.instances.as_slice())
+            .unwrap_or_default()
     }
+}
 
-    ///Container property will be ignored
-    pub fn spawn_or_insert(&mut self, name: &str, template: impl IntoTemplate) {
-        let mut template: TemplateRequest = template.into_template();
-        template.instance_name = name.to_string();
-        if self.spawned.contains_key(name) {
-            template.action = TemplateAction::SpawnOrInsert;
-        }
-        self.queue.push(template);
+pub(crate) struct HostedTemplate {
+    pub id:        String,
+    ///`None` if the source could not be resolved
+    pub source:    Option<BindingSource>,
+    pub instances: Vec<Entity>,
+}
+
+///Root of a spawned template instance
+#[derive(Component, Debug)]
+pub struct TemplateInstance {
+    pub(crate) owner:    Entity,
+    pub(crate) template: usize,
+    pub(crate) index:    usize,
+}
+
+impl TemplateInstance {
+    ///Container which hosts the instance
+    pub const fn owner(&self) -> Entity {
+        self.owner
     }
 
-    ///Container property will be ignored
-    pub fn insert(&mut self, name: &str, template: impl IntoTemplate) {
-        if !self.spawned.contains_key(name) {
-            panic!("Template \"{}\" does not exists", name.to_string());
-        }
-        let mut template: TemplateRequest = template.into_template();
-        template.instance_name = name.to_string();
-        template.action = TemplateAction::Insert;
-        self.queue.push(template);
+    ///Index of the item in the template source
+    pub const fn index(&self) -> usize {
+        self.index
     }
 }
-pub(crate) enum TemplateAction {
-    Spawn,
-    SpawnOrInsert,
-    ///Replace all resources
-    Insert,
-}
 
-pub struct TemplateRequest {
-    pub(crate) name: String,
-    pub(crate) container: String,
-    pub(crate) resources: UiResources,
+///Parameters used to spawn the instances of the templates
+pub(crate) type TemplateParams<'w, 's> = (
+    Commands<'w, 's>,
+    Query<'w, 's, (&'static mut ItemTemplates, &'static UiContext)>,
+    Res<'w, Layouts>,
+    Res<'w, XmlLibrary>,
+    Res<'w, AssetServer>,
+    Res<'w, AppTypeRegistry>,
+);
 
-    pub(crate) action: TemplateAction,
-    pub(crate) instance_name: String,
-}
+pub(crate) fn sync_templates(
+    world:  &mut World,
+    owners: &mut QueryState<(Entity, &ItemTemplates, &UiContext)>,
+    params: &mut SystemState<TemplateParams<'static, 'static>>,
+) {
+    //Owner, template index, item count
+    let mut changes: Vec<(Entity, usize, usize)> = Vec::new();
+    for (entity, templates, context) in owners.iter(world) {
+        templates.hosted.iter().enumerate().for_each(|(i, hosted)| {
+            let Some(source) = &hosted.source else {
+                return;
+            };
+
+            let len = source.len(world, context).unwrap_or(0);
+            if len != hosted.instances.len() {
+                changes.push((entity, i, len));
+            }
+        });
+    }
+
+    if changes.is_empty() {
+        return;
+    }
+
+    {
+        let (mut commands, mut owners, layouts, library, server, registry) = params.get_mut(world);
+        let registry = registry.read();
+
+        for (owner, template, len) in changes {
+            let (mut templates, context) = owners.get_mut(owner).unwrap();
+            let hosted = &mut templates.hosted[template];
+
+            while hosted.instances.len() > len {
+                let instance = hosted.instances.pop().unwrap();
+                commands.entity(instance).despawn();
+            }
+
+            let Some(layout) = layouts.get(&context.layout_handle().id()) else {
+                continue;
+            };
 
-impl TemplateRequest {
-    pub fn new(name: impl Into<String>, container: impl Into<String>, resources: UiResources) -> Self {
-        Self {
-            name: name.into(),
-            container: container.into(),
-            resources,
+            let Some(compiled) = layout.templates.iter().find(|t| t.id == hosted.id) else {
+                continue;
+            };
 
-            action: TemplateAction::Spawn,
-            instance_name: String::new(),
+            let resolver = BindingResolver {
+                registry:   &registry,
+                library:    &library,
+                usings:     &layout.usings,
+                properties: &layout.properties,
+            };
+
+            while hosted.instances.len() < len {
+                let mut entity = commands.spawn(TemplateInstance {
+                    owner,
+                    template,
+                    index: hosted.instances.len(),
+                });
+
+                let mut context = context.clone();
+                context.subtree_root = entity.id();
+                context.owner_entity = entity.id();
+
+                spawn_layout(
+                    &context,
+                    &mut Containers::default(),
+                    &mut entity,
+                    &server,
+                    &library,
+                    &compiled.root,
+                    &[],
+                    &resolver,
+                );
+                entity.insert(ChildOf(owner));
+                hosted.instances.push(entity.id());
+            }
         }
     }
-}
-
 
-pub trait IntoTemplate {
-    fn into_template(self) -> TemplateRequest;
-}
\ No newline at end of file
+    params.apply(world);
+}
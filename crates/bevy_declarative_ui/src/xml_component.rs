@@ -1,27 +1,69 @@
 use std::any::type_name;
+use std::cell::Cell;
 use std::fmt::Debug;
 use bevy::ecs::component::Mutable;
-use bevy::prelude::{error, Asset, AssetServer, Component, Entity, EntityCommands, Handle, World};
+use bevy::prelude::{error, Asset, AssetServer, Component, Entity, EntityCommands, Handle, PartialReflect, World};
 use dyn_clone::DynClone;
 use crate::injector::Injector;
 use crate::prelude::{TypedStorage, UntypedStorage};
 
 pub struct ValueStorage<'a> {
-    value: &'a Box<dyn UntypedStorage>,
+    value:    StorageValue<'a>,
+    ///Set when a read found another type than the injector expected
+    mismatch: Cell<bool>,
+}
+
+enum StorageValue<'a> {
+    Typed(&'a dyn UntypedStorage),
+    Reflect(&'a dyn PartialReflect),
 }
 
 impl<'a> ValueStorage<'a> {
-    pub fn new(value: &'a Box<dyn UntypedStorage>) -> Self {
-        Self { value }
+    pub fn new(value: &'a dyn UntypedStorage) -> Self {
+        Self { value: StorageValue::Typed(value), mismatch: Cell::new(false) }
+    }
+
+    ///Value read from a reflected resource or component
+    pub fn reflect(value: &'a dyn PartialReflect) -> Self {
+        Self { value: StorageValue::Reflect(value), mismatch: Cell::new(false) }
+    }
+
+    ///Value of the expected type, a mismatch is logged and returns `None`
+    pub fn read<Type: 'static>(&self) -> Option<&Type> {
+        let value = self.try_read::<Type>();
+        if value.is_none() {
+            let found = match self.value {
+                StorageValue::Typed(_) => "another storage type",
+                StorageValue::Reflect(value) => value.reflect_type_path(),
+            };
+            error!("Expected '{}', found '{}'", type_name::<Type>(), found);
+            self.mismatch.set(true);
+        }
+        value
+    }
+
+    ///Writes the value to `place` if it has the expected type
+    pub fn write_to<Type: Clone + 'static>(&self, place: &mut Type) {
+        if let Some(value) = self.read::<Type>() {
+            *place = value.clone();
+        }
     }
 
-    pub fn read<Type: 'static>(&self) -> &Type {
-        self.value.as_any().downcast_ref::<TypedStorage<Type>>().unwrap().get()
+    ///Whether a read found another type, the update was skipped
+    pub fn is_mismatched(&self) -> bool {
+        self.mismatch.get()
     }
 
-    pub fn load<A: Asset>(&self, server: &AssetServer) -> Handle<A> {
-        let path: &String = self.read::<String>();
-        server.load::<A>(path)
+    pub fn try_read<Type: 'static>(&self) -> Option<&Type> {
+        match self.value {
+            StorageValue::Typed(value) => value.as_any().downcast_ref::<TypedStorage<Type>>().map(|storage| storage.get()),
+            StorageValue::Reflect(value) => value.try_downcast_ref::<Type>(),
+        }
+    }
+
+    pub fn load<A: Asset>(&self, server: &AssetServer) -> Option<Handle<A>> {
+        let path: &String = self.read::<String>()?;
+        Some(server.load::<A>(path))
     }
 }
 
@@ -38,15 +80,23 @@ impl<'a> Extractor<'a> {
         Self {entity, world }
     }
 
-    pub fn extract<T, F>(&mut self, callback: F)
+    ///Runs the callback on the component, returns `false` if the entity does not have it
+    pub fn extract<T, F>(&mut self, callback: F) -> bool
     where
         T: Component<Mutability = Mutable>,
         F: FnOnce(&mut T),
     {
         let entity = self.entity;
-        let mut entity = self.world.entity_mut(entity);
-        let mut component = entity.get_mut::<T>().unwrap();
+        let Some(mut component) = self.world.get_mut::<T>(entity) else {
+            error!("Entity {} does not have '{}'", entity, type_name::<T>());
+            return false;
+        };
         callback(&mut component);
+        true
+    }
+
+    pub fn get<T: Component>(&self) -> Option<&T> {
+        self.world.get::<T>(self.entity)
     }
 }
 
@@ -69,22 +119,32 @@ impl<'a> MutValueStorage<'a> {
     }
 
     pub fn write<PType: FromStrTyped>(&mut self, value: &str) {
-        let result: Result<PType, String> = PType::from_str_typed(value);
-        let value: PType = if result.is_err() {
-            error!("{}", result.err().unwrap());
+        let value: PType = PType::from_str_typed(value).unwrap_or_else(|error| {
+            error!("{}", error);
             PType::default()
-        } else {
-            result.unwrap()
-        };
+        });
         if let Some(storage) = self.value.as_any_mut().downcast_mut::<TypedStorage<PType>>() {
             storage.set(value)
         }
         else {
-            panic!("Type error todo: {}", type_name::<PType>());
+            error!("Storage does not hold '{}'", type_name::<PType>());
         }
     }
 }
 
+///Parses the value with its `FromStrTyped` implementation, used by `Injector::parse_value`
+pub fn parse_typed<T: FromStrTyped + PartialReflect>(value: &str) -> Option<Result<Box<dyn PartialReflect>, String>> {
+    Some(T::from_str_typed(value).map(|value| Box::new(value) as Box<dyn PartialReflect>))
+}
+
+///Parses a literal emitted by `#[ui_layout]`, an invalid value is logged and replaced by the default
+pub fn parse_or_default<T: FromStrTyped>(value: &str, attribute: &str) -> T {
+    T::from_str_typed(value).unwrap_or_else(|error| {
+        error!("Invalid value '{}' of attribute '{}': {}", value, attribute, error);
+        T::default()
+    })
+}
+
 pub trait FromStrTyped: Default + IsTyped {
     fn from_str_typed(s: &str) -> Result<Self, String>
     where
@@ -1,37 +1,22 @@
+use std::collections::HashSet;
 use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
 use bevy::prelude::*;
-use bevy_declarative_ui_parser::{LayoutReader, PropertyValue, Resources, ItemTemplate, UiNode, XmlLayoutError};
-
-#[derive(Default, Debug)]
-pub(crate) struct LayoutPath {
-    pub current: String,
-    pub global: String,
-}
+use bevy_declarative_ui_parser::{LayoutReader, IncludedFiles, ItemTemplate, LayoutProperty, Style, UiNode, XmlLayoutError};
 
 #[derive(Asset, TypePath, Default, Debug)]
 pub struct XmlLayout {
-    pub(crate) path:       LayoutPath,
-    pub(crate) local:      Resources,
-    pub(crate) global:     Resources,
     pub(crate) templates:  Vec<ItemTemplate>,
     pub(crate) root_nodes: Vec<UiNode>,
+    pub(crate) usings:     HashSet<String>,
+    pub(crate) properties: Vec<LayoutProperty>,
+    ///Classes applied by the `class` attribute of the containers
+    pub(crate) styles:     Vec<Style>,
+    ///File name without the extension, properties are registered for it with `XmlLibrary::add_property`
+    pub(crate) name:       String,
+    ///Asset path, compile errors are reported for it
+    pub(crate) path:       String,
 }
 
-impl XmlLayout {
-    pub(crate) fn get_resource(&self, key: &str) -> Option<&PropertyValue> {
-        if let Some(local_value) = self.local.get(key) {
-            Some(local_value)
-        }
-        else if let Some(global_value) = self.global.get(key) {
-            Some(global_value)
-        }
-        else {
-            None
-        }
-    }
-}
-
-
 #[derive(Default)]
 pub struct XmlLoader;
 
@@ -52,30 +37,34 @@ impl AssetLoader for XmlLoader {
         reader.read_to_string(&mut string).await?;
 
         let path = ctx.path().display().to_string();
-        let mut reader = LayoutReader::new(&string, path.as_str());
-        let result = reader.read();
-        if let Ok(layout) = result {
-            Ok(XmlLayout {
-                path:       LayoutPath {
-                    current: normalize_path(&ctx.path().display().to_string()),
-                    global:  normalize_path(&ctx.path().display().to_string()),
-                },
-                local:      layout.local,
-                global:     layout.global,
-                templates:  layout.templates,
-                root_nodes: layout.root_nodes,
-            })
-        }
-        else {
-            Err(result.err().unwrap())
+        let name = ctx.path().file_stem().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        //Report every mistake at once, the first one fails the load
+        let parsed = LayoutReader::new(&string, path.as_str()).parse_recovering();
+        parsed.errors.iter().skip(1).for_each(|error| error!("{error}"));
+        let layout = parsed.into_result()?;
+
+        //Included files are read as dependencies, the layout is reloaded when one of them changes
+        let mut included = IncludedFiles::new(&path, &layout);
+        while let Some(file) = included.next_pending() {
+            let content = match ctx.read_asset_bytes(file.clone()).await {
+                Ok(bytes) => String::from_utf8(bytes).map_err(|error| error.to_string()),
+                Err(error) => Err(error.to_string()),
+            };
+            included.add(file, content);
         }
+        let layout = included.resolve(&path, layout)?;
+        Ok(XmlLayout {
+            templates:  layout.templates,
+            root_nodes: layout.root_nodes,
+            usings:     layout.usings,
+            properties: layout.properties,
+            styles:     layout.styles,
+            name,
+            path,
+        })
     }
 
     fn extensions(&self) -> &[&str] {
         &["xml"]
     }
 }
-fn normalize_path(path: &str) -> String {
-    let without_xml = path.strip_suffix(".xml").unwrap_or(path);
-    without_xml.replace(['/', '\\'], "::")
-}
\ No newline at end of file
@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+use bevy_declarative_ui_macro::ui_layout;
+
+#[derive(Resource)]
+pub struct Stats {
+    pub visible: bool,
+    pub name: String,
+}
+
+#[derive(Resource)]
+pub struct Sizes {
+    pub height: Val,
+}
+
+#[derive(Resource)]
+pub struct Score {
+    pub points: u32,
+}
+
+#[ui_layout("tests/assets/static_layout.xml")]
+pub struct StatsPlugin;
+
+fn setup() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default(), StatsPlugin));
+    app.insert_resource(Stats {
+        visible: true,
+        name: "Alice".to_string(),
+    });
+    app.update();
+    app.update();
+    app
+}
+
+fn title(app: &mut App) -> (Node, String, Color) {
+    let mut query = app
+        .world_mut()
+        .query_filtered::<(&Node, &Text, &TextColor), With<statsplugin_layout::ids::Title>>();
+    let (node, text, color) = query.single(app.world()).unwrap();
+    (node.clone(), text.0.clone(), color.0)
+}
+
+#[test]
+fn attributes_are_applied() {
+    let mut app = setup();
+    let (node, text, color) = title(&mut app);
+    assert_eq!(node.width, Val::Px(50.0));
+    assert_eq!(node.display, Display::Flex);
+    assert_eq!(text, "Alice");
+    assert_eq!(color, Color::srgb(1.0, 0.0, 0.0));
+}
+
+#[test]
+fn resource_binding_updates() {
+    let mut app = setup();
+    {
+        let mut stats = app.world_mut().resource_mut::<Stats>();
+        stats.visible = false;
+        stats.name = "Bob".to_string();
+    }
+    app.update();
+
+    let (node, text, _) = title(&mut app);
+    assert_eq!(node.width, Val::Px(50.0));
+    assert_eq!(node.display, Display::None);
+    assert_eq!(text, "Bob");
+}
+
+#[test]
+fn fallback_while_resource_is_missing() {
+    let mut app = setup();
+    assert_eq!(title(&mut app).0.height, Val::Px(20.0));
+
+    app.insert_resource(Sizes {
+        height: Val::Px(30.0),
+    });
+    app.update();
+    assert_eq!(title(&mut app).0.height, Val::Px(30.0));
+
+    app.world_mut().remove_resource::<Sizes>();
+    app.update();
+    assert_eq!(title(&mut app).0.height, Val::Px(20.0));
+}
+
+#[test]
+fn interpolated_text() {
+    let mut app = setup();
+    let mut query = app
+        .world_mut()
+        .query_filtered::<&Text, With<statsplugin_layout::ids::Points>>();
+    assert_eq!(query.single(app.world()).unwrap().0, "Alice has no points");
+
+    app.insert_resource(Score { points: 3 });
+    app.update();
+    assert_eq!(query.single(app.world()).unwrap().0, "Alice has 3 points");
+}
+
+#[test]
+fn conditional_containers() {
+    let mut app = setup();
+    let mut online = app
+        .world_mut()
+        .query_filtered::<&Node, With<statsplugin_layout::ids::Online>>();
+    let mut offline = app
+        .world_mut()
+        .query_filtered::<&Node, With<statsplugin_layout::ids::Offline>>();
+    assert_eq!(online.single(app.world()).unwrap().display, Display::Grid);
+    assert_eq!(offline.single(app.world()).unwrap().display, Display::None);
+
+    app.world_mut().resource_mut::<Stats>().visible = false;
+    app.update();
+    assert_eq!(online.single(app.world()).unwrap().display, Display::None);
+    assert_eq!(offline.single(app.world()).unwrap().display, Display::Flex);
+}
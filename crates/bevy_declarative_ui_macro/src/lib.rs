@@ -1,18 +1,21 @@
 use std::path::PathBuf;
 
-use bevy_declarative_ui_parser::LayoutReader;
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, parse_str, DeriveInput, LitStr, Type};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_str, DeriveInput, LitStr, Path, Type};
+use ui_generator::{generate_static_layout_with, GeneratorError};
 
+///The annotated item is emitted next to the error, so the rest of the crate still resolves it
 macro_rules! try_unwrap {
-    ($expr:expr) => {
+    ($expr:expr, $span:expr, $item:expr) => {
         match $expr {
             Ok(val) => val,
             Err(err) => {
-                let error_msg = err.to_string();
+                let error = syn::Error::new($span, err.to_string()).to_compile_error();
+                let item = &$item;
                 return quote! {
-                    compile_error!(#error_msg);
+                    #item
+                    #error
                 }.into();
             }
         }
@@ -23,23 +26,71 @@ macro_rules! try_unwrap {
 pub fn ui_layout(attr: TokenStream, item: TokenStream) -> TokenStream {
     let plugin_type: Type = parse_str("bevy::prelude::Plugin").unwrap();
     let app_type: Type = parse_str("bevy::app::App").unwrap();
+    let startup_type: Type = parse_str("bevy::app::Startup").unwrap();
+    let update_type: Type = parse_str("bevy::app::Update").unwrap();
 
-    let relative_path = parse_macro_input!(attr as LitStr).value();
+    let input = parse_macro_input!(item as DeriveInput);
+    let literal = match syn::parse::<LitStr>(attr) {
+        Ok(literal) => literal,
+        Err(error) => {
+            let error = error.to_compile_error();
+            return quote! {
+                #input
+                #error
+            }.into();
+        }
+    };
+    let span = literal.span();
 
-    let absolute_path = try_unwrap!(to_absolute_path(&relative_path));
-    let file_content  = try_unwrap!(std::fs::read_to_string(&absolute_path));
-    let _parsed_layout = try_unwrap!(LayoutReader::new(&file_content, &absolute_path).parse());
+    let absolute_path = try_unwrap!(to_absolute_path(&literal.value()), span, input);
+    let file_content  = try_unwrap!(std::fs::read_to_string(&absolute_path), span, input);
 
-    let input = parse_macro_input!(item as DeriveInput);
-    let struct_name = input.ident;
-    let vis = input.vis;
+    //Included layouts are inputs of the crate too, even when one of them is invalid
+    let mut included = Vec::new();
+    let result = generate_static_layout_with(&absolute_path, &file_content, |path| {
+        included.push(path.to_string());
+        std::fs::read_to_string(path).map_err(|error| error.to_string())
+    });
+    let readable = included.iter().filter(|path| std::path::Path::new(path).is_file());
+    let dependency = track_files(std::iter::once(&absolute_path).chain(readable));
+
+    let layout = match result {
+        Ok(layout) => layout,
+        Err(error) => {
+            let error = syn::Error::new(span, render_error(&error)).to_compile_error();
+            return quote! {
+                #input
+                #error
+                #dependency
+            }.into();
+        }
+    };
+
+    let content: proc_macro2::TokenStream = try_unwrap!(layout.content.parse(), span, input);
+    let spawn_system: Path = try_unwrap!(parse_str(&layout.spawn_system), span, input);
+    let systems = try_unwrap!(layout.systems
+        .iter()
+        .map(|system| parse_str::<Path>(system))
+        .collect::<Result<Vec<_>, _>>(), span, input);
+
+    let struct_name = &input.ident;
+    let module_name = format_ident!("{}_layout", struct_name.to_string().to_lowercase());
 
     let expanded = quote! {
-        #vis struct #struct_name;
-        
-        impl #plugin_type for #struct_name {
-            fn build(&self, app: &mut #app_type) {
+        #input
+
+        mod #module_name {
+            use super::*;
 
+            #dependency
+
+            #content
+
+            impl #plugin_type for super::#struct_name {
+                fn build(&self, app: &mut #app_type) {
+                    app.add_systems(#startup_type, #spawn_system);
+                    #(app.add_systems(#update_type, #systems);)*
+                }
             }
         }
     };
@@ -47,14 +98,28 @@ pub fn ui_layout(attr: TokenStream, item: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
-fn to_absolute_path(relative_path: &str) -> Result<String, TokenStream> {
-    let project_root = std::env::var("CARGO_MANIFEST_DIR");
-    if let Err(error) = project_root {
-        let error_msg = error.to_string();
-        return Err(quote!(compile_error!(#error_msg)).into())
-    }
- 
-    let mut path = PathBuf::from(project_root.unwrap());
+fn to_absolute_path(relative_path: &str) -> Result<String, std::env::VarError> {
+    let mut path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR")?);
     path.push(relative_path);
-    Ok(path.to_string_lossy().replace('\\', "\\\\"))
+    Ok(path.to_string_lossy().to_string())
+}
+
+///Makes the layout files inputs of the crate, so editing one of them triggers recompilation
+fn track_files<'a>(absolute_paths: impl Iterator<Item = &'a String>) -> proc_macro2::TokenStream {
+    quote! {
+        #(const _: &[u8] = include_bytes!(#absolute_paths);)*
+    }
+}
+
+///The file, location and underlined source are already rendered by the parser
+fn render_error(error: &GeneratorError) -> String {
+    let GeneratorError::Layout(error) = error else {
+        return error.to_string();
+    };
+    let message = error.to_string();
+    let header = format!("error[{}]: ", error.code());
+    match message.strip_prefix(&header) {
+        Some(message) => format!("[{}] {}", error.code(), message.trim_end()),
+        None => message,
+    }
 }
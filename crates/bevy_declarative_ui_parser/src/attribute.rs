@@ -1,6 +1,8 @@
 use crate::errors::XmlLayoutError;
 use crate::layout_reader::LayoutReader;
 use crate::lexer::Value;
+#[cfg(feature = "node_span")]
+use crate::position::NodeSpan;
 use crate::values::AttributeValue;
 use crate::{XmlTag, lexer};
 
@@ -8,35 +10,45 @@ use crate::{XmlTag, lexer};
 pub struct Attribute {
     pub name: String,
     pub value: AttributeValue,
+    #[cfg(feature = "node_span")]
+    pub name_span: NodeSpan,
+    /// Raw value in the source, quotes excluded
+    #[cfg(feature = "node_span")]
+    pub value_span: NodeSpan,
 }
 
+/// Invalid attributes are left out, their errors are returned in the order of the attributes
 pub(super) fn parse_attributes(
     reader: &LayoutReader,
     vec: Vec<lexer::Attribute>,
-) -> Result<Vec<Attribute>, XmlLayoutError> {
+) -> (Vec<Attribute>, Vec<XmlLayoutError>) {
     let mut result = Vec::with_capacity(vec.len());
-    vec.into_iter()
-        .try_for_each(|attr| -> Result<(), XmlLayoutError> {
-            result.push(Attribute {
-                value: AttributeValue::parse(reader, &attr.value, false)?,
+    let mut errors = vec![];
+    for attr in vec {
+        match AttributeValue::parse(reader, &attr.value, false) {
+            Ok(value) => result.push(Attribute {
+                value,
+                #[cfg(feature = "node_span")]
+                name_span: NodeSpan::new(attr.name.span, attr.name.location),
+                #[cfg(feature = "node_span")]
+                value_span: NodeSpan::new(attr.value.span, attr.value.location),
                 name: attr.into_name(),
-            });
-            Ok(())
-        })?;
+            }),
+            Err(error) => errors.push(error),
+        }
+    }
 
-    Ok(result)
+    (result, errors)
 }
 
 impl<'a> LayoutReader<'a> {
-    pub(super) fn parse_required_attribute(
+    pub(super) fn parse_required_attribute<'t>(
         &self,
-        tag: &XmlTag,
+        tag: &XmlTag<'t>,
         attribute: &'static str,
-    ) -> Result<Value, XmlLayoutError> {
+    ) -> Result<Value<'t>, XmlLayoutError> {
         Ok(tag
-            .attributes()
-            .iter()
-            .find(|&a| a.name() == attribute)
+            .attribute(attribute)
             .ok_or(self.err_missing_attribute(tag, attribute))?
             .value
             .clone())
@@ -0,0 +1,55 @@
+//! Formats XML layouts in place with [`LayoutWriter::format`].
+//!
+//! `layout_fmt [--check] <files>...`, with `--check` the files are left untouched and the ones
+//! which are not formatted are listed, the exit code is non-zero if there are any.
+//! The files are formatted from their source and not with the lossy [`LayoutWriter::write`],
+//! so their `<Define>` and `<Include>` elements are kept
+
+use bevy_declarative_ui_parser::LayoutWriter;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let (flags, files): (Vec<String>, Vec<String>) = std::env::args()
+        .skip(1)
+        .partition(|arg| arg.starts_with("--"));
+    let check = flags.iter().any(|flag| flag == "--check");
+    if files.is_empty() {
+        eprintln!("Usage: layout_fmt [--check] <files>...");
+        return ExitCode::FAILURE;
+    }
+
+    let writer = LayoutWriter::default();
+    let mut failed = false;
+    for file in &files {
+        let formatted = std::fs::read_to_string(file)
+            .map_err(|error| format!("{file}: {error}"))
+            .and_then(|content| {
+                let formatted = writer
+                    .format(&content, file)
+                    .map_err(|error| error.to_string())?;
+                Ok((formatted != content).then_some(formatted))
+            });
+
+        let result = match formatted {
+            Ok(None) => Ok(()),
+            Ok(Some(_)) if check => {
+                println!("{file}");
+                Err(None)
+            }
+            Ok(Some(formatted)) => {
+                std::fs::write(file, formatted).map_err(|error| Some(format!("{file}: {error}")))
+            }
+            Err(error) => Err(Some(error)),
+        };
+        if let Err(error) = result {
+            error.iter().for_each(|error| eprintln!("{error}"));
+            failed = true;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
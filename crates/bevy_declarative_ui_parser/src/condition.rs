@@ -0,0 +1,88 @@
+use crate::values::AttributeValue;
+use crate::{UiNode, XmlTag, errors::XmlLayoutError, layout_reader::LayoutReader};
+
+/// `<If>`, `<Else>` or `<Case>` container, shown while the bound value passes the test
+#[derive(Clone, Debug, PartialEq)]
+pub struct Condition {
+    /// `test` of the `<If>`, or `value` of the `<Switch>` around the `<Case>`
+    pub value: AttributeValue,
+    pub test: ConditionTest,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConditionTest {
+    /// `<If>`, the value is `true` or `Some`
+    True,
+    /// `<Else>` after an `<If>`, the value is not `true` or `Some`, or it does not exist
+    False,
+    /// `<Case equals="..">` of a `<Switch>`, the value is displayed as the text.
+    /// Unit enum variants are displayed as their name
+    Equals(String),
+}
+
+/// Attribute of the conditional tag which is read by the parser, e.g. `test` of `<If>`
+pub fn condition_attribute(tag: &str) -> Option<&'static str> {
+    match tag {
+        "If" => Some("test"),
+        "Switch" => Some("value"),
+        "Case" => Some("equals"),
+        _ => None,
+    }
+}
+
+impl Condition {
+    /// Binding of `<If test>` or `<Switch value>`, taken from the parsed attributes of the
+    /// container. `None` if the attribute could not be parsed, the error is already reported
+    pub(crate) fn value(
+        reader: &LayoutReader,
+        tag: &XmlTag,
+        node: &UiNode,
+        attribute: &'static str,
+    ) -> Result<Option<AttributeValue>, XmlLayoutError> {
+        let raw = reader.parse_required_attribute(tag, attribute)?;
+        match parsed(node, attribute) {
+            Some(value) if is_binding(value) => Ok(Some(value.clone())),
+            Some(_) => Err(reader.err_invalid_condition(&raw)),
+            None => Ok(None),
+        }
+    }
+
+    /// Value of the `<Switch>` around a `<Case>`, `None` if it was reported as invalid
+    pub(crate) fn switch_value(switch: &UiNode) -> Option<AttributeValue> {
+        parsed(switch, "value")
+            .filter(|value| is_binding(value))
+            .cloned()
+    }
+
+    /// Text of `<Case equals>`, bindings are not allowed
+    pub(crate) fn equals(
+        reader: &LayoutReader,
+        tag: &XmlTag,
+        node: &UiNode,
+    ) -> Result<Option<String>, XmlLayoutError> {
+        reader.parse_required_attribute(tag, "equals")?;
+        match parsed(node, "equals") {
+            Some(AttributeValue::Value(value)) => Ok(Some(value.clone())),
+            Some(_) => Err(reader.err_expected_value(tag)),
+            None => Ok(None),
+        }
+    }
+}
+
+fn is_binding(value: &AttributeValue) -> bool {
+    matches!(
+        value,
+        AttributeValue::Resource(_)
+            | AttributeValue::Component(_)
+            | AttributeValue::Item(_)
+            | AttributeValue::Property(_)
+    )
+}
+
+fn parsed<'n>(node: &'n UiNode, attribute: &str) -> Option<&'n AttributeValue> {
+    node.tag
+        .attributes
+        .iter()
+        .find(|parsed| parsed.name == attribute)
+        .map(|parsed| &parsed.value)
+}
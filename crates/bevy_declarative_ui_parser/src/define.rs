@@ -0,0 +1,329 @@
+use crate::errors::XmlLayoutError;
+use crate::lexer::{Attribute, TagEnd, Token};
+use crate::values::is_property_name;
+use crate::{LayoutReader, XmlTag};
+use std::borrow::Cow;
+use std::cell::Cell;
+
+/// Tags read by the parser, a `<Define>` can not take their name
+const LAYOUT_TAGS: [&str; 17] = [
+    "Layout",
+    "Use",
+    "GlobalResources",
+    "LocalResources",
+    "Property",
+    "Styles",
+    "Style",
+    "ItemTemplate",
+    "Container",
+    "Include",
+    "Define",
+    "If",
+    "Else",
+    "Switch",
+    "Case",
+    "Slot",
+    "Fill",
+];
+
+/// Tags which take an `id`, the ids of the content of an instance are scoped by the instance
+const CONTAINER_TAGS: [&str; 6] = ["Container", "Include", "If", "Else", "Switch", "Case"];
+
+/// `<Define name="IconButton" params="icon, label">`, a container declared before the tree.
+/// Every later `<IconButton icon=".." label=".."/>` is replaced by a container with the content
+/// of the define, `{icon}` and `{label}` in its attribute values are replaced by the arguments.
+/// `<Slot name="..">` elements of the content are replaced by the `<Fill>` elements of the
+/// instance, or by their own content if the instance does not fill them.
+/// Ids in the content and in the fills are scoped by the id of the instance, like `Play.Icon`,
+/// or by the name of the define and the number of the instance, like `IconButton0.Icon`
+pub(crate) struct Define<'a> {
+    pub name: String,
+    pub params: Vec<String>,
+    /// Tokens between `<Define>` and `</Define>`, instances of earlier defines are expanded
+    pub body: Vec<Token<'a>>,
+    /// Names of the `<Slot>` elements of the body
+    pub slots: Vec<String>,
+    /// Instances read so far, they number the scopes of the instances without an id
+    instances: Cell<usize>,
+}
+
+/// `<Fill slot="..">` inside an instance of a `<Define>`
+pub(crate) struct Fill<'a> {
+    pub slot: String,
+    /// Tokens of the content, they are not affected by the parameters of the define
+    pub content: Vec<Token<'a>>,
+}
+
+impl<'a> Define<'a> {
+    pub fn new(reader: &LayoutReader<'a>, tag: &XmlTag<'a>) -> Result<Self, XmlLayoutError> {
+        let name = reader.parse_required_attribute(tag, "name")?;
+        let name = name.value();
+        if name.is_empty() {
+            return Err(reader.err_empty_attribute(tag, "name"));
+        }
+        if LAYOUT_TAGS.contains(&name) {
+            return Err(reader.err_invalid_define(tag, name, "it is a layout tag"));
+        }
+        if reader.define(name).is_some() {
+            return Err(reader.err_invalid_define(tag, name, "it is already defined"));
+        }
+
+        let params = tag
+            .attribute("params")
+            .map(|params| params.value())
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|param| !param.is_empty())
+            .map(|param| {
+                if is_property_name(param) && param != "id" {
+                    Ok(param.to_string())
+                } else {
+                    Err(reader.err_invalid_define(
+                        tag,
+                        param,
+                        "parameters are named with letters, digits and '_', except id",
+                    ))
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Define {
+            name: name.to_string(),
+            params,
+            body: vec![],
+            slots: vec![],
+            instances: Cell::new(0),
+        })
+    }
+
+    /// Collects the names of the slots once the body is read, every `<Slot>` needs one
+    pub fn read_slots(&mut self, reader: &LayoutReader<'a>) -> Result<(), XmlLayoutError> {
+        for token in &self.body {
+            let (Token::TagStart(slot) | Token::TagEmpty(slot)) = token else {
+                continue;
+            };
+            if slot.qualified_name() != "Slot" {
+                continue;
+            }
+
+            let name = reader.parse_required_attribute(slot, "name")?;
+            if name.value().is_empty() {
+                return Err(reader.err_empty_attribute(slot, "name"));
+            }
+            if !self.slots.iter().any(|declared| declared == name.value()) {
+                self.slots.push(name.value().to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Tokens of the instance: a `<Container>` with the `id` of the instance around the body,
+    /// every parameter must be given. `end` is the end tag of an instance with content
+    pub fn instantiate(
+        &self,
+        reader: &LayoutReader<'a>,
+        tag: &XmlTag<'a>,
+        fills: &[Fill<'a>],
+        end: Option<TagEnd<'a>>,
+    ) -> Result<Vec<Token<'a>>, XmlLayoutError> {
+        if let Some(unknown) = tag.attributes().iter().find(|attribute| {
+            attribute.name() != "id" && !self.params.iter().any(|param| param == attribute.name())
+        }) {
+            return Err(reader.err_unknown_argument(tag, unknown, &self.name));
+        }
+        let arguments = self
+            .params
+            .iter()
+            .map(|param| match tag.attribute(param) {
+                Some(argument) => Ok((param.as_str(), argument.value())),
+                None => Err(reader.err_missing_argument(tag, &self.name, param)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let number = self.instances.replace(self.instances.get() + 1);
+        let scope = match tag.attribute("id") {
+            Some(id) => id.value().to_string(),
+            None => format!("{}{number}", self.name),
+        };
+        let container = XmlTag {
+            identifier: "Container",
+            prefix: None,
+            namespace: None,
+            attributes: tag.attribute("id").cloned().into_iter().collect(),
+            ..tag.clone()
+        };
+        let end = match end {
+            Some(end) => TagEnd {
+                identifier: "Container",
+                prefix: None,
+                ..end
+            },
+            None => TagEnd {
+                span: tag.span(),
+                location: tag.location(),
+                identifier: "Container",
+                prefix: None,
+            },
+        };
+
+        let mut tokens = Vec::with_capacity(self.body.len() + 2);
+        tokens.push(Token::TagStart(container));
+        let mut body = self.body.iter();
+        while let Some(token) = body.next() {
+            match token {
+                Token::TagEmpty(slot) if slot.qualified_name() == "Slot" => {
+                    tokens.extend(filled(slot, fills).map(|token| scope_token(token, &scope)));
+                }
+                Token::TagStart(slot) if slot.qualified_name() == "Slot" => {
+                    let default = element_content(&mut body);
+                    if filled(slot, fills).next().is_some() {
+                        tokens.extend(filled(slot, fills).map(|token| scope_token(token, &scope)));
+                    } else {
+                        tokens.extend(default.into_iter().map(|token| {
+                            scope_token(&substitute_token(token, &arguments), &scope)
+                        }));
+                    }
+                }
+                token => tokens.push(scope_token(&substitute_token(token, &arguments), &scope)),
+            }
+        }
+        tokens.push(Token::TagEnd(end));
+        Ok(tokens)
+    }
+}
+
+impl<'a> Fill<'a> {
+    pub fn new(
+        reader: &LayoutReader<'a>,
+        tag: &XmlTag<'a>,
+        define: &Define<'a>,
+        content: Vec<Token<'a>>,
+    ) -> Result<Self, XmlLayoutError> {
+        let slot = reader.parse_required_attribute(tag, "slot")?;
+        if !define.slots.iter().any(|declared| declared == slot.value()) {
+            return Err(reader.err_unknown_slot(tag, &define.name, slot.value()));
+        }
+        Ok(Fill {
+            slot: slot.value().to_string(),
+            content,
+        })
+    }
+}
+
+/// Content of the fills of the slot, in the order of the instance
+fn filled<'t, 'a>(slot: &XmlTag<'a>, fills: &'t [Fill<'a>]) -> impl Iterator<Item = &'t Token<'a>> {
+    let name = slot.attribute("name").map(|name| name.value().to_string());
+    fills
+        .iter()
+        .filter(move |fill| name.as_deref() == Some(fill.slot.as_str()))
+        .flat_map(|fill| &fill.content)
+}
+
+/// Tokens up to the end tag of the element which was just started, the end tag is consumed
+fn element_content<'t, 'a>(tokens: &mut std::slice::Iter<'t, Token<'a>>) -> Vec<&'t Token<'a>> {
+    let mut content = vec![];
+    let mut depth = 0usize;
+    for token in tokens.by_ref() {
+        match token {
+            Token::TagStart(_) => depth += 1,
+            Token::TagEnd(_) if depth == 0 => break,
+            Token::TagEnd(_) => depth -= 1,
+            _ => {}
+        }
+        content.push(token);
+    }
+    content
+}
+
+impl<'a> LayoutReader<'a> {
+    /// Define of the element, only unprefixed elements can be instances
+    pub(crate) fn define(&self, name: &str) -> Option<&Define<'a>> {
+        self.defines.iter().find(|define| define.name == name)
+    }
+}
+
+/// `id="Icon"` of a container becomes `id="{scope}.Icon"`
+fn scope_token<'a>(token: &Token<'a>, scope: &str) -> Token<'a> {
+    let scope_tag = |tag: &XmlTag<'a>| {
+        let mut tag = tag.clone();
+        if CONTAINER_TAGS.contains(&tag.qualified_name().as_str())
+            && let Some(id) = tag
+                .attributes
+                .iter_mut()
+                .find(|attribute| attribute.name() == "id")
+        {
+            id.value.inner = Cow::Owned(format!("{scope}.{}", id.value()));
+            id.value.references.clear();
+        }
+        tag
+    };
+    match token {
+        Token::TagStart(tag) => Token::TagStart(scope_tag(tag)),
+        Token::TagEmpty(tag) => Token::TagEmpty(scope_tag(tag)),
+        token => token.clone(),
+    }
+}
+
+fn substitute_token<'a>(token: &Token<'a>, arguments: &[(&str, &str)]) -> Token<'a> {
+    match token {
+        Token::TagStart(tag) => Token::TagStart(substitute_tag(tag, arguments)),
+        Token::TagEmpty(tag) => Token::TagEmpty(substitute_tag(tag, arguments)),
+        token => token.clone(),
+    }
+}
+
+fn substitute_tag<'a>(tag: &XmlTag<'a>, arguments: &[(&str, &str)]) -> XmlTag<'a> {
+    let mut tag = tag.clone();
+    tag.attributes
+        .iter_mut()
+        .for_each(|attribute| substitute_attribute(attribute, arguments));
+    tag
+}
+
+fn substitute_attribute(attribute: &mut Attribute, arguments: &[(&str, &str)]) {
+    if let Some(value) = substitute(attribute.value(), arguments) {
+        attribute.value.inner = Cow::Owned(value);
+        //The offsets of decoded references do not match the new value
+        attribute.value.references.clear();
+    }
+}
+
+/// Replaces `{param}` by the argument, also inside bindings. `None` if nothing was replaced
+fn substitute(value: &str, arguments: &[(&str, &str)]) -> Option<String> {
+    let mut result = String::with_capacity(value.len());
+    let mut replaced = false;
+    let mut rest = value;
+    while let Some(start) = rest.find(['{', '}']) {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        //Escaped braces are literal text
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            result.push_str(&rest[..2]);
+            rest = &rest[2..];
+            continue;
+        }
+
+        let argument = rest[1..].find('}').and_then(|end| {
+            let name = &rest[1..end + 1];
+            arguments
+                .iter()
+                .find(|(param, _)| *param == name)
+                .map(|(_, argument)| (end + 2, *argument))
+        });
+        match argument {
+            Some((length, argument)) => {
+                result.push_str(argument);
+                rest = &rest[length..];
+                replaced = true;
+            }
+            None => {
+                result.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    replaced.then_some(result)
+}
@@ -0,0 +1,211 @@
+use crate::errors::{Duplicates, ErrorContext};
+use crate::position::Location;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+
+/// Renderable form of [`XmlLayoutError`](crate::errors::XmlLayoutError),
+/// printed as a code frame by `Display` and serialized by [`Diagnostic::to_json`]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Diagnostic {
+    pub code: &'static str,
+    pub message: String,
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    /// Text the spans point into, usually a tag or an attribute value
+    pub source: Option<String>,
+    /// Line of the first line of `source`
+    #[serde(skip)]
+    pub first_line: usize,
+    /// Primary span first, then secondary spans in source order
+    pub spans: Vec<Label>,
+    pub help: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Label {
+    /// Byte offset in [`Diagnostic::source`]
+    pub start: usize,
+    pub length: usize,
+    pub line: usize,
+    pub column: usize,
+    pub primary: bool,
+    pub message: Option<String>,
+}
+
+impl Diagnostic {
+    pub(crate) const fn new(code: &'static str, message: String, help: Option<String>) -> Self {
+        Self {
+            code,
+            message,
+            file: None,
+            line: None,
+            column: None,
+            source: None,
+            first_line: 0,
+            spans: vec![],
+            help,
+        }
+    }
+
+    pub(crate) fn in_file(mut self, file: &str) -> Self {
+        self.file = Some(file.to_string());
+        self
+    }
+
+    pub(crate) fn at(mut self, file: &str, location: Location) -> Self {
+        self.line = Some(location.line());
+        self.column = Some(location.column());
+        self.in_file(file)
+    }
+
+    pub(crate) fn with_context(self, context: &ErrorContext) -> Self {
+        let error = &context.error;
+        let mut diagnostic = self.at(&context.file, context.location);
+        let preceding_lines = lines_before(error.source(), error.start());
+        diagnostic.first_line = context.location.line().saturating_sub(preceding_lines);
+        diagnostic.spans.push(Label {
+            start: error.start(),
+            length: error.length(),
+            line: context.location.line(),
+            column: context.location.column(),
+            primary: true,
+            message: None,
+        });
+        diagnostic.source = Some(error.source().to_string());
+        diagnostic
+    }
+
+    /// The first occurrence is the primary span, every repetition is a secondary one
+    pub(crate) fn with_duplicates(self, context: &Duplicates) -> Self {
+        let mut diagnostic = self.in_file(&context.file);
+        diagnostic.first_line = context.location.line();
+
+        let mut spans = context.errors.iter().collect::<Vec<_>>();
+        spans.sort_by_key(|span| span.start);
+        for (i, span) in spans.into_iter().enumerate() {
+            let (line, column) = position_of(&context.source, span.start, context.location);
+            diagnostic.spans.push(Label {
+                start: span.start,
+                length: span.length,
+                line,
+                column,
+                primary: i == 0,
+                message: (i != 0).then(|| "specified again here".to_string()),
+            });
+        }
+
+        if let Some(primary) = diagnostic.spans.first() {
+            diagnostic.line = Some(primary.line);
+            diagnostic.column = Some(primary.column);
+        }
+        diagnostic.source = Some(context.source.clone());
+        diagnostic
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Diagnostic is always serializable")
+    }
+
+    fn write_source(&self, source: &str, gutter: usize, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut offset = 0;
+        for (i, text) in source.split('\n').enumerate() {
+            let end = offset + text.len();
+            let text = text.strip_suffix('\r').unwrap_or(text);
+            writeln!(f, "{:>gutter$} | {text}", self.first_line + i)?;
+
+            let mut markers = vec![' '; text.chars().count()];
+            let mut note = None;
+            for label in &self.spans {
+                //Empty spans still get a single marker
+                let label_end = label.start + label.length.max(1);
+                if label_end <= offset || label.start > end {
+                    continue;
+                }
+
+                let start = label.start.max(offset);
+                let stop = label_end.min(end);
+
+                let from = char_index(text, start - offset);
+                let to = char_index(text, stop - offset).max(from + 1);
+                if markers.len() < to {
+                    markers.resize(to, ' ');
+                }
+                let marker = if label.primary { '^' } else { '-' };
+                markers[from..to].fill(marker);
+                if label.message.is_some() {
+                    note = label.message.as_deref();
+                }
+            }
+
+            if markers.iter().any(|marker| *marker != ' ') {
+                let markers = markers.into_iter().collect::<String>();
+                match note {
+                    Some(note) => writeln!(f, "{:gutter$} | {} {note}", "", markers.trim_end())?,
+                    None => writeln!(f, "{:gutter$} | {}", "", markers.trim_end())?,
+                }
+            }
+            offset = end + 1;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "error[{}]: {}", self.code, self.message)?;
+
+        let lines = self
+            .source
+            .as_deref()
+            .map_or(0, |source| source.split('\n').count());
+        let last_line = self.first_line + lines.saturating_sub(1);
+        let gutter = last_line.max(self.line.unwrap_or(0)).to_string().len();
+
+        match (&self.file, self.line, self.column) {
+            (Some(file), Some(line), Some(column)) => {
+                writeln!(f, "{:gutter$}--> {file}:{line}:{column}", "")?
+            }
+            (Some(file), _, _) => writeln!(f, "{:gutter$}--> {file}", "")?,
+            _ => {}
+        }
+
+        if let Some(source) = &self.source {
+            writeln!(f, "{:gutter$} |", "")?;
+            self.write_source(source, gutter, f)?;
+        }
+
+        if let Some(help) = &self.help {
+            writeln!(f, "{:gutter$} = help: {help}", "")?;
+        }
+        Ok(())
+    }
+}
+
+fn lines_before(source: &str, offset: usize) -> usize {
+    source.as_bytes()[..offset.min(source.len())]
+        .iter()
+        .filter(|byte| **byte == b'\n')
+        .count()
+}
+
+/// Line and column of `offset` in a source which starts at `location`, columns count characters
+fn position_of(source: &str, offset: usize, location: Location) -> (usize, usize) {
+    let before = &source.as_bytes()[..offset.min(source.len())];
+    let columns = |bytes: &[u8]| String::from_utf8_lossy(bytes).chars().count();
+    match before.iter().rposition(|byte| *byte == b'\n') {
+        Some(newline) => (
+            location.line() + lines_before(source, offset),
+            columns(&before[newline..]),
+        ),
+        None => (location.line(), location.column() + columns(before)),
+    }
+}
+
+/// Byte offset in the line to the index of the marker
+fn char_index(text: &str, offset: usize) -> usize {
+    match text.get(..offset) {
+        Some(prefix) => prefix.chars().count(),
+        None => offset.min(text.len()),
+    }
+}
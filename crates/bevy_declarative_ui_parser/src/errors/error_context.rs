@@ -1,19 +1,21 @@
 use crate::position::{ErrorSpan, Location};
 use std::fmt::{Display, Formatter};
 
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct ErrorContext {
     pub file: String,
     pub location: Location,
-    pub error: ErrorSpan,
+    /// Boxed, every variant of [`XmlLayoutError`](crate::errors::XmlLayoutError) carries a
+    /// context and the error stays small enough to return
+    pub error: Box<ErrorSpan>,
 }
 
 impl ErrorContext {
-    pub const fn new(file: String, location: Location, error: ErrorSpan) -> Self {
+    pub fn new(file: String, location: Location, error: ErrorSpan) -> Self {
         Self {
             file,
             location,
-            error,
+            error: Box::new(error),
         }
     }
 }
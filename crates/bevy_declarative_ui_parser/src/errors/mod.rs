@@ -1,7 +1,9 @@
+mod diagnostic;
 mod duplicates;
 mod error_context;
 mod io_error;
 
+pub use diagnostic::{Diagnostic, Label};
 pub use duplicates::Duplicates;
 pub use error_context::ErrorContext;
 use io_error::IoError;
@@ -28,15 +30,123 @@ pub enum XmlLayoutError {
         char: char,
     },
 
+    InvalidReference {
+        context: ErrorContext,
+        reference: String,
+    },
+
+    UnboundPrefix {
+        context: ErrorContext,
+        prefix: String,
+    },
+
     EndOfFile {
         file: String,
         location: Location,
     },
 
+    UnclosedComment {
+        context: ErrorContext,
+    },
+
     MissingLayout {
         file: String,
     },
 
+    /// The tag is not registered in the library of the runtime loader
+    UnknownComponent {
+        file: String,
+        name: String,
+        namespace: String,
+    },
+
+    /// The file of an `<Include>` could not be read
+    UnreadableInclude {
+        context: ErrorContext,
+        path: String,
+        message: String,
+    },
+
+    /// Layouts which include each other, the first file is repeated at the end
+    IncludeCycle {
+        context: ErrorContext,
+        cycle: Vec<String>,
+    },
+
+    /// `<Include>` sets a property which the included layout does not declare
+    UnknownIncludeProperty {
+        context: ErrorContext,
+        path: String,
+        name: String,
+    },
+
+    /// Error of an included layout, the location is the `<Include>` in the including layout
+    InIncludedLayout {
+        file: String,
+        location: Location,
+        error: Box<XmlLayoutError>,
+    },
+
+    /// `<If test>` or `<Switch value>` is not a binding
+    InvalidCondition {
+        context: ErrorContext,
+    },
+
+    /// `<Else>` which does not follow an `<If>`, or `<Case>` outside of a `<Switch>`
+    MisplacedCondition {
+        context: ErrorContext,
+        current: String,
+        expected: &'static str,
+    },
+
+    /// `<Define>` whose name or parameter can not be used
+    InvalidDefine {
+        context: ErrorContext,
+        name: String,
+        reason: &'static str,
+    },
+
+    /// Instance of a `<Define>` with an attribute which is not a parameter
+    UnknownArgument {
+        context: ErrorContext,
+        define: String,
+        name: String,
+    },
+
+    /// Instance of a `<Define>` without one of its parameters
+    MissingArgument {
+        context: ErrorContext,
+        define: String,
+        name: String,
+    },
+
+    /// `<Slot>` outside of a `<Define>`, or `<Fill>` outside of an instance
+    MisplacedSlot {
+        context: ErrorContext,
+        current: String,
+        expected: &'static str,
+    },
+
+    /// `<Fill>` of a slot which is not in the `<Define>`
+    UnknownSlot {
+        context: ErrorContext,
+        define: String,
+        name: String,
+    },
+
+    /// `<Style>` whose class can not be used
+    InvalidStyle {
+        context: ErrorContext,
+        class: String,
+        reason: &'static str,
+    },
+
+    /// `class` of a container which is not declared in `<Styles>`
+    UnknownClass {
+        context: ErrorContext,
+        class: String,
+    },
+
     MissingAttribute {
         context: ErrorContext,
         attribute: &'static str,
@@ -56,7 +166,7 @@ pub enum XmlLayoutError {
     MismatchedEndTag {
         context: ErrorContext,
         current: String,
-        expected: &'static str,
+        expected: String,
     },
 
     ExceptedValue {
@@ -73,10 +183,39 @@ pub enum XmlLayoutError {
         name: String,
     },
 
+    UnknownBindingMode {
+        context: ErrorContext,
+        name: String,
+    },
+
+    UnexpectedUnnamedParameter {
+        context: ErrorContext,
+        parameter: String,
+    },
+
+    InvalidTemplateSource {
+        context: ErrorContext,
+    },
+
     DuplicateParam {
-        context: Duplicates,
+        context: Box<Duplicates>,
         name: String,
     },
+
+    InvalidFilter {
+        context: ErrorContext,
+        message: String,
+    },
+
+    InvalidPath {
+        context: ErrorContext,
+        message: String,
+    },
+
+    InvalidInterpolation {
+        context: ErrorContext,
+        message: String,
+    },
 }
 
 impl From<std::io::Error> for XmlLayoutError {
@@ -89,131 +228,299 @@ impl std::error::Error for XmlLayoutError {}
 
 impl std::fmt::Display for XmlLayoutError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            XmlLayoutError::Io(error) => write!(f, "Could not load file: {error}"),
-            XmlLayoutError::Utf8Error(error) => write!(f, "Could not read file: {error}"),
-            XmlLayoutError::UnexpectedChar {
-                context,
-                expected,
-                found,
-            } => write_single_error(
-                format!("Unexpected char: Expected '{expected}', but found '{found}'"),
-                context,
-                f,
-            ),
+        self.diagnostic().fmt(f)
+    }
+}
 
-            XmlLayoutError::ExpectedIdentifier { context, found } => write_single_error(
-                format!("Expected identifier, but found '{found}'"),
-                context,
-                f,
-            ),
+impl XmlLayoutError {
+    /// Stable identifier of the error kind, e.g. `E0304`
+    pub const fn code(&self) -> &'static str {
+        match self {
+            XmlLayoutError::Io(_) => "E0001",
+            XmlLayoutError::Utf8Error(_) => "E0002",
+            XmlLayoutError::UnexpectedChar { .. } => "E0100",
+            XmlLayoutError::ExpectedIdentifier { .. } => "E0101",
+            XmlLayoutError::InvalidChar { .. } => "E0102",
+            XmlLayoutError::InvalidReference { .. } => "E0103",
+            XmlLayoutError::UnboundPrefix { .. } => "E0104",
+            XmlLayoutError::EndOfFile { .. } => "E0105",
+            XmlLayoutError::UnclosedComment { .. } => "E0106",
+            XmlLayoutError::MissingLayout { .. } => "E0200",
+            XmlLayoutError::MissingAttribute { .. } => "E0201",
+            XmlLayoutError::EmptyAttribute { .. } => "E0202",
+            XmlLayoutError::UnexpectedTag { .. } => "E0203",
+            XmlLayoutError::MismatchedEndTag { .. } => "E0204",
+            XmlLayoutError::ExceptedValue { .. } => "E0205",
+            XmlLayoutError::InvalidTemplateSource { .. } => "E0206",
+            XmlLayoutError::UnknownComponent { .. } => "E0207",
+            XmlLayoutError::UnreadableInclude { .. } => "E0208",
+            XmlLayoutError::IncludeCycle { .. } => "E0209",
+            XmlLayoutError::UnknownIncludeProperty { .. } => "E0210",
+            XmlLayoutError::InIncludedLayout { error, .. } => error.code(),
+            XmlLayoutError::InvalidCondition { .. } => "E0211",
+            XmlLayoutError::MisplacedCondition { .. } => "E0212",
+            XmlLayoutError::InvalidDefine { .. } => "E0213",
+            XmlLayoutError::UnknownArgument { .. } => "E0214",
+            XmlLayoutError::MissingArgument { .. } => "E0215",
+            XmlLayoutError::MisplacedSlot { .. } => "E0216",
+            XmlLayoutError::UnknownSlot { .. } => "E0217",
+            XmlLayoutError::InvalidStyle { .. } => "E0218",
+            XmlLayoutError::UnknownClass { .. } => "E0219",
+            XmlLayoutError::MissingParameter { .. } => "E0300",
+            XmlLayoutError::UnknownBindingType { .. } => "E0301",
+            XmlLayoutError::UnknownBindingMode { .. } => "E0302",
+            XmlLayoutError::UnexpectedUnnamedParameter { .. } => "E0303",
+            XmlLayoutError::DuplicateParam { .. } => "E0304",
+            XmlLayoutError::InvalidFilter { .. } => "E0305",
+            XmlLayoutError::InvalidPath { .. } => "E0306",
+            XmlLayoutError::InvalidInterpolation { .. } => "E0307",
+        }
+    }
 
-            XmlLayoutError::InvalidChar { context, char } => {
-                write_single_error(format!("Invalid character: '{char}'"), context, f)
+    pub fn message(&self) -> String {
+        match self {
+            XmlLayoutError::Io(error) => format!("Could not load file: {error}"),
+            XmlLayoutError::Utf8Error(error) => format!("Could not read file: {error}"),
+            XmlLayoutError::UnexpectedChar {
+                expected, found, ..
+            } => format!("Unexpected char: Expected '{expected}', but found '{found}'"),
+            XmlLayoutError::ExpectedIdentifier { found, .. } => {
+                format!("Expected identifier, but found '{found}'")
             }
-
-            XmlLayoutError::EndOfFile { file, location } => {
-                write!(f, "[{file}:{location}] Unexpected end of file")
+            XmlLayoutError::InvalidChar { char, .. } => format!("Invalid character: '{char}'"),
+            XmlLayoutError::InvalidReference { reference, .. } => {
+                format!("Invalid character reference: '{reference}'")
             }
-            XmlLayoutError::MissingLayout { file } => {
-                write!(f, "[{file}:0:0] Missing <Layout> tag")
+            XmlLayoutError::UnboundPrefix { prefix, .. } => {
+                format!("Namespace prefix '{prefix}' is not declared")
             }
-
-            XmlLayoutError::MissingAttribute { context, attribute } => {
-                write_single_error(format!("Missing attribute: {attribute}"), context, f)
+            XmlLayoutError::EndOfFile { .. } => "Unexpected end of file".to_string(),
+            XmlLayoutError::UnclosedComment { .. } => "Unclosed comment".to_string(),
+            XmlLayoutError::MissingLayout { .. } => "Missing <Layout> tag".to_string(),
+            XmlLayoutError::UnknownComponent {
+                name, namespace, ..
+            } => format!("Unknown component: {name} in namespace '{namespace}'"),
+            XmlLayoutError::UnreadableInclude { path, message, .. } => {
+                format!("Could not read included layout '{path}': {message}")
             }
-
-            XmlLayoutError::EmptyAttribute { context, attribute } => {
-                write_single_error(format!("Empty attribute: {attribute}"), context, f)
+            XmlLayoutError::IncludeCycle { cycle, .. } => {
+                format!("Include cycle: {}", cycle.join(" -> "))
+            }
+            XmlLayoutError::UnknownIncludeProperty { path, name, .. } => {
+                format!("Unknown property {name} of included layout '{path}'")
+            }
+            XmlLayoutError::InIncludedLayout {
+                file,
+                location,
+                error,
+            } => format!("{} (included from {file}:{location})", error.message()),
+            XmlLayoutError::InvalidCondition { .. } => {
+                "Condition must be a Resource, Component or Item binding or a layout property"
+                    .to_string()
+            }
+            XmlLayoutError::MisplacedCondition {
+                current, expected, ..
+            } => format!("<{current}> must be placed {expected}"),
+            XmlLayoutError::InvalidDefine { name, reason, .. } => {
+                format!("Invalid <Define> '{name}': {reason}")
+            }
+            XmlLayoutError::UnknownArgument { define, name, .. } => {
+                format!("Unknown parameter {name} of <{define}>")
+            }
+            XmlLayoutError::MissingArgument { define, name, .. } => {
+                format!("Missing parameter {name} of <{define}>")
+            }
+            XmlLayoutError::MisplacedSlot {
+                current, expected, ..
+            } => format!("<{current}> must be placed {expected}"),
+            XmlLayoutError::UnknownSlot { define, name, .. } => {
+                format!("Unknown slot {name} of <{define}>")
+            }
+            XmlLayoutError::InvalidStyle { class, reason, .. } => {
+                format!("Invalid <Style> '{class}': {reason}")
+            }
+            XmlLayoutError::UnknownClass { class, .. } => format!("Unknown class {class}"),
+            XmlLayoutError::MissingAttribute { attribute, .. } => {
+                format!("Missing attribute: {attribute}")
+            }
+            XmlLayoutError::EmptyAttribute { attribute, .. } => {
+                format!("Empty attribute: {attribute}")
             }
-
             XmlLayoutError::UnexpectedTag {
-                context,
-                current,
-                expected,
-            } => write_single_error(
-                format!("Unexpected tag. Expected: {expected:?}, but found {current}"),
-                context,
-                f,
-            ),
-
+                current, expected, ..
+            } => format!("Unexpected tag. Expected: {expected:?}, but found {current}"),
             XmlLayoutError::MismatchedEndTag {
-                context,
-                current,
-                expected,
-            } => write_single_error(
-                format!("Mismatched end tag. Expected {expected}, but found {current}"),
-                context,
-                f,
-            ),
-
-            XmlLayoutError::ExceptedValue { context } => {
-                write_single_error("Expected value", context, f)
+                current, expected, ..
+            } => format!("Mismatched end tag. Expected {expected}, but found {current}"),
+            XmlLayoutError::ExceptedValue { .. } => "Expected value".to_string(),
+            XmlLayoutError::InvalidTemplateSource { .. } => {
+                "ItemTemplate source must be a Resource or Component binding".to_string()
             }
-
-            XmlLayoutError::MissingParameter { context, name } => {
-                write_single_error(format!("Missing parameter: {name}"), context, f)
+            XmlLayoutError::MissingParameter { name, .. } => format!("Missing parameter: {name}"),
+            XmlLayoutError::UnknownBindingType { name, .. } => {
+                format!("Unknown binding type: {name}")
             }
-
-            XmlLayoutError::UnknownBindingType { context, name } => {
-                write_single_error(format!("Unknown binding type: {name}"), context, f)
+            XmlLayoutError::UnknownBindingMode { name, .. } => {
+                format!("Unknown binding mode: {name}")
+            }
+            XmlLayoutError::UnexpectedUnnamedParameter { parameter, .. } => {
+                format!("Unexpected unnamed parameter: '{parameter}'")
+            }
+            XmlLayoutError::DuplicateParam { name, .. } => {
+                format!("Parameter '{name}' specified more than once")
+            }
+            XmlLayoutError::InvalidFilter { message, .. } => format!("Invalid filter: {message}"),
+            XmlLayoutError::InvalidPath { message, .. } => format!("Invalid path: {message}"),
+            XmlLayoutError::InvalidInterpolation { message, .. } => {
+                format!("Invalid interpolation: {message}")
             }
-
-            XmlLayoutError::DuplicateParam { context, name } => write_multiple_error(
-                format!("Parameter '{name}' specified more than once"),
-                context,
-                f,
-            ),
         }
     }
-}
-
-fn write_single_error(
-    message: impl Into<String>,
-    context: &ErrorContext,
-    f: &mut std::fmt::Formatter<'_>,
-) -> std::fmt::Result {
-    let file = &context.file;
-    let location = &context.location;
-    let error = &context.error;
-
-    writeln!(f, "error: {}", message.into())?;
-    writeln!(f, "  --> {file}:{}:{}", location.line(), location.column())?;
-    writeln!(f, "   |")?;
-    writeln!(f, "   |      {}", error.source())?;
-    writeln!(
-        f,
-        "   |      {}{}",
-        " ".repeat(error.start()),
-        "^".repeat(error.length())
-    )?;
-    Ok(())
-}
 
-fn write_multiple_error(
-    message: impl Into<String>,
-    context: &Duplicates,
-    f: &mut std::fmt::Formatter<'_>,
-) -> std::fmt::Result {
-    let file = &context.file;
-    let location = &context.location;
-
-    writeln!(f, "error: {}", message.into())?;
-    writeln!(f, "  --> {file}:{}:{}", location.line(), location.column())?;
-    writeln!(f, "   |")?;
-    writeln!(f, "   |      {}", context.source)?;
-
-    let mut marker_line = vec![' '; context.source.len()];
-
-    for span in context.errors.iter() {
-        let start = span.start.min(context.source.len());
-        let end = (span.start + span.length).min(context.source.len());
+    /// Hint on how to fix the error
+    pub fn help(&self) -> Option<String> {
+        let help = match self {
+            XmlLayoutError::InIncludedLayout { error, .. } => return error.help(),
+            XmlLayoutError::ExpectedIdentifier { .. } => {
+                "Names start with a letter, '_' or ':'".to_string()
+            }
+            XmlLayoutError::InvalidReference { .. } => {
+                "Use &amp;, &lt;, &gt;, &quot;, &apos; or a numeric reference like &#x2764;"
+                    .to_string()
+            }
+            XmlLayoutError::UnboundPrefix { prefix, .. } => {
+                format!("Declare it with xmlns:{prefix}=\"...\" on this tag or on a parent")
+            }
+            XmlLayoutError::EndOfFile { .. } => "Check that every tag is closed".to_string(),
+            XmlLayoutError::UnclosedComment { .. } => "Close the comment with -->".to_string(),
+            XmlLayoutError::MissingLayout { .. } => {
+                "Put the content into <Layout>...</Layout>".to_string()
+            }
+            XmlLayoutError::UnknownComponent { .. } => {
+                "Register it with XmlLibrary::add_component or add_namespaced_component".to_string()
+            }
+            XmlLayoutError::UnreadableInclude { .. } => {
+                "The path is relative to the including layout".to_string()
+            }
+            XmlLayoutError::IncludeCycle { .. } => {
+                "A layout can not include itself, directly or through other layouts".to_string()
+            }
+            XmlLayoutError::UnknownIncludeProperty { .. } => {
+                "Only the <LocalResources> properties of the included layout can be set".to_string()
+            }
+            XmlLayoutError::InvalidCondition { .. } => {
+                "Use {Resource Name, Path=...}, {Component Name, Path=...}, {Item path} or {Property}"
+                    .to_string()
+            }
+            XmlLayoutError::MisplacedCondition { .. } => {
+                "<Else> follows the </If> it belongs to, <Case> is a child of <Switch>".to_string()
+            }
+            XmlLayoutError::UnknownArgument { name, .. } => {
+                format!("Add {name} to the params of the <Define>")
+            }
+            XmlLayoutError::MissingArgument { name, .. } => {
+                format!("Add {name}=\"...\" to the element")
+            }
+            XmlLayoutError::MisplacedSlot { .. } => {
+                "<Slot> belongs to the content of a <Define>, <Fill> is a child of its instances"
+                    .to_string()
+            }
+            XmlLayoutError::UnknownSlot { name, .. } => {
+                format!("Add <Slot name=\"{name}\"/> to the <Define>")
+            }
+            XmlLayoutError::UnknownClass { class, .. } => {
+                format!("Declare <Style class=\"{class}\"> in the <Styles> of the layout")
+            }
+            XmlLayoutError::MismatchedEndTag { expected, .. } => {
+                format!("Close <{expected}> with </{expected}>")
+            }
+            XmlLayoutError::ExceptedValue { .. } => {
+                "Bindings are not allowed here, use a plain value".to_string()
+            }
+            XmlLayoutError::InvalidTemplateSource { .. } => {
+                "Use {Resource Name, Path=...} or {Component Name, Path=...}".to_string()
+            }
+            XmlLayoutError::MissingParameter { name, .. } => {
+                format!("Add {name}=... to the binding")
+            }
+            XmlLayoutError::UnknownBindingType { .. } => {
+                "Expected Resource, Component, Item or Asset".to_string()
+            }
+            XmlLayoutError::UnknownBindingMode { .. } => {
+                "Expected Read, Write, ReadWrite or ReadOnce".to_string()
+            }
+            XmlLayoutError::UnexpectedUnnamedParameter { .. } => {
+                "Only the first parameter can be unnamed, use Name=value".to_string()
+            }
+            XmlLayoutError::DuplicateParam { .. } => "Remove all but one of them".to_string(),
+            XmlLayoutError::InvalidInterpolation { .. } => {
+                "Use {{ and }} for literal braces".to_string()
+            }
+            _ => return None,
+        };
+        Some(help)
+    }
 
-        for i in start..end {
-            marker_line[i] = '^';
+    /// Source position of the errors which point into the layout
+    pub const fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            XmlLayoutError::UnexpectedChar { context, .. }
+            | XmlLayoutError::ExpectedIdentifier { context, .. }
+            | XmlLayoutError::InvalidChar { context, .. }
+            | XmlLayoutError::InvalidReference { context, .. }
+            | XmlLayoutError::UnboundPrefix { context, .. }
+            | XmlLayoutError::UnclosedComment { context }
+            | XmlLayoutError::UnreadableInclude { context, .. }
+            | XmlLayoutError::IncludeCycle { context, .. }
+            | XmlLayoutError::UnknownIncludeProperty { context, .. }
+            | XmlLayoutError::InvalidCondition { context }
+            | XmlLayoutError::MisplacedCondition { context, .. }
+            | XmlLayoutError::InvalidDefine { context, .. }
+            | XmlLayoutError::UnknownArgument { context, .. }
+            | XmlLayoutError::MissingArgument { context, .. }
+            | XmlLayoutError::MisplacedSlot { context, .. }
+            | XmlLayoutError::UnknownSlot { context, .. }
+            | XmlLayoutError::InvalidStyle { context, .. }
+            | XmlLayoutError::UnknownClass { context, .. }
+            | XmlLayoutError::MissingAttribute { context, .. }
+            | XmlLayoutError::EmptyAttribute { context, .. }
+            | XmlLayoutError::UnexpectedTag { context, .. }
+            | XmlLayoutError::MismatchedEndTag { context, .. }
+            | XmlLayoutError::ExceptedValue { context }
+            | XmlLayoutError::InvalidTemplateSource { context }
+            | XmlLayoutError::MissingParameter { context, .. }
+            | XmlLayoutError::UnknownBindingType { context, .. }
+            | XmlLayoutError::UnknownBindingMode { context, .. }
+            | XmlLayoutError::UnexpectedUnnamedParameter { context, .. }
+            | XmlLayoutError::InvalidFilter { context, .. }
+            | XmlLayoutError::InvalidPath { context, .. }
+            | XmlLayoutError::InvalidInterpolation { context, .. } => Some(context),
+            XmlLayoutError::InIncludedLayout { error, .. } => error.context(),
+            XmlLayoutError::Io(_)
+            | XmlLayoutError::Utf8Error(_)
+            | XmlLayoutError::EndOfFile { .. }
+            | XmlLayoutError::MissingLayout { .. }
+            | XmlLayoutError::UnknownComponent { .. }
+            | XmlLayoutError::DuplicateParam { .. } => None,
         }
     }
 
-    writeln!(f, "   |      {}", marker_line.iter().collect::<String>())?;
-    Ok(())
+    pub fn diagnostic(&self) -> Diagnostic {
+        let diagnostic = Diagnostic::new(self.code(), self.message(), self.help());
+        match self {
+            XmlLayoutError::EndOfFile { file, location } => diagnostic.at(file, *location),
+            XmlLayoutError::MissingLayout { file } => diagnostic.in_file(file),
+            XmlLayoutError::UnknownComponent { file, .. } => diagnostic.in_file(file),
+            XmlLayoutError::DuplicateParam { context, .. } => diagnostic.with_duplicates(context),
+            //Points into the included layout, the message names the including one
+            XmlLayoutError::InIncludedLayout { error, .. } => Diagnostic {
+                message: self.message(),
+                ..error.diagnostic()
+            },
+            _ => match self.context() {
+                Some(context) => diagnostic.with_context(context),
+                None => diagnostic,
+            },
+        }
+    }
 }
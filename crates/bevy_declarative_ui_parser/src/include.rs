@@ -0,0 +1,380 @@
+use crate::errors::ErrorContext;
+use crate::{Id, LayoutReader, UiNode, XmlLayout, XmlLayoutError, XmlTag};
+use std::collections::HashMap;
+
+/// `<Include path=".." id=".." Name="value"/>`, a container which is replaced by the content of
+/// the included layout when the includes are resolved with [`IncludedFiles`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Include {
+    /// Path of the included layout, relative to the including one
+    pub path: String,
+    /// Id of the container which takes the place of the element
+    pub node: Id,
+    /// Values of `<LocalResources>` properties of the included layout, by name
+    pub overrides: Vec<(String, String)>,
+    /// The element in the including layout, errors of the included layout point to it
+    pub context: ErrorContext,
+}
+
+impl Include {
+    pub fn new(reader: &LayoutReader, tag: &XmlTag, node: Id) -> Result<Self, XmlLayoutError> {
+        let path = reader.parse_required_attribute(tag, "path")?;
+        if path.value().is_empty() {
+            return Err(reader.err_empty_attribute(tag, "path"));
+        }
+
+        let overrides = tag
+            .attributes()
+            .iter()
+            .filter(|attribute| !matches!(attribute.name(), "path" | "id"))
+            .map(|attribute| (attribute.name().to_string(), attribute.value().to_string()))
+            .collect();
+
+        Ok(Include {
+            path: path.value().to_string(),
+            node,
+            overrides,
+            context: reader.tag_context(tag),
+        })
+    }
+}
+
+/// Joins the path of an `<Include>` to the directory of the including file,
+/// `.` and `..` segments are resolved
+pub fn include_path(file: &str, path: &str) -> String {
+    let mut segments = file.split(['/', '\\']).collect::<Vec<_>>();
+    segments.pop();
+    for segment in path.split(['/', '\\']) {
+        match segment {
+            "" | "." => {}
+            ".." if segments
+                .last()
+                .is_some_and(|last| !last.is_empty() && *last != "..") =>
+            {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+/// Layouts of the files included by a layout, read by the caller so the runtime loader can read
+/// them as asset dependencies and the generator from the file system:
+///
+/// ```ignore
+/// let mut included = IncludedFiles::new(path, &layout);
+/// while let Some(file) = included.next_pending() {
+///     included.add(file, read(&file));
+/// }
+/// let layout = included.resolve(path, layout)?;
+/// ```
+#[derive(Default)]
+pub struct IncludedFiles {
+    /// Parsed layouts by path, or the reason the file could not be read
+    files: HashMap<String, Result<XmlLayout, Unavailable>>,
+    pending: Vec<String>,
+}
+
+enum Unavailable {
+    Unreadable(String),
+    Invalid(XmlLayoutError),
+}
+
+impl IncludedFiles {
+    pub fn new(file: &str, layout: &XmlLayout) -> Self {
+        let mut included = Self::default();
+        included.queue(file, layout);
+        included
+    }
+
+    fn queue(&mut self, file: &str, layout: &XmlLayout) {
+        for include in &layout.includes {
+            let path = include_path(file, &include.path);
+            if !self.files.contains_key(&path) && !self.pending.contains(&path) {
+                self.pending.push(path);
+            }
+        }
+    }
+
+    /// Next file to read, the files included by the added ones are queued too
+    pub fn next_pending(&mut self) -> Option<String> {
+        self.pending.pop()
+    }
+
+    /// Parses the content of an included file, or keeps the read error for the `<Include>`
+    pub fn add(&mut self, file: String, content: Result<String, String>) {
+        let layout = match content {
+            Ok(content) => LayoutReader::new(&content, &file)
+                .parse()
+                .map_err(Unavailable::Invalid),
+            Err(message) => Err(Unavailable::Unreadable(message)),
+        };
+        if let Ok(layout) = &layout {
+            self.queue(&file, layout);
+        }
+        self.files.insert(file, layout);
+    }
+
+    /// Reads every pending file with `read`
+    pub fn read_with(mut self, mut read: impl FnMut(&str) -> Result<String, String>) -> Self {
+        while let Some(file) = self.next_pending() {
+            let content = read(&file);
+            self.add(file, content);
+        }
+        self
+    }
+
+    /// Paths of the files read so far
+    pub fn files(&self) -> impl Iterator<Item = &str> {
+        self.files.keys().map(String::as_str)
+    }
+
+    /// Replaces every `<Include>` of the layout of `file` by the content of the included layout.
+    /// Ids of the included containers and templates are renumbered after the ids of the
+    /// including layout and custom ids are scoped by the id of the `<Include>`, like
+    /// `Header.Close`, so a file can be included twice. Its usings and properties are added, a
+    /// property declared by the including layout or by an earlier include is kept
+    pub fn resolve(
+        mut self,
+        file: &str,
+        mut layout: XmlLayout,
+    ) -> Result<XmlLayout, XmlLayoutError> {
+        let mut ids = Renumber::after(&layout);
+        self.splice(&mut layout, &mut vec![file.to_string()], &mut ids)?;
+        Ok(layout)
+    }
+
+    fn splice(
+        &mut self,
+        layout: &mut XmlLayout,
+        stack: &mut Vec<String>,
+        ids: &mut Renumber,
+    ) -> Result<(), XmlLayoutError> {
+        for include in std::mem::take(&mut layout.includes) {
+            let path = include_path(stack.last().unwrap(), &include.path);
+            if let Some(start) = stack.iter().position(|file| *file == path) {
+                let mut cycle = stack[start..].to_vec();
+                cycle.push(path);
+                return Err(XmlLayoutError::IncludeCycle {
+                    context: include.context,
+                    cycle,
+                });
+            }
+
+            let mut included = match self.files.get(&path) {
+                Some(Ok(included)) => included.clone(),
+                _ => return Err(self.unavailable(include, path)),
+            };
+
+            //Renumbered first, so its own includes are scoped by their final ids
+            ids.renumber(&mut included, &include.node);
+            stack.push(path.clone());
+            let result = self.splice(&mut included, stack, ids);
+            stack.pop();
+            result.map_err(|error| include.wrap(error))?;
+
+            include.set_properties(&path, &mut included)?;
+            include.insert(layout, included);
+        }
+        Ok(())
+    }
+
+    /// Error of an included file which could not be read or parsed
+    fn unavailable(&mut self, include: Include, path: String) -> XmlLayoutError {
+        let message = match self.files.remove(&path) {
+            Some(Err(Unavailable::Invalid(error))) => return include.wrap(error),
+            Some(Err(Unavailable::Unreadable(message))) => message,
+            _ => "the file was not read".to_string(),
+        };
+        XmlLayoutError::UnreadableInclude {
+            context: include.context,
+            path,
+            message,
+        }
+    }
+}
+
+impl Include {
+    fn wrap(&self, error: XmlLayoutError) -> XmlLayoutError {
+        XmlLayoutError::InIncludedLayout {
+            file: self.context.file.clone(),
+            location: self.context.location,
+            error: Box::new(error),
+        }
+    }
+
+    fn set_properties(&self, path: &str, included: &mut XmlLayout) -> Result<(), XmlLayoutError> {
+        for (name, value) in &self.overrides {
+            let Some(property) = included
+                .properties
+                .iter_mut()
+                .find(|property| !property.global && property.name == *name)
+            else {
+                return Err(XmlLayoutError::UnknownIncludeProperty {
+                    context: self.context.clone(),
+                    path: path.to_string(),
+                    name: name.clone(),
+                });
+            };
+            property.value = value.clone();
+        }
+        Ok(())
+    }
+
+    /// The container of the element takes the content of the root container of the included
+    /// layout, the templates it owns move to the container. Its classes are applied first, they
+    /// are only declared for the included file
+    fn insert(&self, layout: &mut XmlLayout, mut included: XmlLayout) {
+        included.apply_styles();
+        let root = Id::Custom("Root".into());
+        if let Some(node) = layout
+            .root_nodes
+            .iter_mut()
+            .chain(
+                layout
+                    .templates
+                    .iter_mut()
+                    .flat_map(|template| &mut template.nodes),
+            )
+            .find_map(|node| node.find_mut(&self.node))
+        {
+            node.tag.name = "Container".to_string();
+            node.tag
+                .attributes
+                .retain(|attribute| attribute.name == "id");
+            for root in included.root_nodes {
+                node.components.extend(root.components);
+                node.children.extend(root.children);
+            }
+        }
+
+        layout
+            .templates
+            .extend(included.templates.into_iter().map(|mut template| {
+                if template.owner == root {
+                    template.owner = self.node.clone();
+                }
+                template
+            }));
+        layout.usings.extend(included.usings);
+        for property in included.properties {
+            if !layout.properties.iter().any(|declared| {
+                declared.name == property.name && declared.global == property.global
+            }) {
+                layout.properties.push(property);
+            }
+        }
+    }
+}
+
+impl UiNode {
+    fn find_mut(&mut self, id: &Id) -> Option<&mut UiNode> {
+        if self.id == *id {
+            return Some(self);
+        }
+        self.children
+            .iter_mut()
+            .find_map(|child| child.find_mut(id))
+    }
+}
+
+/// Numbers of the next ids, after the ones of the including layout
+struct Renumber {
+    node: u64,
+    template: u64,
+}
+
+impl Renumber {
+    fn after(layout: &XmlLayout) -> Self {
+        let mut ids = Renumber {
+            node: 0,
+            template: 0,
+        };
+        layout.visit_ids(&mut |id| match id {
+            Id::Default(number) => ids.node = ids.node.max(*number + 1),
+            Id::Template(number) => ids.template = ids.template.max(*number + 1),
+            _ => {}
+        });
+        ids
+    }
+
+    /// Gives new numbers to the generated ids of the layout and puts `scope.` before its custom
+    /// ids, owners of templates and `<Include>` elements follow them. The root container is
+    /// replaced by the container of the scope
+    fn renumber(&mut self, layout: &mut XmlLayout, scope: &Id) {
+        let mut renamed: Vec<(Id, Id)> = vec![];
+        layout.visit_ids_mut(&mut |id| {
+            let new = match id {
+                Id::Custom(name) if name != "Root" => Id::Custom(format!("{scope}.{name}")),
+                Id::Default(_) => {
+                    self.node += 1;
+                    Id::Default(self.node - 1)
+                }
+                Id::Template(_) => match renamed.iter().find(|(old, _)| old == id) {
+                    Some((_, new)) => new.clone(),
+                    None => {
+                        self.template += 1;
+                        Id::Template(self.template - 1)
+                    }
+                },
+                _ => return,
+            };
+            renamed.push((id.clone(), new.clone()));
+            *id = new;
+        });
+        let owners = layout
+            .templates
+            .iter_mut()
+            .map(|template| &mut template.owner)
+            .chain(layout.includes.iter_mut().map(|include| &mut include.node));
+        for owner in owners {
+            if let Some((_, new)) = renamed.iter().find(|(old, _)| old == owner) {
+                *owner = new.clone();
+            }
+        }
+    }
+}
+
+impl XmlLayout {
+    /// Ids of the containers and templates, in the order of the tree
+    fn visit_ids(&self, visit: &mut impl FnMut(&Id)) {
+        fn visit_node(node: &UiNode, visit: &mut impl FnMut(&Id)) {
+            visit(&node.id);
+            node.children
+                .iter()
+                .for_each(|child| visit_node(child, visit));
+        }
+
+        self.root_nodes
+            .iter()
+            .for_each(|root| visit_node(root, visit));
+        self.templates.iter().for_each(|template| {
+            visit(&template.id);
+            template
+                .nodes
+                .iter()
+                .for_each(|root| visit_node(root, visit));
+        });
+    }
+
+    fn visit_ids_mut(&mut self, visit: &mut impl FnMut(&mut Id)) {
+        fn visit_node(node: &mut UiNode, visit: &mut impl FnMut(&mut Id)) {
+            visit(&mut node.id);
+            node.children
+                .iter_mut()
+                .for_each(|child| visit_node(child, visit));
+        }
+
+        self.root_nodes
+            .iter_mut()
+            .for_each(|root| visit_node(root, visit));
+        self.templates.iter_mut().for_each(|template| {
+            visit(&mut template.id);
+            template
+                .nodes
+                .iter_mut()
+                .for_each(|root| visit_node(root, visit));
+        });
+    }
+}
@@ -1,19 +1,51 @@
 use crate::attribute::{Attribute, parse_attributes};
 use crate::errors::XmlLayoutError;
+#[cfg(feature = "node_span")]
+use crate::position::NodeSpan;
 use crate::{LayoutReader, lexer};
 
 #[derive(PartialEq, Clone, Debug)]
 pub struct Tag {
     pub name: String,
+    /// Resolved namespace of the tag, `None` if the layout does not declare one
+    pub namespace: Option<String>,
+    /// Prefix of the tag in the source, the writer declares it again
+    pub prefix: Option<String>,
     pub attributes: Vec<Attribute>,
+    /// Start tag in the source
+    #[cfg(feature = "node_span")]
+    pub span: NodeSpan,
 }
 
 impl Tag {
     pub fn from(reader: &LayoutReader, xml_tag: lexer::Tag) -> Result<Tag, XmlLayoutError> {
-        let (name, attributes) = xml_tag.into_inner();
-        Ok(Tag {
+        let (tag, mut errors) = Tag::parse(reader, xml_tag);
+        if errors.is_empty() {
+            Ok(tag)
+        } else {
+            Err(errors.remove(0))
+        }
+    }
+
+    /// Keeps the valid attributes and returns the errors of the others
+    pub fn parse(reader: &LayoutReader, xml_tag: lexer::Tag) -> (Tag, Vec<XmlLayoutError>) {
+        let namespace = xml_tag.namespace.clone();
+        let prefix = xml_tag.prefix.map(str::to_string);
+        #[cfg(feature = "node_span")]
+        let span = NodeSpan::new(xml_tag.span, xml_tag.location);
+        let (name, mut attributes) = xml_tag.into_inner();
+        //Attributes of other namespaces, like `xml:lang`, are not addressed to the component
+        attributes
+            .retain(|attribute| attribute.namespace.is_none() || attribute.namespace == namespace);
+        let (attributes, errors) = parse_attributes(reader, attributes);
+        let tag = Tag {
             name,
-            attributes: parse_attributes(reader, attributes)?,
-        })
+            namespace,
+            prefix,
+            attributes,
+            #[cfg(feature = "node_span")]
+            span,
+        };
+        (tag, errors)
     }
 }
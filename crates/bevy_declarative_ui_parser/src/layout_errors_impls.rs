@@ -4,7 +4,11 @@ use crate::errors::ErrorContext;
 use crate::errors::XmlLayoutError;
 use crate::layout_reader::LayoutReader;
 use crate::lexer::Value;
+use crate::lexer::{Attribute, TagEnd};
 use crate::position::*;
+use crate::values::InterpolationError;
+use crate::values::bindings::filter::FilterError;
+use crate::values::bindings::path::PathError;
 
 impl<'a> LayoutReader<'a> {
     pub(super) fn err_missing_layout(&self) -> XmlLayoutError {
@@ -31,6 +35,16 @@ impl<'a> LayoutReader<'a> {
         }
     }
 
+    /// Context of an error at the name of the tag, reported after the tag was read
+    pub(super) fn tag_context(&self, tag: &XmlTag) -> ErrorContext {
+        self.context(
+            tag.span(),
+            tag.location(),
+            tag.location(),
+            tag.identifier().len(),
+        )
+    }
+
     pub(super) fn err_unexpected_tag(
         &self,
         tag: XmlTag,
@@ -48,16 +62,21 @@ impl<'a> LayoutReader<'a> {
         }
     }
 
+    pub(super) fn err_mismatched_end_tag(&self, tag: &TagEnd, expected: &str) -> XmlLayoutError {
+        let current = tag.qualified_name();
+        XmlLayoutError::MismatchedEndTag {
+            context: self.context(tag.span(), tag.location(), tag.location(), current.len()),
+            current,
+            expected: expected.to_string(),
+        }
+    }
+
     pub(super) fn err_empty_attribute(
         &self,
         tag: &XmlTag,
         attribute_name: &'static str,
     ) -> XmlLayoutError {
-        let attribute = tag
-            .attributes()
-            .iter()
-            .find(|a| a.name() == attribute_name)
-            .unwrap();
+        let attribute = tag.attribute(attribute_name).unwrap();
 
         XmlLayoutError::EmptyAttribute {
             context: self.context(
@@ -119,6 +138,165 @@ impl<'a> LayoutReader<'a> {
         }
     }
 
+    pub(super) fn err_unknown_binding_mode(&self, value: &Value) -> XmlLayoutError {
+        XmlLayoutError::UnknownBindingMode {
+            context: self.context(
+                value.span(),
+                value.location(),
+                value.location(),
+                value.value().len(),
+            ),
+            name: value.value().to_string(),
+        }
+    }
+
+    pub(super) fn err_unexpected_unnamed_parameter(
+        &self,
+        value: &Value,
+        parameter: &str,
+    ) -> XmlLayoutError {
+        XmlLayoutError::UnexpectedUnnamedParameter {
+            context: self.context(
+                value.span(),
+                value.location(),
+                value.location(),
+                value.value().len(),
+            ),
+            parameter: parameter.to_string(),
+        }
+    }
+
+    pub(super) fn err_invalid_template_source(&self, value: &Value) -> XmlLayoutError {
+        XmlLayoutError::InvalidTemplateSource {
+            context: self.context(
+                value.span(),
+                value.location(),
+                value.location(),
+                value.value().len(),
+            ),
+        }
+    }
+
+    pub(super) fn err_invalid_condition(&self, value: &Value) -> XmlLayoutError {
+        XmlLayoutError::InvalidCondition {
+            context: self.context(
+                value.span(),
+                value.location(),
+                value.location(),
+                value.value().len(),
+            ),
+        }
+    }
+
+    pub(super) fn err_misplaced_condition(
+        &self,
+        tag: XmlTag,
+        expected: &'static str,
+    ) -> XmlLayoutError {
+        XmlLayoutError::MisplacedCondition {
+            context: self.tag_context(&tag),
+            current: tag.into_identifier(),
+            expected,
+        }
+    }
+
+    pub(super) fn err_invalid_define(
+        &self,
+        tag: &XmlTag,
+        name: &str,
+        reason: &'static str,
+    ) -> XmlLayoutError {
+        XmlLayoutError::InvalidDefine {
+            context: self.tag_context(tag),
+            name: name.to_string(),
+            reason,
+        }
+    }
+
+    pub(super) fn err_unknown_argument(
+        &self,
+        tag: &XmlTag,
+        attribute: &Attribute,
+        define: &str,
+    ) -> XmlLayoutError {
+        XmlLayoutError::UnknownArgument {
+            context: self.context(
+                tag.span(),
+                attribute.location(),
+                attribute.location(),
+                attribute.name().len(),
+            ),
+            define: define.to_string(),
+            name: attribute.name().to_string(),
+        }
+    }
+
+    pub(super) fn err_missing_argument(
+        &self,
+        tag: &XmlTag,
+        define: &str,
+        name: &str,
+    ) -> XmlLayoutError {
+        XmlLayoutError::MissingArgument {
+            context: self.tag_context(tag),
+            define: define.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    pub(super) fn err_misplaced_slot(&self, tag: XmlTag, expected: &'static str) -> XmlLayoutError {
+        XmlLayoutError::MisplacedSlot {
+            context: self.tag_context(&tag),
+            current: tag.into_identifier(),
+            expected,
+        }
+    }
+
+    pub(super) fn err_unknown_slot(
+        &self,
+        tag: &XmlTag,
+        define: &str,
+        name: &str,
+    ) -> XmlLayoutError {
+        let slot = tag.attribute("slot").unwrap();
+        XmlLayoutError::UnknownSlot {
+            context: self.context(
+                tag.span(),
+                slot.location(),
+                slot.location(),
+                slot.name().len(),
+            ),
+            define: define.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    pub(super) fn err_invalid_style(
+        &self,
+        tag: &XmlTag,
+        class: &str,
+        reason: &'static str,
+    ) -> XmlLayoutError {
+        XmlLayoutError::InvalidStyle {
+            context: self.tag_context(tag),
+            class: class.to_string(),
+            reason,
+        }
+    }
+
+    pub(super) fn err_unknown_class(&self, tag: &XmlTag, class: &str) -> XmlLayoutError {
+        let attribute = tag.attribute("class").unwrap();
+        XmlLayoutError::UnknownClass {
+            context: self.context(
+                tag.span(),
+                attribute.location(),
+                attribute.location(),
+                attribute.name().len(),
+            ),
+            class: class.to_string(),
+        }
+    }
+
     pub(super) fn err_duplicate_param(
         &self,
         source: &Value,
@@ -126,41 +304,77 @@ impl<'a> LayoutReader<'a> {
         parameter: &str,
     ) -> XmlLayoutError {
         XmlLayoutError::DuplicateParam {
-            context: Duplicates::new(
+            context: Box::new(Duplicates::new(
                 self.file().to_string(),
                 source.location(),
                 self.substring_other(&source.span()),
                 self.attribute_error_list(source, duplicates),
-            ),
+            )),
             name: parameter.to_string(),
         }
     }
 
+    pub(super) fn err_invalid_filter(&self, value: &Value, error: FilterError) -> XmlLayoutError {
+        let location = value.slice(error.start, error.start).location();
+        XmlLayoutError::InvalidFilter {
+            context: ErrorContext::new(
+                self.file().to_string(),
+                location,
+                ErrorSpan::new(value.value().to_string(), error.start, error.length),
+            ),
+            message: error.message,
+        }
+    }
+
+    pub(super) fn err_invalid_path(&self, value: &Value, error: PathError) -> XmlLayoutError {
+        let location = value.slice(error.start, error.start).location();
+        XmlLayoutError::InvalidPath {
+            context: ErrorContext::new(
+                self.file().to_string(),
+                location,
+                ErrorSpan::new(value.value().to_string(), error.start, error.length),
+            ),
+            message: error.message,
+        }
+    }
+
+    pub(super) fn err_invalid_interpolation(
+        &self,
+        value: &Value,
+        error: InterpolationError,
+    ) -> XmlLayoutError {
+        let location = value.slice(error.start, error.start).location();
+        XmlLayoutError::InvalidInterpolation {
+            context: ErrorContext::new(
+                self.file().to_string(),
+                location,
+                ErrorSpan::new(value.value().to_string(), error.start, error.length),
+            ),
+            message: error.message,
+        }
+    }
+
     fn make_error_span(&self, span: &Span, location: Location, length: usize) -> ErrorSpan {
         let inner = self.substring_other(span);
-        let start = location.column() - (span.start() - location.position());
+        let start = location.offset - (span.start() - location.position());
         ErrorSpan::new(inner, start, length)
     }
 
     fn attribute_error_span(&self, span: &Span, location: Location, length: usize) -> ErrorSpan {
         let inner = self.substring_other(span);
-        let start = (location.column() + 1) - (span.start() - location.position()); //Skip '{'
+        let start = (location.offset + 1) - (span.start() - location.position()); //Skip '{'
         ErrorSpan::new(inner, start, length)
     }
 
     fn attribute_error_list(&self, source: &Value, values: &[Value]) -> Vec<SimpleErrorSpan> {
-        let mut list = vec![];
-        values.iter().for_each(|value| {
-            let offset = value.location().column - source.location().column();
-            let start =
-                offset + value.location.column() - (value.span.start() - value.location.position());
-            list.push(SimpleErrorSpan {
-                start,
+        //Offsets in the source value, so the spans stay valid in multi-line values
+        values
+            .iter()
+            .map(|value| SimpleErrorSpan {
+                start: value.span.start() - source.span.start(),
                 length: value.span.len(),
-            });
-        });
-
-        list
+            })
+            .collect()
     }
 
     fn context(
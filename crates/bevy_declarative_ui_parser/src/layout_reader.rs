@@ -1,38 +1,100 @@
 use crate::XmlLayout;
+use crate::define::Define;
 use crate::errors::XmlLayoutError;
+use crate::lexer::Token;
+use crate::lexer::namespaces::Namespaces;
 use crate::position::{Location, Span};
 use crate::states::{FSMContext, State};
-use std::io::Cursor;
 
 pub struct LayoutReader<'a> {
     pub(crate) file: String,
-    pub(crate) inner: Cursor<&'a str>,
+    /// Source of the layout, tokens borrow their names and values from it
+    pub(crate) source: &'a str,
+    /// Offset of the next byte to read
+    pub(crate) position: usize,
     pub(crate) location: Location,
     pub(crate) start_of_line: usize,
     pub(crate) current_span: Span,
+    pub(crate) namespaces: Namespaces<'a>,
+    /// Keeps the markup which carries nothing for the layout, used by the formatter
+    pub(crate) lossless: bool,
+    /// `<Define>` elements read so far, in the order of the source
+    pub(crate) defines: Vec<Define<'a>>,
+    /// Tokens of an expanded `<Define>` instance, in reverse order, read before the source
+    pub(crate) replay: Vec<Token<'a>>,
+}
+
+/// Result of [`LayoutReader::parse_recovering`]
+#[derive(Default, Debug)]
+pub struct ParsedLayout {
+    /// Everything that could be parsed, invalid attributes and tags are left out
+    pub layout: XmlLayout,
+    /// Errors in the order of the source, a syntax error which stopped the parser is the last one
+    pub errors: Vec<XmlLayoutError>,
+}
+
+impl ParsedLayout {
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// The layout, or the first error
+    pub fn into_result(mut self) -> Result<XmlLayout, XmlLayoutError> {
+        if self.errors.is_empty() {
+            Ok(self.layout)
+        } else {
+            Err(self.errors.remove(0))
+        }
+    }
 }
 
 impl<'a> LayoutReader<'a> {
     pub fn new(content: &'a str, file: &'a str) -> Self {
         Self {
             file: String::from(file),
-            inner: Cursor::new(content),
+            source: content,
+            position: 0,
             location: Location::new(1, 1, 0),
             current_span: Span::new(0, 0),
             start_of_line: 1,
+            namespaces: Namespaces::default(),
+            lossless: false,
+            defines: vec![],
+            replay: vec![],
         }
     }
 
+    /// Stops at the first error
     pub fn parse(&mut self) -> Result<XmlLayout, XmlLayoutError> {
         let mut context = FSMContext::default();
+        self.run(&mut context)?;
+        Ok(context.layout)
+    }
+
+    /// Collects every error it can recover from and keeps parsing,
+    /// so the whole file is checked at once and a partial layout is still available
+    pub fn parse_recovering(&mut self) -> ParsedLayout {
+        let mut context = FSMContext::recovering(true);
+        if let Err(error) = self.run(&mut context) {
+            context.errors.push(error);
+            context.close_containers();
+        }
+
+        ParsedLayout {
+            layout: context.layout,
+            errors: context.errors,
+        }
+    }
+
+    fn run(&mut self, context: &mut FSMContext<'a>) -> Result<(), XmlLayoutError> {
         let mut state = State::Layout;
         while state != State::Break {
-            context.token = self.read()?;
-            let result = state.execute(&mut context, self);
+            context.token = context.next_token(self)?;
+            let result = state.execute(context, self);
             //println!("State: {state}");
             state = result?;
         }
 
-        Ok(context.layout)
+        Ok(())
     }
 }
@@ -1,19 +1,37 @@
+use crate::lexer::namespaces::qualified_name;
 use crate::lexer::value::Value;
 use crate::position::{Location, Span};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Attribute {
+pub struct Attribute<'a> {
     pub(crate) span: Span,
     pub(crate) location: Location,
-    pub(crate) name: Value,
-    pub(crate) value: Value,
+    /// Local name, without the prefix
+    pub(crate) name: Value<'a>,
+    pub(crate) value: Value<'a>,
+    pub(crate) prefix: Option<String>,
+    /// Namespace bound to the prefix, unprefixed attributes have none
+    pub(crate) namespace: Option<String>,
 }
 
-impl Attribute {
+impl Attribute<'_> {
     pub fn name(&self) -> &str {
         &self.name.value()
     }
 
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    /// `prefix:name`, or the local name of an unprefixed attribute
+    pub fn qualified_name(&self) -> String {
+        qualified_name(self.prefix(), self.name())
+    }
+
     pub fn value(&self) -> &str {
         &self.value.value()
     }
@@ -1,6 +1,6 @@
 use crate::LayoutReader;
 use crate::errors::{ErrorContext, XmlLayoutError};
-use crate::position::Location;
+use crate::position::{ErrorSpan, Location};
 
 impl<'a> LayoutReader<'a> {
     pub(super) fn err_unexpected_eof(&self) -> XmlLayoutError {
@@ -10,6 +10,16 @@ impl<'a> LayoutReader<'a> {
         }
     }
 
+    pub(super) fn err_unclosed_comment(&self, location: Location) -> XmlLayoutError {
+        XmlLayoutError::UnclosedComment {
+            context: ErrorContext::new(
+                self.file.clone(),
+                location,
+                ErrorSpan::new("<!--".to_string(), 0, 4),
+            ),
+        }
+    }
+
     pub(super) fn err_unexpected_char(&self, expected: char, found: char) -> XmlLayoutError {
         XmlLayoutError::UnexpectedChar {
             context: ErrorContext::new(self.file.clone(), self.location(), self.error_span(1)),
@@ -38,6 +48,38 @@ impl<'a> LayoutReader<'a> {
         }
     }
 
+    pub(super) fn err_invalid_reference(
+        &self,
+        location: Location,
+        reference: String,
+    ) -> XmlLayoutError {
+        let length = reference.len();
+        XmlLayoutError::InvalidReference {
+            context: ErrorContext::new(
+                self.file.clone(),
+                location,
+                ErrorSpan::new(reference.clone(), 0, length),
+            ),
+            reference,
+        }
+    }
+
+    pub(super) fn err_unbound_prefix(
+        &self,
+        location: Location,
+        name: &str,
+        prefix: &str,
+    ) -> XmlLayoutError {
+        XmlLayoutError::UnboundPrefix {
+            context: ErrorContext::new(
+                self.file.clone(),
+                location,
+                ErrorSpan::new(name.to_string(), 0, prefix.len()),
+            ),
+            prefix: prefix.to_string(),
+        }
+    }
+
     pub(super) fn err_invalid_char(&self, found: char) -> XmlLayoutError {
         XmlLayoutError::InvalidChar {
             context: ErrorContext::new(self.file.clone(), self.location(), self.error_span(1)),
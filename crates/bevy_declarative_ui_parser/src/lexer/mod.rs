@@ -1,5 +1,6 @@
 mod attribute;
 mod error_impls;
+pub(crate) mod namespaces;
 mod tag;
 mod tag_end;
 mod token;
@@ -7,7 +8,7 @@ mod utils;
 mod value;
 
 use crate::position::*;
-use std::io::Read;
+use std::borrow::Cow;
 
 pub use attribute::Attribute;
 pub use tag::Tag;
@@ -17,7 +18,10 @@ pub use value::Value;
 
 use crate::LayoutReader;
 use crate::errors::XmlLayoutError;
-use crate::lexer::utils::{is_name_char, is_name_start_char, is_valid_xml_text_char};
+use crate::lexer::namespaces::split_qualified_name;
+use crate::lexer::utils::{
+    is_char_start, is_name_char, is_name_start_char, is_valid_xml_text_char, is_whitespace,
+};
 
 impl<'a> LayoutReader<'a> {
     pub fn location(&self) -> Location {
@@ -25,6 +29,7 @@ impl<'a> LayoutReader<'a> {
             line_position: self.start_of_line.min(self.current_span.start),
             line: self.location.line,
             column: self.location.column.max(1),
+            offset: self.location.offset.max(1),
         }
     }
 
@@ -33,75 +38,76 @@ impl<'a> LayoutReader<'a> {
     }
 
     fn consume_byte(&mut self, skip_whitespaces: bool) -> Result<u8, XmlLayoutError> {
-        let mut buffer = [0u8; 1];
-
         loop {
-            let result = match self.inner.read(&mut buffer) {
-                Ok(0) => return Err(self.err_unexpected_eof()),
-                _ => Ok(buffer[0]),
+            let Some(byte) = self.peek_byte_safe() else {
+                return Err(self.err_unexpected_eof());
             };
-
-            let byte = buffer[0];
+            self.position += 1;
 
             if byte == b'\n' {
-                self.start_of_line = self.inner.position() as usize - 1;
+                self.start_of_line = self.position - 1;
                 self.location.line += 1;
                 self.location.column = 0; // set to 0 because cursor stayed at \n
+                self.location.offset = 0;
             } else {
-                self.location.column += 1;
+                // Continuation bytes belong to the column of their character
+                if is_char_start(byte) {
+                    self.location.column += 1;
+                }
+                self.location.offset += 1;
             }
 
-            if !skip_whitespaces {
-                return result;
+            if !skip_whitespaces || !is_whitespace(byte) {
+                return Ok(byte);
             }
+        }
+    }
 
-            if byte == b' ' || byte == b'\t' || byte == b'\r' || byte == b'\n' {
-                continue;
+    /// Moves to `end`, the lines and columns of the skipped bytes are counted like
+    /// [`Self::consume_byte`] does
+    fn advance_to(&mut self, end: usize) {
+        let skipped = &self.source.as_bytes()[self.position..end];
+        let columns = |bytes: &[u8]| bytes.iter().filter(|byte| is_char_start(**byte)).count();
+        match skipped.iter().rposition(|byte| *byte == b'\n') {
+            Some(last) => {
+                self.location.line += skipped.iter().filter(|byte| **byte == b'\n').count();
+                self.start_of_line = self.position + last;
+                self.location.column = columns(&skipped[last + 1..]);
+                self.location.offset = skipped.len() - last - 1;
+            }
+            None => {
+                self.location.column += columns(skipped);
+                self.location.offset += skipped.len();
             }
-
-            return result;
         }
+        self.position = end;
     }
 
-    fn peek_byte_safe(&mut self) -> Option<u8> {
-        let mut buffer = [0u8; 1];
-        let current_pos = self.inner.position();
-        let result = match self.inner.read(&mut buffer) {
-            Ok(0) => None,
-            _ => Some(buffer[0]),
-        };
-        self.inner.set_position(current_pos);
-        result
+    fn peek_byte_safe(&self) -> Option<u8> {
+        self.source.as_bytes().get(self.position).copied()
     }
 
-    fn peek_byte(&mut self) -> Result<u8, XmlLayoutError> {
-        let mut buffer = [0u8; 1];
-        let current_pos = self.inner.position();
-        let result = match self.inner.read(&mut buffer) {
-            Ok(0) => return Err(self.err_unexpected_eof()),
-            _ => Ok(buffer[0]),
-        };
-        self.inner.set_position(current_pos);
-        result
+    fn peek_byte(&self) -> Result<u8, XmlLayoutError> {
+        self.peek_byte_safe()
+            .ok_or_else(|| self.err_unexpected_eof())
     }
 
-    fn peek_byte_no_ws(&mut self) -> Result<u8, XmlLayoutError> {
-        let mut buffer = [0u8; 1];
-        let current_pos = self.inner.position();
-        loop {
-            match self.inner.read(&mut buffer) {
-                Ok(0) => return Err(self.err_unexpected_eof()),
-                _ => {}
-            }
-            if buffer[0] == b' ' || buffer[0] == b'\t' || buffer[0] == b'\r' || buffer[0] == b'\n' {
-                continue;
-            }
+    /// Offset of the next byte which is not a whitespace
+    fn skip_whitespaces_offset(&self) -> Result<usize, XmlLayoutError> {
+        self.source.as_bytes()[self.position..]
+            .iter()
+            .position(|byte| !is_whitespace(*byte))
+            .map(|offset| self.position + offset)
+            .ok_or_else(|| self.err_unexpected_eof())
+    }
 
-            break;
-        }
+    fn peek_byte_no_ws(&self) -> Result<u8, XmlLayoutError> {
+        Ok(self.source.as_bytes()[self.skip_whitespaces_offset()?])
+    }
 
-        self.inner.set_position(current_pos);
-        Ok(buffer[0])
+    /// Character which starts at `offset`, the offset is always on a character boundary
+    fn char_at(&self, offset: usize) -> char {
+        self.source[offset..].chars().next().unwrap_or_default()
     }
 
     fn read_tag_span(&mut self) -> Result<bool, XmlLayoutError> {
@@ -109,6 +115,7 @@ impl<'a> LayoutReader<'a> {
         self.consume_byte(true)?; // Skip '<' with whitespaces
 
         let old_location = self.location;
+        let old_start_of_line = self.start_of_line;
         let start = self.cursor_position();
         let mut end = start;
 
@@ -120,22 +127,27 @@ impl<'a> LayoutReader<'a> {
             _ => {}
         }
 
-        let mut inside_quotes = false;
+        // Quote which opened the current attribute value, the other one is a plain character
+        let mut quote = None;
         let mut slash = false;
         loop {
-            self.current_span = Span::new(start as usize - 1, end as usize + 1);
+            self.current_span = Span::new(start - 1, end + 1);
             let byte = self.consume_byte(true)?;
             end = self.cursor_position();
             match byte {
-                b'"' => inside_quotes = !inside_quotes,
+                b'"' | b'\'' => match quote {
+                    None => quote = Some(byte),
+                    Some(open) if open == byte => quote = None,
+                    Some(_) => {}
+                },
                 b'>' => {
-                    if inside_quotes {
+                    if quote.is_some() {
                         continue;
                     }
                     break;
                 }
                 b'/' => {
-                    if inside_quotes {
+                    if quote.is_some() {
                         continue;
                     }
                     if slash {
@@ -146,35 +158,95 @@ impl<'a> LayoutReader<'a> {
                 _ => {}
             }
         }
-        self.inner.set_position(start);
+        self.position = start;
         self.location = old_location;
+        self.start_of_line = old_start_of_line;
         Ok(is_open)
     }
 
-    fn text(&mut self) -> Result<Token, XmlLayoutError> {
+    fn text(&mut self) -> Result<Token<'a>, XmlLayoutError> {
         let start = self.cursor_position();
-        loop {
-            let byte = self.peek_byte_safe();
-            if byte.is_none() {
-                break;
+        // Start tags leave their '>' unconsumed
+        if self.peek_byte_safe() == Some(b'>') {
+            self.consume_byte(false)?;
+        }
+
+        // The text is borrowed from the source until a reference has to be decoded
+        let mut decoded: Option<String> = None;
+        let mut run = self.position;
+        while let Some(byte) = self.peek_byte_safe() {
+            match byte {
+                b'<' => break,
+                b'&' => {
+                    let text = decoded.get_or_insert_with(String::new);
+                    text.push_str(&self.source[run..self.position]);
+                    self.consume_byte(false)?;
+                    text.push(self.read_reference()?);
+                    run = self.position;
+                }
+                b'\n' => {
+                    self.consume_byte(false)?;
+                }
+                _ => {
+                    let c = self.char_at(self.position);
+                    if !is_valid_xml_text_char(c) {
+                        return Err(self.err_invalid_char(c));
+                    }
+                    self.advance_to(self.position + c.len_utf8());
+                }
             }
-            match byte.unwrap() {
-                b'<' | b'&' => {
+        }
+
+        self.current_span.start = start + 1;
+        self.current_span.end = self.cursor_position();
+        let rest = &self.source[run..self.position];
+        Ok(Token::Text(match decoded {
+            Some(mut text) => {
+                text.push_str(rest);
+                Cow::Owned(text)
+            }
+            None => Cow::Borrowed(rest),
+        }))
+    }
+
+    /// Decodes `&name;`, `&#N;` or `&#xN;`, the leading `&` is already consumed
+    fn read_reference(&mut self) -> Result<char, XmlLayoutError> {
+        let location = self.location();
+        let mut reference = String::from("&");
+        loop {
+            match self.peek_byte_safe() {
+                Some(b';') => {
+                    self.consume_byte(false)?;
+                    reference.push(';');
                     break;
                 }
-                b => {
-                    if is_valid_xml_text_char(b as char) {
-                        self.consume_byte(false)?;
-                    } else {
-                        return Err(self.err_invalid_char(b as char));
-                    }
+                Some(b) if b.is_ascii_alphanumeric() || b == b'#' => {
+                    self.consume_byte(false)?;
+                    reference.push(b as char);
                 }
+                _ => return Err(self.err_invalid_reference(location, reference)),
             }
         }
 
-        self.current_span.start = start as usize + 1;
-        self.current_span.end = self.cursor_position() as usize;
-        Ok(Token::Text(self.substring()))
+        let decoded = match &reference[1..reference.len() - 1] {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            name => {
+                let code = match name.strip_prefix("#x") {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => name
+                        .strip_prefix('#')
+                        .and_then(|decimal| decimal.parse().ok()),
+                };
+                code.and_then(char::from_u32)
+                    .filter(|c| is_valid_xml_text_char(*c))
+            }
+        };
+
+        decoded.ok_or_else(|| self.err_invalid_reference(location, reference))
     }
 
     fn peek_comment(&mut self) -> Result<bool, XmlLayoutError> {
@@ -189,7 +261,7 @@ impl<'a> LayoutReader<'a> {
         if self.peek_byte()? == b'!' {
             self.consume_byte(false)?;
         } else {
-            self.inner.set_position(position);
+            self.position = position;
             self.location = location;
             return Ok(false);
         }
@@ -198,7 +270,7 @@ impl<'a> LayoutReader<'a> {
             if self.peek_byte()? == b'-' {
                 self.consume_byte(false)?;
             } else {
-                self.inner.set_position(position);
+                self.position = position;
                 self.location = location;
                 return Ok(false);
             }
@@ -207,35 +279,78 @@ impl<'a> LayoutReader<'a> {
         Ok(true)
     }
 
-    fn read_comment(&mut self) -> Result<Token, XmlLayoutError> {
-        self.consume_byte(true)?; // Skip '<' with whitespaces
-        let mut buffer = [0u8; 2];
-        let mut first = true;
-        loop {
-            let byte = self.peek_byte().unwrap();
-            if byte == b'>' {
-                if buffer[0] == b'-' && buffer[1] == b'-' {
-                    self.consume_byte(false)?;
-                    return Ok(Token::Comment);
-                }
-            }
-            if first {
-                buffer[0] = byte;
-                first = false;
-            } else {
-                buffer[1] = byte;
-                first = true;
-            }
+    fn read_comment(&mut self) -> Result<Token<'a>, XmlLayoutError> {
+        // `peek_comment` stopped on the last '-' of "<!--"
+        let mut location = self.location();
+        location.shift(-3);
 
+        match self.read_until("-->") {
+            Ok(content) => Ok(Token::Comment(content)),
+            Err(XmlLayoutError::EndOfFile { .. }) => Err(self.err_unclosed_comment(location)),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Consumes `prefix` after optional whitespaces, restores the position if it does not match
+    fn peek_markup(&mut self, prefix: &[u8]) -> Result<bool, XmlLayoutError> {
+        let location = self.location;
+        let position = self.cursor_position();
+        if self.peek_byte_no_ws()? != prefix[0] {
+            return Ok(false);
+        }
+        self.consume_byte(true)?;
+
+        for expected in &prefix[1..] {
+            if self.peek_byte_safe() != Some(*expected) {
+                self.position = position;
+                self.location = location;
+                return Ok(false);
+            }
             self.consume_byte(false)?;
         }
+
+        Ok(true)
+    }
+
+    /// Source up to `terminator`, which is consumed but not included
+    fn read_until(&mut self, terminator: &str) -> Result<&'a str, XmlLayoutError> {
+        let Some(length) = self.source[self.position..].find(terminator) else {
+            self.advance_to(self.source.len());
+            return Err(self.err_unexpected_eof());
+        };
+        let content = &self.source[self.position..self.position + length];
+        self.advance_to(self.position + length + terminator.len());
+        Ok(content)
+    }
+
+    /// `<?xml version="1.0"?>` and other processing instructions carry nothing for the layout,
+    /// only the lossless reader returns them
+    fn read_processing_instruction(&mut self) -> Result<Token<'a>, XmlLayoutError> {
+        let content = self.read_until("?>")?;
+        if self.lossless {
+            Ok(Token::Instruction(content))
+        } else {
+            self.read()
+        }
+    }
+
+    /// Content of `<![CDATA[...]]>` is delivered as is, without decoding references
+    fn read_cdata(&mut self) -> Result<Token<'a>, XmlLayoutError> {
+        let start = self.cursor_position();
+        let content = self.read_until("]]>")?;
+        self.current_span = Span::new(start, start + content.len());
+        Ok(Token::Text(Cow::Borrowed(content)))
     }
 
-    pub(crate) fn read(&mut self) -> Result<Token, XmlLayoutError> {
+    pub(crate) fn read(&mut self) -> Result<Token<'a>, XmlLayoutError> {
         match self.peek_byte_no_ws() {
             Ok(b'<') => {
                 if self.peek_comment()? {
                     self.read_comment()
+                } else if self.peek_markup(b"<?")? {
+                    self.read_processing_instruction()
+                } else if self.peek_markup(b"<![CDATA[")? {
+                    self.read_cdata()
                 } else {
                     if self.read_tag_span()? {
                         self.read_tag()
@@ -248,53 +363,62 @@ impl<'a> LayoutReader<'a> {
                 XmlLayoutError::EndOfFile { .. } => Ok(Token::EOF),
                 _ => Err(e),
             },
-            Ok(other) => {
-                let byte = self.peek_byte_no_ws().unwrap();
-                match byte {
-                    b'<' | b'&' => {
-                        todo!("not impl: {}", other as char)
-                    }
-                    b => {
-                        if is_valid_xml_text_char(b as char) {
-                            self.text()
-                        } else {
-                            Err(self.err_invalid_char(b as char))
-                        }
-                    }
+            Ok(b'&') => self.text(),
+            Ok(_) => {
+                let c = self.char_at(self.skip_whitespaces_offset()?);
+                if is_valid_xml_text_char(c) {
+                    self.text()
+                } else {
+                    Err(self.err_invalid_char(c))
                 }
             }
         }
     }
 
-    fn cursor_position(&self) -> u64 {
-        self.inner.position()
+    const fn cursor_position(&self) -> usize {
+        self.position
     }
 
     pub fn substring(&self) -> String {
-        self.inner.get_ref()[self.current_span.start..self.current_span.end].to_string()
+        self.substring_other(&self.current_span)
     }
 
+    /// Spans of errors may not fall on character boundaries, their bytes are decoded lossily
     pub fn substring_other(&self, span: &Span) -> String {
-        self.inner.get_ref()[span.start..span.end].to_string()
+        let bytes = self.source.as_bytes();
+        let end = span.end.min(bytes.len());
+        String::from_utf8_lossy(&bytes[span.start.min(end)..end]).into_owned()
     }
 
-    fn read_tag_end(&mut self) -> Result<Token, XmlLayoutError> {
+    fn read_tag_end(&mut self) -> Result<Token<'a>, XmlLayoutError> {
         self.consume_byte(true)?; // Skip '/' with whitespaces
+        let mut location = self.location();
+        location.shift(1);
 
         let span = self.current_span;
         self.current_span = self.read_tag_identifier(false)?;
-        Ok(Token::TagEnd(TagEnd {
+
+        let name = &self.source[self.current_span.start..self.current_span.end];
+        let (prefix, identifier) = split_qualified_name(name);
+        let tag = TagEnd {
             span,
-            identifier: self.substring(),
-        }))
+            location,
+            identifier,
+            prefix,
+        };
+        match self.namespaces.pop() {
+            Some(open) if open != name => Err(self.err_mismatched_end_tag(&tag, open)),
+            _ => Ok(Token::TagEnd(tag)),
+        }
     }
 
-    fn read_tag(&mut self) -> Result<Token, XmlLayoutError> {
+    fn read_tag(&mut self) -> Result<Token<'a>, XmlLayoutError> {
         self.skip_whitespaces()?;
         let mut location = self.location();
-        location.column += 1;
+        location.shift(1);
 
         let identifier = self.read_tag_identifier(true)?;
+        let identifier = &self.source[identifier.start..identifier.end];
         let attributes = self.read_attributes()?;
 
         let mut byte = self.peek_byte_no_ws()?;
@@ -303,60 +427,69 @@ impl<'a> LayoutReader<'a> {
             byte = self.peek_byte_no_ws()?;
             if byte == b'>' {
                 self.consume_byte(true)?;
-                Ok(Token::TagEmpty(Tag {
+                let mut tag = Tag {
                     span: self.current_span,
                     location,
-                    identifier: self.substring_other(&identifier),
+                    identifier,
+                    prefix: None,
+                    namespace: None,
                     attributes,
-                }))
+                };
+                self.resolve_namespaces(&mut tag, true)?;
+                Ok(Token::TagEmpty(tag))
             } else {
                 Err(self.err_unexpected_char_with_loc(location, '>', byte as char))
             }
         } else if byte == b'>' {
-            Ok(Token::TagStart(Tag {
+            let mut tag = Tag {
                 span: self.current_span,
                 location,
-                identifier: self.substring_other(&identifier),
+                identifier,
+                prefix: None,
+                namespace: None,
                 attributes,
-            }))
+            };
+            self.resolve_namespaces(&mut tag, false)?;
+            Ok(Token::TagStart(tag))
         } else {
             Err(self.err_unexpected_char_with_loc(location, '>', byte as char))
         }
     }
 
-    fn read_tag_identifier(&mut self, is_start: bool) -> Result<Span, XmlLayoutError> {
-        self.read_identifier_first_char()?;
-        let start = self.cursor_position() as usize - 1;
-        let mut end = start + 1;
-
-        loop {
-            match self.consume_byte(false)? {
-                b'_' => end += 1,
-                b'-' => end += 1,
-                b'.' => end += 1,
-                b if b.is_ascii_alphabetic() => end += 1,
-                b if b.is_ascii_digit() => end += 1,
-                b if b.is_ascii_whitespace() => return Ok(Span { start, end }),
-                b'>' => {
-                    self.inner.set_position(end as u64);
-                    return Ok(Span { start, end });
-                }
-                b'/' if is_start => {
-                    self.inner.set_position(end as u64);
-                    return Ok(Span { start, end });
-                }
+    /// Length in bytes of the name characters which follow the position
+    fn name_length(&self) -> usize {
+        let rest = &self.source[self.position..];
+        rest.char_indices()
+            .find(|(_, c)| !is_name_char(*c))
+            .map_or(rest.len(), |(offset, _)| offset)
+    }
 
-                other => return Err(self.err_expected_identifier(other as char)),
+    fn read_tag_identifier(&mut self, is_start: bool) -> Result<Span, XmlLayoutError> {
+        let first = self.read_identifier_first_char()?;
+        let start = self.cursor_position() - first.len_utf8();
+        let end = self.cursor_position() + self.name_length();
+        self.advance_to(end);
+
+        match self.consume_byte(false)? {
+            b if b.is_ascii_whitespace() => Ok(Span { start, end }),
+            b'>' => {
+                self.position = end;
+                Ok(Span { start, end })
             }
+            b'/' if is_start => {
+                self.position = end;
+                Ok(Span { start, end })
+            }
+            _ => Err(self.err_expected_identifier(self.char_at(end))),
         }
     }
 
-    fn read_attributes(&mut self) -> Result<Vec<Attribute>, XmlLayoutError> {
+    fn read_attributes(&mut self) -> Result<Vec<Attribute<'a>>, XmlLayoutError> {
         let mut attributes = Vec::new();
         while !self.peek_end_of_tag()? {
             self.skip_whitespaces()?;
             let mut attribute_location = self.location();
-            attribute_location.column += 1;
+            attribute_location.shift(1);
             let attribute_name = self.read_attribute_identifier()?;
             self.consume_equal_char()?;
             let attribute_value = self.read_attribute_value()?;
@@ -365,48 +498,48 @@ impl<'a> LayoutReader<'a> {
                 location: attribute_location,
                 name: attribute_name,
                 value: attribute_value,
+                prefix: None,
+                namespace: None,
             })
         }
         Ok(attributes)
     }
 
+    /// Names may use any alphabetic character, the whole character is consumed
     fn read_identifier_first_char(&mut self) -> Result<char, XmlLayoutError> {
-        let first = self.consume_byte(true)?;
+        self.consume_byte(true)?;
+        let first = self.char_at(self.position - 1);
+        self.advance_to(self.position - 1 + first.len_utf8());
         if !is_name_start_char(first) {
-            return Err(self.err_expected_identifier(first as char));
+            return Err(self.err_expected_identifier(first));
         }
 
-        Ok(first as char)
+        Ok(first)
     }
 
-    fn read_attribute_identifier(&mut self) -> Result<Value, XmlLayoutError> {
-        let mut identifier = String::new();
+    fn read_attribute_identifier(&mut self) -> Result<Value<'a>, XmlLayoutError> {
         let first = self.read_identifier_first_char()?;
-        identifier.push(first);
+        let begin = self.cursor_position() - first.len_utf8();
 
         let location = self.location();
-        let start = location.column + location.position();
+        let start = location.offset + location.position();
 
-        loop {
-            let byte = self.peek_byte()?;
-            if is_name_char(byte) {
-                self.consume_byte(false)?;
-                identifier.push(byte as char);
-            } else {
-                break;
-            }
-        }
+        let end = self.cursor_position() + self.name_length();
+        self.advance_to(end);
+        self.peek_byte()?;
 
+        let identifier = &self.source[begin..end];
         Ok(Value {
             location,
             span: Span::new(start, start + identifier.len()),
-            inner: identifier,
+            inner: Cow::Borrowed(identifier),
+            references: Vec::new(),
         })
     }
 
     fn error_span(&self, length: usize) -> ErrorSpan {
         let inner = self.substring();
-        let start = self.location.column
+        let start = self.location.offset
             - (self.current_span.start - self.start_of_line.min(self.current_span.start));
         let source = inner.trim().to_string();
         ErrorSpan {
@@ -416,17 +549,21 @@ impl<'a> LayoutReader<'a> {
         }
     }
 
-    fn read_attribute_value(&mut self) -> Result<Value, XmlLayoutError> {
+    fn read_attribute_value(&mut self) -> Result<Value<'a>, XmlLayoutError> {
         let quote = self.consume_byte(true)?;
         if quote != b'"' && quote != b'\'' {
             return Err(self.err_unexpected_char('"', quote as char));
         }
 
         let mut location = self.location();
-        location.column += 1;
-        let start = location.column + location.position();
+        location.shift(1);
+        let start = location.offset + location.position();
+        let begin = self.cursor_position();
 
-        let mut value = String::new();
+        // The value is borrowed from the source until a reference has to be decoded
+        let mut decoded: Option<String> = None;
+        let mut run = begin;
+        let mut references = Vec::new();
 
         loop {
             let byte = self.consume_byte(false)?;
@@ -434,13 +571,32 @@ impl<'a> LayoutReader<'a> {
                 break;
             }
 
-            value.push(byte as char);
+            if byte == b'&' {
+                let reference_start = self.cursor_position() - 1;
+                let value = decoded.get_or_insert_with(String::new);
+                value.push_str(&self.source[run..reference_start]);
+                let c = self.read_reference()?;
+                value.push(c);
+                let raw = self.cursor_position() - reference_start;
+                references.push((value.len(), raw - c.len_utf8()));
+                run = self.cursor_position();
+            }
         }
 
+        // The span covers the raw value, references included
+        let end = self.cursor_position() - 1;
+        let rest = &self.source[run..end];
         Ok(Value {
             location,
-            span: Span::new(start, start + value.len()),
-            inner: value,
+            span: Span::new(start, start + end - begin),
+            inner: match decoded {
+                Some(mut value) => {
+                    value.push_str(rest);
+                    Cow::Owned(value)
+                }
+                None => Cow::Borrowed(rest),
+            },
+            references,
         })
     }
 
@@ -466,3 +622,42 @@ impl<'a> LayoutReader<'a> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::LayoutReader;
+    use crate::lexer::Token;
+    use std::borrow::Cow;
+
+    #[test]
+    fn borrowed_unless_decoded() {
+        let mut reader = LayoutReader::new(
+            r#"<Text text="plain" other="a &amp; b">x &lt; y</Text><Use>z</Use>"#,
+            "layout.xml",
+        );
+        let mut tokens = vec![];
+        loop {
+            match reader.read().unwrap() {
+                Token::EOF => break,
+                token => tokens.push(token),
+            }
+        }
+
+        let Token::TagStart(tag) = &tokens[0] else {
+            panic!("Expected a start tag, found {}", tokens[0]);
+        };
+        assert_eq!(tag.attributes[0].value.inner, Cow::Borrowed("plain"));
+        assert!(matches!(tag.attributes[1].value.inner, Cow::Owned(_)));
+        assert_eq!(tag.attributes[1].value.value(), "a & b");
+
+        let texts = tokens
+            .iter()
+            .filter_map(|token| match token {
+                Token::Text(text) if !text.is_empty() => Some(text),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert!(matches!(texts[0], Cow::Owned(text) if text == "x < y"));
+        assert!(matches!(texts[1], Cow::Borrowed("z")));
+    }
+}
@@ -0,0 +1,137 @@
+use crate::LayoutReader;
+use crate::errors::XmlLayoutError;
+use crate::lexer::{Attribute, Tag};
+
+/// Namespace of `xsi:schemaLocation` and other attributes meant for editors and validators
+pub(crate) const XML_SCHEMA_INSTANCE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+/// Namespace bound to the reserved `xml` prefix
+pub(crate) const XML: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// Open element with the prefixes it declares
+struct Scope<'a> {
+    /// Qualified name of the element, its end tag must repeat it
+    name: &'a str,
+    bindings: Vec<(Option<String>, String)>,
+}
+
+/// Prefixes declared with `xmlns` and `xmlns:prefix`, one scope per open element
+#[derive(Default)]
+pub(crate) struct Namespaces<'a> {
+    scopes: Vec<Scope<'a>>,
+}
+
+impl<'a> Namespaces<'a> {
+    fn push(&mut self, name: &'a str, bindings: Vec<(Option<String>, String)>) {
+        self.scopes.push(Scope { name, bindings });
+    }
+
+    /// Closes the innermost element and returns its qualified name
+    pub(crate) fn pop(&mut self) -> Option<&'a str> {
+        self.scopes.pop().map(|scope| scope.name)
+    }
+
+    /// Innermost declaration wins, an unprefixed name without `xmlns` has no namespace
+    fn resolve(&self, prefix: Option<&str>) -> Option<&str> {
+        if prefix == Some("xml") {
+            return Some(XML);
+        }
+
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| &scope.bindings)
+            .find(|(declared, _)| declared.as_deref() == prefix)
+            .map(|(_, namespace)| namespace.as_str())
+            .filter(|namespace| !namespace.is_empty())
+    }
+}
+
+/// `prefix:name` -> `(Some(prefix), name)`
+pub(crate) fn split_qualified_name(name: &str) -> (Option<&str>, &str) {
+    match name.split_once(':') {
+        Some((prefix, local)) => (Some(prefix), local),
+        None => (None, name),
+    }
+}
+
+/// Inverse of [`split_qualified_name`]
+pub(crate) fn qualified_name(prefix: Option<&str>, local: &str) -> String {
+    match prefix {
+        Some(prefix) => format!("{prefix}:{local}"),
+        None => local.to_string(),
+    }
+}
+
+impl<'a> LayoutReader<'a> {
+    /// Opens the scope of the tag and resolves its prefix,
+    /// namespace declarations and schema-instance attributes are removed from the tag.
+    /// The lossless reader only opens the scope, the tag is kept as written
+    pub(super) fn resolve_namespaces(
+        &mut self,
+        tag: &mut Tag<'a>,
+        is_empty: bool,
+    ) -> Result<(), XmlLayoutError> {
+        if self.lossless {
+            if !is_empty {
+                self.namespaces.push(tag.identifier, vec![]);
+            }
+            return Ok(());
+        }
+
+        let mut bindings = vec![];
+        tag.attributes.retain(|attribute| {
+            let binding = match split_qualified_name(attribute.name()) {
+                (None, "xmlns") => None,
+                (Some("xmlns"), prefix) => Some(prefix.to_string()),
+                _ => return true,
+            };
+            bindings.push((binding, attribute.value().to_string()));
+            false
+        });
+        self.namespaces.push(tag.identifier, bindings);
+
+        let result = self.bind_tag(tag);
+        if is_empty {
+            self.namespaces.pop();
+        }
+        result
+    }
+
+    fn bind_tag(&self, tag: &mut Tag<'a>) -> Result<(), XmlLayoutError> {
+        let (prefix, local) = split_qualified_name(tag.identifier);
+        let namespace = self.namespaces.resolve(prefix).map(str::to_string);
+        if let Some(prefix) = prefix
+            && namespace.is_none()
+        {
+            return Err(self.err_unbound_prefix(tag.location, tag.identifier, prefix));
+        }
+
+        tag.prefix = prefix;
+        tag.identifier = local;
+        tag.namespace = namespace;
+
+        tag.attributes
+            .iter_mut()
+            .try_for_each(|attribute| self.bind_attribute(attribute))?;
+        tag.attributes
+            .retain(|attribute| attribute.namespace() != Some(XML_SCHEMA_INSTANCE));
+        Ok(())
+    }
+
+    /// Unprefixed attributes have no namespace, they belong to the tag
+    fn bind_attribute(&self, attribute: &mut Attribute) -> Result<(), XmlLayoutError> {
+        let name = attribute.name().to_string();
+        let (Some(prefix), local) = split_qualified_name(&name) else {
+            return Ok(());
+        };
+        let Some(namespace) = self.namespaces.resolve(Some(prefix)) else {
+            return Err(self.err_unbound_prefix(attribute.location, &name, prefix));
+        };
+
+        attribute.namespace = Some(namespace.to_string());
+        attribute.prefix = Some(prefix.to_string());
+        attribute.name = attribute.name.slice(name.len() - local.len(), name.len());
+        Ok(())
+    }
+}
@@ -1,20 +1,46 @@
 use crate::lexer::Attribute;
+use crate::lexer::namespaces::qualified_name;
 use crate::position::{Location, Span};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Tag {
+pub struct Tag<'a> {
     pub span: Span,
     pub location: Location,
-    pub identifier: String,
-    pub attributes: Vec<Attribute>,
+    /// Local name, without the prefix
+    pub identifier: &'a str,
+    pub prefix: Option<&'a str>,
+    /// Namespace bound to the prefix, or the default namespace for an unprefixed tag
+    pub namespace: Option<String>,
+    pub attributes: Vec<Attribute<'a>>,
 }
 
-impl Tag {
-    pub fn identifier(&self) -> &str {
-        &self.identifier
+impl<'a> Tag<'a> {
+    pub const fn identifier(&self) -> &'a str {
+        self.identifier
+    }
+
+    pub const fn prefix(&self) -> Option<&'a str> {
+        self.prefix
+    }
+
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
     }
 
-    pub const fn attributes(&self) -> &Vec<Attribute> {
+    /// `prefix:name`, structural tags are matched on it so `<game:Container/>` is a component
+    pub fn qualified_name(&self) -> String {
+        qualified_name(self.prefix(), self.identifier())
+    }
+
+    /// Attribute addressed to the tag, either unprefixed or in the namespace of the tag
+    pub fn attribute(&self, name: &str) -> Option<&Attribute<'a>> {
+        self.attributes.iter().find(|attribute| {
+            attribute.name() == name
+                && (attribute.namespace.is_none() || attribute.namespace == self.namespace)
+        })
+    }
+
+    pub const fn attributes(&self) -> &Vec<Attribute<'a>> {
         &self.attributes
     }
 
@@ -27,14 +53,14 @@ impl Tag {
     }
 
     pub fn into_identifier(self) -> String {
-        self.identifier
+        self.identifier.to_string()
     }
 
-    pub fn into_attributes(self) -> Vec<Attribute> {
+    pub fn into_attributes(self) -> Vec<Attribute<'a>> {
         self.attributes
     }
 
-    pub fn into_inner(self) -> (String, Vec<Attribute>) {
-        (self.identifier, self.attributes)
+    pub fn into_inner(self) -> (String, Vec<Attribute<'a>>) {
+        (self.identifier.to_string(), self.attributes)
     }
 }
@@ -1,14 +1,31 @@
-use crate::position::Span;
+use crate::lexer::namespaces::qualified_name;
+use crate::position::{Location, Span};
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct TagEnd {
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TagEnd<'a> {
     pub span: Span,
-    pub identifier: String,
+    pub location: Location,
+    /// Local name, without the prefix
+    pub identifier: &'a str,
+    pub prefix: Option<&'a str>,
 }
 
-impl TagEnd {
-    pub fn identifier(&self) -> &str {
-        &self.identifier
+impl<'a> TagEnd<'a> {
+    pub const fn identifier(&self) -> &'a str {
+        self.identifier
+    }
+
+    pub const fn prefix(&self) -> Option<&'a str> {
+        self.prefix
+    }
+
+    /// `prefix:name`, it must match the qualified name of the start tag
+    pub fn qualified_name(&self) -> String {
+        qualified_name(self.prefix(), self.identifier())
+    }
+
+    pub const fn location(&self) -> Location {
+        self.location
     }
 
     pub const fn span(&self) -> Span {
@@ -1,26 +1,33 @@
 use crate::lexer::Tag;
 use crate::lexer::tag_end::TagEnd;
+use std::borrow::Cow;
 use std::fmt;
 
-#[derive(Default, Debug, PartialEq, Eq)]
-pub enum Token {
-    TagStart(Tag),
-    TagEmpty(Tag),
-    TagEnd(TagEnd),
-    Text(String),
-    Comment,
+/// Names and values are borrowed from the source of the reader
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub enum Token<'a> {
+    TagStart(Tag<'a>),
+    TagEmpty(Tag<'a>),
+    TagEnd(TagEnd<'a>),
+    /// Owned only if references were decoded
+    Text(Cow<'a, str>),
+    /// Content between `<!--` and `-->`
+    Comment(&'a str),
+    /// Content between `<?` and `?>`, only read by the lossless reader of the formatter
+    Instruction(&'a str),
     #[default]
     EOF,
 }
 
-impl fmt::Display for Token {
+impl fmt::Display for Token<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Token::TagStart(_) => write!(f, "TagStart"),
             Token::TagEnd(_) => write!(f, "TagEnd"),
             Token::TagEmpty(_) => write!(f, "TagEmpty"),
             Token::Text(_) => write!(f, "Text"),
-            Token::Comment => write!(f, "Comment"),
+            Token::Comment(_) => write!(f, "Comment"),
+            Token::Instruction(_) => write!(f, "Instruction"),
             Token::EOF => write!(f, "EOF"),
         }
     }
@@ -1,9 +1,18 @@
-pub(super) fn is_name_start_char(byte: u8) -> bool {
-    byte.is_ascii_alphabetic() || byte == b'_' || byte == b':'
+pub(super) fn is_name_start_char(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == ':'
 }
 
-pub(super) fn is_name_char(byte: u8) -> bool {
-    byte.is_ascii_alphanumeric() || matches!(byte, b'.' | b'-' | b'_' | b':')
+pub(super) fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '.' | '-' | '_' | ':')
+}
+
+pub(super) const fn is_whitespace(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b'\r' | b'\n')
+}
+
+/// First byte of a UTF-8 character, the other ones are `10xxxxxx`
+pub(super) const fn is_char_start(byte: u8) -> bool {
+    byte & 0xC0 != 0x80
 }
 
 pub(super) fn is_valid_xml_text_char(c: char) -> bool {
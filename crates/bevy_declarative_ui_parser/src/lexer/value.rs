@@ -1,18 +1,24 @@
 use crate::position::{Location, Span};
+use std::borrow::Cow;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Value {
+pub struct Value<'a> {
     pub(crate) span: Span,
     pub(crate) location: Location,
-    pub(crate) inner: String,
+    /// Borrowed from the source, owned only if references were decoded
+    pub(crate) inner: Cow<'a, str>,
+    /// Decoded offsets which follow a character reference, with the bytes the reference
+    /// takes in the source beyond its decoded character
+    pub(crate) references: Vec<(usize, usize)>,
 }
 
-impl Value {
-    pub fn new(span: Span, location: Location, inner: impl Into<String>) -> Self {
+impl<'a> Value<'a> {
+    pub fn new(span: Span, location: Location, inner: impl Into<Cow<'a, str>>) -> Self {
         Self {
             span,
             location,
             inner: inner.into(),
+            references: Vec::new(),
         }
     }
     pub fn value(&self) -> &str {
@@ -28,6 +34,61 @@ impl Value {
     }
 
     pub fn into_inner(self) -> String {
-        self.inner
+        self.inner.into_owned()
+    }
+
+    /// Offset in the source of the given offset in the decoded value
+    pub fn raw_offset(&self, offset: usize) -> usize {
+        offset
+            + self
+                .references
+                .iter()
+                .filter(|(at, _)| *at <= offset)
+                .map(|(_, extra)| extra)
+                .sum::<usize>()
+    }
+
+    /// Characters in the source before the given offset in the decoded value, a reference
+    /// counts with every character it takes in the source
+    fn raw_columns(&self, offset: usize) -> usize {
+        let references = self
+            .references
+            .iter()
+            .filter(|(at, _)| *at <= offset)
+            .map(|(at, extra)| {
+                //References are ASCII, their decoded character is one column
+                let decoded = self.inner[..*at]
+                    .chars()
+                    .next_back()
+                    .map_or(0, char::len_utf8);
+                extra + decoded - 1
+            })
+            .sum::<usize>();
+        self.inner[..offset].chars().count() + references
+    }
+
+    /// Part of the decoded value, the span and the location point to its source
+    pub(crate) fn slice(&self, start: usize, end: usize) -> Value<'a> {
+        let raw_start = self.raw_offset(start);
+        let mut location = self.location;
+        location.column += self.raw_columns(start);
+        location.offset += raw_start;
+        Value {
+            span: Span::new(
+                self.span.start + raw_start,
+                self.span.start + self.raw_offset(end),
+            ),
+            location,
+            inner: match &self.inner {
+                Cow::Borrowed(inner) => Cow::Borrowed(&inner[start..end]),
+                Cow::Owned(inner) => Cow::Owned(inner[start..end].to_string()),
+            },
+            references: self
+                .references
+                .iter()
+                .filter(|(at, _)| *at > start && *at <= end)
+                .map(|(at, extra)| (at - start, *extra))
+                .collect(),
+        }
     }
 }
@@ -1,29 +1,48 @@
 pub mod attribute;
+mod condition;
+mod define;
 pub mod errors;
+mod include;
 pub mod into;
 mod layout_errors_impls;
 mod layout_reader;
 mod lexer;
 pub mod position;
+mod property;
 mod states;
+mod style;
 mod template;
 pub mod utils;
 pub mod values;
+mod writer;
 
-pub use layout_reader::LayoutReader;
+pub use condition::{Condition, ConditionTest, condition_attribute};
+pub use include::{Include, IncludedFiles, include_path};
+pub use layout_reader::{LayoutReader, ParsedLayout};
+pub use property::LayoutProperty;
+pub use style::Style;
 pub use template::ItemTemplate;
+pub use writer::LayoutWriter;
 
 pub use crate::errors::XmlLayoutError;
 use crate::into::Tag;
+#[cfg(feature = "node_span")]
+use crate::position::NodeSpan;
 use std::collections::HashSet;
 
-type XmlTag = lexer::Tag;
+type XmlTag<'a> = lexer::Tag<'a>;
 
-#[derive(Default, Debug)]
+#[derive(Clone, Default, Debug)]
 pub struct XmlLayout {
     pub templates: Vec<ItemTemplate>,
     pub root_nodes: Vec<UiNode>,
     pub usings: HashSet<String>,
+    /// Declared in `<GlobalResources>` and `<LocalResources>`
+    pub properties: Vec<LayoutProperty>,
+    /// Declared in `<Styles>`, applied with the `class` attribute of the containers
+    pub styles: Vec<Style>,
+    /// `<Include>` elements, empty once they are resolved by [`IncludedFiles::resolve`]
+    pub includes: Vec<Include>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -61,15 +80,24 @@ pub struct UiNode {
     pub id: Id,
     pub components: Vec<Tag>,
     pub children: Vec<UiNode>,
+    /// Set on `<If>`, `<Else>` and `<Case>` containers
+    pub condition: Option<Condition>,
+    /// Whole element, from the start tag to the end tag
+    #[cfg(feature = "node_span")]
+    pub span: NodeSpan,
 }
 
 impl UiNode {
     pub fn new(reader: &LayoutReader, tag: XmlTag) -> Result<UiNode, XmlLayoutError> {
+        let tag = Tag::from(reader, tag)?;
         Ok(UiNode {
-            tag: Tag::from(reader, tag)?,
+            #[cfg(feature = "node_span")]
+            span: tag.span,
+            tag,
             id: Id::Default(0),
             components: vec![],
             children: vec![],
+            condition: None,
         })
     }
 }
@@ -1,4 +1,4 @@
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct ErrorSpan {
     pub(crate) source: String,
     pub(crate) start: usize,
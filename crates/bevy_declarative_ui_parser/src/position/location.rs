@@ -4,7 +4,10 @@ use std::fmt::Display;
 pub struct Location {
     pub(crate) line_position: usize,
     pub(crate) line: usize,
+    /// Characters before the location in its line
     pub(crate) column: usize,
+    /// Bytes before the location in its line, the spans are computed from it
+    pub(crate) offset: usize,
 }
 
 impl Display for Location {
@@ -19,6 +22,7 @@ impl Location {
             line_position: position,
             line,
             column,
+            offset: column,
         }
     }
 
@@ -33,4 +37,10 @@ impl Location {
     pub const fn column(&self) -> usize {
         self.column
     }
+
+    /// Moves the location over ASCII characters, which take one byte each
+    pub(crate) const fn shift(&mut self, columns: isize) {
+        self.column = self.column.saturating_add_signed(columns);
+        self.offset = self.offset.saturating_add_signed(columns);
+    }
 }
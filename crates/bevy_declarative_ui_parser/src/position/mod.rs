@@ -1,9 +1,11 @@
 mod error_span;
 mod location;
+mod node_span;
 mod simple_error_span;
 mod span;
 
 pub use error_span::ErrorSpan;
 pub use location::Location;
+pub use node_span::NodeSpan;
 pub use simple_error_span::SimpleErrorSpan;
 pub use span::Span;
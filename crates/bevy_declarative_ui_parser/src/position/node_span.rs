@@ -0,0 +1,23 @@
+use crate::position::{Location, Span};
+
+/// Source position of a node, tag or attribute of the parsed tree, kept with the `node_span`
+/// feature so errors found after parsing can point at the XML
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct NodeSpan {
+    pub span: Span,
+    pub location: Location,
+}
+
+impl NodeSpan {
+    pub const fn new(span: Span, location: Location) -> Self {
+        Self { span, location }
+    }
+
+    pub const fn span(&self) -> Span {
+        self.span
+    }
+
+    pub const fn location(&self) -> Location {
+        self.location
+    }
+}
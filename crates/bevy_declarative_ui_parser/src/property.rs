@@ -0,0 +1,30 @@
+use crate::{XmlTag, errors::XmlLayoutError, layout_reader::LayoutReader};
+
+/// Value declared with `<Property name=".." type=".." value=".."/>`, read by `{Name}` bindings
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutProperty {
+    pub name: String,
+    /// Name of the type registered in the runtime library
+    pub r#type: String,
+    pub value: String,
+    /// Declared in `<GlobalResources>`, shared by every document of the layout
+    pub global: bool,
+}
+
+impl LayoutProperty {
+    pub fn new(reader: &LayoutReader, tag: &XmlTag, global: bool) -> Result<Self, XmlLayoutError> {
+        let name = reader.parse_required_attribute(tag, "name")?;
+        if name.value().is_empty() {
+            return Err(reader.err_empty_attribute(tag, "name"));
+        }
+        let r#type = reader.parse_required_attribute(tag, "type")?;
+        let value = reader.parse_required_attribute(tag, "value")?;
+
+        Ok(Self {
+            name: name.value().to_string(),
+            r#type: r#type.value().to_string(),
+            value: value.value().to_string(),
+            global,
+        })
+    }
+}
@@ -2,18 +2,51 @@ use crate::lexer::Token;
 use crate::states::{FSMContext, State};
 use crate::{LayoutReader, XmlLayoutError};
 
-pub(super) fn content_execute(
-    context: &mut FSMContext,
-    reader: &LayoutReader,
+pub(super) fn content_execute<'a>(
+    context: &mut FSMContext<'a>,
+    reader: &mut LayoutReader<'a>,
 ) -> Result<State, XmlLayoutError> {
     match &context.token {
-        Token::TagStart(tag) => match tag.identifier() {
+        Token::TagStart(tag) => match tag.qualified_name().as_str() {
             "Use" => Ok(State::Use),
+            "GlobalResources" => Ok(State::GlobalResources),
+            "LocalResources" => Ok(State::LocalResources),
+            "Styles" => Ok(State::Styles),
+            "Define" => context.create_define(reader, tag.clone(), false),
             "ItemTemplate" => context.create_template(reader, tag.clone()),
             "Container" => context.create_nested_container(reader, tag.clone()),
-            other => panic!("TODO: Unknown tag: {other}"),
+            "If" | "Else" | "Switch" | "Case" => {
+                context.create_condition(reader, tag.clone(), false)
+            }
+            "Slot" | "Fill" => context.misplaced_slot(reader, tag.clone(), false, State::Content),
+            _ => {
+                context.report(reader.err_unexpected_tag(
+                    tag.clone(),
+                    vec![
+                        "Use",
+                        "GlobalResources",
+                        "LocalResources",
+                        "Styles",
+                        "Define",
+                        "ItemTemplate",
+                        "Container",
+                        "If",
+                        "Switch",
+                        "Any component",
+                    ],
+                ))?;
+                Ok(context.skip_tag(State::Content))
+            }
+        },
+        Token::TagEmpty(tag) => match tag.qualified_name().as_str() {
+            "Include" => context.create_include(reader, tag.clone()),
+            "Define" => context.create_define(reader, tag.clone(), true),
+            "If" | "Else" | "Switch" | "Case" => {
+                context.create_condition(reader, tag.clone(), true)
+            }
+            "Slot" | "Fill" => context.misplaced_slot(reader, tag.clone(), true, State::Content),
+            _ => context.create_component_node(reader, tag.clone()),
         },
-        Token::TagEmpty(tag) => context.create_component_node(reader, tag.clone()),
         Token::EOF => Err(reader.err_end_of_file()),
         _ => Ok(State::Content),
     }
@@ -0,0 +1,152 @@
+use crate::define::{Define, Fill};
+use crate::lexer::{TagEnd, Token};
+use crate::states::{FSMContext, State};
+use crate::{LayoutReader, XmlLayoutError, XmlTag};
+
+impl<'a> FSMContext<'a> {
+    /// Next token of the layout, an unprefixed element named like a `<Define>` is replaced by
+    /// the tokens of the instance, the `<Fill>` elements of its content take the place of the
+    /// slots
+    pub(crate) fn next_token(
+        &mut self,
+        reader: &mut LayoutReader<'a>,
+    ) -> Result<Token<'a>, XmlLayoutError> {
+        loop {
+            if let Some(token) = reader.replay.pop() {
+                return Ok(token);
+            }
+
+            let token = reader.read()?;
+            let (tag, empty) = match &token {
+                Token::TagStart(tag) if tag.prefix().is_none() => (tag, false),
+                Token::TagEmpty(tag) if tag.prefix().is_none() => (tag, true),
+                _ => return Ok(token),
+            };
+            if reader.define(tag.identifier()).is_none() {
+                return Ok(token);
+            }
+
+            let (fills, end) = match empty {
+                true => (vec![], None),
+                false => {
+                    let (fills, end) = self.read_fills(reader, tag)?;
+                    (fills, Some(end))
+                }
+            };
+            let define = reader.define(tag.identifier()).unwrap();
+            match define.instantiate(reader, tag, &fills, end) {
+                Ok(tokens) => reader.replay.extend(tokens.into_iter().rev()),
+                //The element is left out
+                Err(error) => self.report(error)?,
+            }
+        }
+    }
+
+    /// `<Fill>` elements up to the end tag of the instance, other elements are left out
+    fn read_fills(
+        &mut self,
+        reader: &mut LayoutReader<'a>,
+        tag: &XmlTag<'a>,
+    ) -> Result<(Vec<Fill<'a>>, TagEnd<'a>), XmlLayoutError> {
+        let mut fills = vec![];
+        loop {
+            let (element, content) = match self.next_token(reader)? {
+                Token::TagEnd(end) => {
+                    if end.qualified_name() != tag.qualified_name() {
+                        self.report(reader.err_mismatched_end_tag(&end, &tag.qualified_name()))?;
+                    }
+                    return Ok((fills, end));
+                }
+                Token::TagStart(element) => {
+                    let name = element.qualified_name();
+                    let content = self.read_content(reader, &name)?;
+                    (element, content)
+                }
+                Token::TagEmpty(element) => (element, vec![]),
+                Token::EOF => return Err(reader.err_end_of_file()),
+                _ => continue,
+            };
+
+            let define = reader.define(tag.identifier()).unwrap();
+            let fill = match element.qualified_name() == "Fill" {
+                true => Fill::new(reader, &element, define, content),
+                false => Err(reader.err_unexpected_tag(element, vec!["Fill"])),
+            };
+            match fill {
+                Ok(fill) => fills.push(fill),
+                Err(error) => self.report(error)?,
+            }
+        }
+    }
+
+    /// Reads the content of the `<Define>`, it can be used by the elements after it
+    pub(super) fn create_define(
+        &mut self,
+        reader: &mut LayoutReader<'a>,
+        tag: XmlTag<'a>,
+        empty: bool,
+    ) -> Result<State, XmlLayoutError> {
+        let mut define = match Define::new(reader, &tag) {
+            Ok(define) => define,
+            Err(error) => {
+                self.report(error)?;
+                return Ok(if empty {
+                    State::Content
+                } else {
+                    self.skip_tag(State::Content)
+                });
+            }
+        };
+
+        if !empty {
+            define.body = self.read_content(reader, "Define")?;
+        }
+        if let Err(error) = define.read_slots(reader) {
+            self.report(error)?;
+        }
+        reader.defines.push(define);
+        Ok(State::Content)
+    }
+
+    /// Tokens up to the end tag of the element which was just started
+    fn read_content(
+        &mut self,
+        reader: &mut LayoutReader<'a>,
+        name: &str,
+    ) -> Result<Vec<Token<'a>>, XmlLayoutError> {
+        let mut body = vec![];
+        let mut depth = 0usize;
+        loop {
+            let token = self.next_token(reader)?;
+            match &token {
+                Token::TagStart(_) => depth += 1,
+                Token::TagEnd(end) if depth == 0 => {
+                    if end.qualified_name() != name {
+                        self.report(reader.err_mismatched_end_tag(end, name))?;
+                    }
+                    return Ok(body);
+                }
+                Token::TagEnd(_) => depth -= 1,
+                Token::EOF => return Err(reader.err_end_of_file()),
+                _ => {}
+            }
+            body.push(token);
+        }
+    }
+
+    /// `<Slot>` and `<Fill>` are only read inside a `<Define>` and its instances
+    pub(super) fn misplaced_slot(
+        &mut self,
+        reader: &LayoutReader,
+        tag: XmlTag,
+        empty: bool,
+        state: State,
+    ) -> Result<State, XmlLayoutError> {
+        let expected = match tag.qualified_name().as_str() {
+            "Slot" => "inside a <Define>",
+            _ => "directly inside an element of a <Define>",
+        };
+        self.report(reader.err_misplaced_slot(tag, expected))?;
+        Ok(if empty { state } else { self.skip_tag(state) })
+    }
+}
@@ -1,4 +1,5 @@
 use crate::lexer::Token;
+use crate::position::NodeSpan;
 use crate::states::{FSMContext, State};
 use crate::{LayoutReader, XmlLayoutError};
 
@@ -6,14 +7,15 @@ pub(super) fn layout_execute(
     context: &mut FSMContext,
     reader: &LayoutReader,
 ) -> Result<State, XmlLayoutError> {
-    context.create_root_container();
     match &context.token {
         Token::TagStart(tag) => {
-            if tag.identifier() != "Layout" {
+            if tag.qualified_name() != "Layout" {
                 return Err(reader.err_missing_layout());
             }
+            context.create_root_container(NodeSpan::new(tag.span, tag.location));
             Ok(State::Content)
         }
+        Token::Comment(_) => Ok(State::Layout),
         _ => Err(reader.err_missing_layout()),
     }
 }
@@ -1,13 +1,21 @@
 mod content;
+mod define;
 mod layout;
+mod resources;
+mod skip;
+mod styles;
 mod template;
 mod using;
 
+use crate::attribute::Attribute;
 use crate::lexer::Token;
+use crate::position::{NodeSpan, Span};
 use crate::values::AttributeValue;
-use crate::{Id, ItemTemplate, LayoutReader, UiNode, XmlLayout, XmlLayoutError, XmlTag, into::Tag};
+use crate::{
+    Condition, ConditionTest, Id, Include, ItemTemplate, LayoutReader, UiNode, XmlLayout,
+    XmlLayoutError, XmlTag, into::Tag,
+};
 use std::fmt::{Display, Formatter};
-use std::sync::atomic::{AtomicU64, Ordering};
 
 struct Container {
     depth: usize,
@@ -15,50 +23,181 @@ struct Container {
 }
 
 #[derive(Default)]
-pub(super) struct FSMContext {
+pub(super) struct FSMContext<'a> {
     depth: usize,
     container_tmp: Vec<Container>,
     pub(crate) layout: XmlLayout,
-    pub(crate) token: Token,
+    pub(crate) token: Token<'a>,
+    /// Recoverable errors are collected instead of stopping the parser
+    pub(crate) recover: bool,
+    pub(crate) errors: Vec<XmlLayoutError>,
+    /// Depth inside the skipped tag and the state to continue with after it
+    skipped: usize,
+    resume: State,
+    ids: Ids,
 }
 
-static ID: AtomicU64 = AtomicU64::new(0);
+/// Numbers of the next generated ids, counted per document in the order of the source,
+/// so the same file always gets the same ids
+#[derive(Clone, Copy, Default)]
+struct Ids {
+    node: u64,
+    template: u64,
+}
+
+impl<'a> FSMContext<'a> {
+    pub fn recovering(recover: bool) -> Self {
+        Self {
+            recover,
+            ..Default::default()
+        }
+    }
+
+    /// Context of a nested tag, like `<ItemTemplate>`, which keeps numbering the ids of this one
+    fn nested(&self) -> Self {
+        Self {
+            recover: self.recover,
+            ids: self.ids,
+            layout: XmlLayout {
+                styles: self.layout.styles.clone(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn next_id(&mut self) -> Id {
+        let id = Id::Default(self.ids.node);
+        self.ids.node += 1;
+        id
+    }
+
+    fn next_template_id(&mut self) -> Id {
+        let id = Id::Template(self.ids.template);
+        self.ids.template += 1;
+        id
+    }
 
-impl FSMContext {
-    fn create_root_container(&mut self) {
+    /// Records the error if the parser recovers, otherwise stops with it
+    fn report(&mut self, error: XmlLayoutError) -> Result<(), XmlLayoutError> {
+        if self.recover {
+            self.errors.push(error);
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+
+    /// Ignores the current start tag with all of its content
+    fn skip_tag(&mut self, resume: State) -> State {
+        self.skipped = 1;
+        self.resume = resume;
+        State::Skip
+    }
+
+    /// Closes the containers left open by a fatal error, so the partial tree is kept
+    pub fn close_containers(&mut self) {
+        while self.depth > 0 {
+            self.push_nested_containers_in_parent();
+        }
+
+        let temp = std::mem::take(&mut self.container_tmp);
+        self.layout
+            .root_nodes
+            .extend(temp.into_iter().map(|c| c.inner));
+    }
+
+    /// The root container is placed at the `<Layout>` tag
+    #[cfg_attr(not(feature = "node_span"), allow(unused_variables))]
+    fn create_root_container(&mut self, span: NodeSpan) {
         self.container_tmp.push(Container {
             depth: 1,
             inner: UiNode {
                 tag: Tag {
                     name: "Container".to_string(),
+                    namespace: None,
+                    prefix: None,
                     attributes: vec![],
+                    #[cfg(feature = "node_span")]
+                    span,
                 },
                 id: Id::Custom("Root".into()),
                 components: vec![],
                 children: vec![],
+                condition: None,
+                #[cfg(feature = "node_span")]
+                span,
             },
         })
     }
 
+    /// Extends the span of the innermost open container up to its end tag
+    #[cfg_attr(not(feature = "node_span"), allow(unused_variables))]
+    fn end_container(&mut self, end: Span) {
+        #[cfg(feature = "node_span")]
+        if let Some(container) = self.container_tmp.last_mut() {
+            container.inner.span.span.extend(end);
+        }
+    }
+
     fn create_container_node(
         &mut self,
         reader: &LayoutReader,
         tag: XmlTag,
     ) -> Result<UiNode, XmlLayoutError> {
-        let mut node: UiNode = UiNode::new(reader, tag.clone())?;
-        node.id = if let Some(attr) = node.tag.attributes.iter().find(|attr| attr.name == "id") {
-            if let AttributeValue::Value(value) = &attr.value {
-                Id::Custom(value.clone())
-            } else {
-                return Err(reader.err_expected_value(&tag));
+        let (node_tag, errors) = Tag::parse(reader, tag.clone());
+        errors
+            .into_iter()
+            .try_for_each(|error| self.report(error))?;
+
+        let mut node = UiNode {
+            #[cfg(feature = "node_span")]
+            span: node_tag.span,
+            tag: node_tag,
+            id: Id::Default(0),
+            components: vec![],
+            children: vec![],
+            condition: None,
+        };
+        node.id = match node.tag.attributes.iter().find(|attr| attr.name == "id") {
+            Some(Attribute {
+                value: AttributeValue::Value(value),
+                ..
+            }) => Id::Custom(value.clone()),
+            Some(_) => {
+                self.report(reader.err_expected_value(&tag))?;
+                self.next_id()
             }
-        } else {
-            Id::Default(ID.fetch_add(1, Ordering::SeqCst))
+            None => self.next_id(),
         };
+        self.check_classes(reader, &tag, &node)?;
 
         Ok(node)
     }
 
+    /// Every class of the `class` attribute must be declared in `<Styles>`
+    fn check_classes(
+        &mut self,
+        reader: &LayoutReader,
+        tag: &XmlTag,
+        node: &UiNode,
+    ) -> Result<(), XmlLayoutError> {
+        match node.tag.attributes.iter().find(|attr| attr.name == "class") {
+            Some(Attribute {
+                value: AttributeValue::Value(_),
+                ..
+            }) => node
+                .classes()
+                .filter(|class| !self.layout.styles.iter().any(|style| style.class == *class))
+                .map(|class| reader.err_unknown_class(tag, class))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .try_for_each(|error| self.report(error)),
+            Some(_) => self.report(reader.err_expected_value(tag)),
+            None => Ok(()),
+        }
+    }
+
     pub fn create_nested_container(
         &mut self,
         reader: &LayoutReader,
@@ -73,17 +212,113 @@ impl FSMContext {
         Ok(State::Root)
     }
 
+    /// The element becomes an empty container, the included layout takes its place
+    /// when the includes are resolved
+    pub fn create_include(
+        &mut self,
+        reader: &LayoutReader,
+        tag: XmlTag,
+    ) -> Result<State, XmlLayoutError> {
+        if self.container_tmp.is_empty() {
+            self.report(reader.err_unexpected_tag(tag, vec!["Container"]))?;
+            return Ok(State::Content);
+        }
+
+        let node = self.create_container_node(reader, tag.clone())?;
+        match Include::new(reader, &tag, node.id.clone()) {
+            Ok(include) => self.layout.includes.push(include),
+            Err(error) => {
+                self.report(error)?;
+                return Ok(State::Root);
+            }
+        }
+
+        self.container_tmp.push(Container {
+            depth: self.depth,
+            inner: node,
+        });
+        self.depth += 1;
+        self.push_nested_containers_in_parent();
+        Ok(State::Root)
+    }
+
+    /// `<If>`, `<Else>`, `<Switch>` and `<Case>` are containers, the condition of the first
+    /// three decides whether they are shown. `<Else>` takes the test of the `<If>` before it,
+    /// `<Case>` the value of its `<Switch>`
+    pub fn create_condition(
+        &mut self,
+        reader: &LayoutReader,
+        tag: XmlTag,
+        empty: bool,
+    ) -> Result<State, XmlLayoutError> {
+        let name = tag.qualified_name();
+        let mut node = self.create_container_node(reader, tag.clone())?;
+        let parent = self.container_tmp.last().map(|parent| &parent.inner);
+        let condition = match name.as_str() {
+            "If" => Condition::value(reader, &tag, &node, "test").map(|value| {
+                value.map(|value| Condition {
+                    value,
+                    test: ConditionTest::True,
+                })
+            }),
+            "Else" => match parent.and_then(|parent| parent.children.last()) {
+                Some(UiNode {
+                    condition:
+                        Some(Condition {
+                            value,
+                            test: ConditionTest::True,
+                        }),
+                    ..
+                }) => Ok(Some(Condition {
+                    value: value.clone(),
+                    test: ConditionTest::False,
+                })),
+                _ => Err(reader.err_misplaced_condition(tag.clone(), "directly after an <If>")),
+            },
+            "Switch" => Condition::value(reader, &tag, &node, "value").map(|_| None),
+            _ => match parent.filter(|parent| parent.tag.name == "Switch") {
+                Some(switch) => Condition::equals(reader, &tag, &node).map(|equals| {
+                    let value = Condition::switch_value(switch);
+                    value.zip(equals).map(|(value, equals)| Condition {
+                        value,
+                        test: ConditionTest::Equals(equals),
+                    })
+                }),
+                None => Err(reader.err_misplaced_condition(tag.clone(), "inside a <Switch>")),
+            },
+        };
+        match condition {
+            Ok(condition) => node.condition = condition,
+            Err(error) => self.report(error)?,
+        }
+
+        self.container_tmp.push(Container {
+            depth: self.depth,
+            inner: node,
+        });
+        self.depth += 1;
+        if empty {
+            self.push_nested_containers_in_parent();
+        }
+        Ok(State::Root)
+    }
+
     pub fn create_component_node(
         &mut self,
         reader: &LayoutReader,
         tag: XmlTag,
     ) -> Result<State, XmlLayoutError> {
-        self.container_tmp
-            .last_mut()
-            .unwrap()
-            .inner
-            .components
-            .push(Tag::from(reader, tag.clone())?);
+        let Some(container) = self.container_tmp.last_mut() else {
+            //Components of a template must be inside its container
+            self.report(reader.err_unexpected_tag(tag, vec!["Container"]))?;
+            return Ok(State::Content);
+        };
+
+        let (component, errors) = Tag::parse(reader, tag);
+        container.inner.components.push(component);
+        errors
+            .into_iter()
+            .try_for_each(|error| self.report(error))?;
         Ok(State::Root)
     }
 
@@ -124,23 +359,40 @@ impl FSMContext {
         reader: &LayoutReader,
         tag: XmlTag,
     ) -> Result<State, XmlLayoutError> {
-        let owner = self.container_tmp.last_mut().unwrap().inner.id.clone();
-        self.layout
-            .templates
-            .push(ItemTemplate::new(reader, &tag, owner)?);
-        Ok(State::ItemTemplate)
+        let Some(container) = self.container_tmp.last() else {
+            self.report(reader.err_unexpected_tag(tag, vec!["Container"]))?;
+            return Ok(self.skip_tag(State::Content));
+        };
+
+        let owner = container.inner.id.clone();
+        match ItemTemplate::new(reader, &tag, owner) {
+            Ok(template) => {
+                self.layout.templates.push(template);
+                Ok(State::ItemTemplate)
+            }
+            Err(error) => {
+                self.report(error)?;
+                Ok(self.skip_tag(State::Root))
+            }
+        }
     }
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, Default, PartialEq)]
 pub(super) enum State {
+    #[default]
     Layout,
     Content,
     Use,
+    GlobalResources,
+    LocalResources,
+    Styles,
+    Style,
 
     ItemTemplate,
 
     Root,
+    Skip,
     Break,
 }
 
@@ -150,25 +402,35 @@ impl Display for State {
             State::Layout => write!(f, "Layout"),
             State::Content => write!(f, "Content"),
             State::Use => write!(f, "Use"),
+            State::GlobalResources => write!(f, "GlobalResources"),
+            State::LocalResources => write!(f, "LocalResources"),
+            State::Styles => write!(f, "Styles"),
+            State::Style => write!(f, "Style"),
             State::ItemTemplate => write!(f, "ItemTemplate"),
             State::Root => write!(f, "Root"),
+            State::Skip => write!(f, "Skip"),
             State::Break => write!(f, "Break"),
         }
     }
 }
 
 impl State {
-    pub fn execute(
+    pub fn execute<'a>(
         &self,
-        context: &mut FSMContext,
-        reader: &mut LayoutReader,
+        context: &mut FSMContext<'a>,
+        reader: &mut LayoutReader<'a>,
     ) -> Result<State, XmlLayoutError> {
         match self {
             State::Layout => layout::layout_execute(context, reader),
             State::Content => content::content_execute(context, reader),
-            State::Use => using::use_execute(context),
+            State::Use => using::use_execute(context, reader),
+            State::GlobalResources => resources::resources_execute(context, reader, true),
+            State::LocalResources => resources::resources_execute(context, reader, false),
+            State::Styles => styles::styles_execute(context, reader),
+            State::Style => styles::style_execute(context, reader),
             State::ItemTemplate => template::template_execute(context, reader),
             State::Root => root_execute(context, reader),
+            State::Skip => skip::skip_execute(context, reader),
             State::Break => Ok(State::Break),
         }
     }
@@ -176,30 +438,46 @@ impl State {
 
 fn root_execute(context: &mut FSMContext, reader: &LayoutReader) -> Result<State, XmlLayoutError> {
     match &context.token {
-        Token::TagStart(tag) => match tag.identifier() {
+        Token::TagStart(tag) => match tag.qualified_name().as_str() {
             "ItemTemplate" => context.create_template(reader, tag.clone()),
             "Container" => context.create_nested_container(reader, tag.clone()),
-            _ => Err(reader.err_unexpected_tag(
-                tag.clone(),
-                vec!["ItemTemplate", "Container", "Any component"],
-            )),
+            "If" | "Else" | "Switch" | "Case" => {
+                context.create_condition(reader, tag.clone(), false)
+            }
+            "Slot" | "Fill" => context.misplaced_slot(reader, tag.clone(), false, State::Root),
+            _ => {
+                context.report(reader.err_unexpected_tag(
+                    tag.clone(),
+                    vec!["ItemTemplate", "Container", "If", "Switch", "Any component"],
+                ))?;
+                Ok(context.skip_tag(State::Root))
+            }
+        },
+        Token::TagEmpty(tag) => match tag.qualified_name().as_str() {
+            "Include" => context.create_include(reader, tag.clone()),
+            "If" | "Else" | "Switch" | "Case" => {
+                context.create_condition(reader, tag.clone(), true)
+            }
+            "Slot" | "Fill" => context.misplaced_slot(reader, tag.clone(), true, State::Root),
+            _ => context.create_component_node(reader, tag.clone()),
         },
-        Token::TagEmpty(tag) => {
-            context
-                .container_tmp
-                .last_mut()
-                .unwrap()
-                .inner
-                .components
-                .push(Tag::from(reader, tag.clone())?);
-            Ok(State::Root)
-        }
-        Token::TagEnd(tag) => match tag.identifier() {
-            "Container" => {
+        Token::TagEnd(tag) => match tag.qualified_name().as_str() {
+            name @ ("Container" | "If" | "Else" | "Switch" | "Case") => {
+                let span = tag.span;
+                let open = context
+                    .container_tmp
+                    .last()
+                    .map(|open| &open.inner.tag.name);
+                if let Some(open) = open.filter(|open| *open != name) {
+                    let error = reader.err_mismatched_end_tag(tag, open);
+                    context.report(error)?;
+                }
+                context.end_container(span);
                 context.push_nested_containers_in_parent();
                 Ok(State::Root)
             }
             "Layout" => {
+                context.end_container(tag.span);
                 let temp = std::mem::take(&mut context.container_tmp);
                 context
                     .layout
@@ -207,7 +485,10 @@ fn root_execute(context: &mut FSMContext, reader: &LayoutReader) -> Result<State
                     .extend(temp.into_iter().map(|c| c.inner));
                 Ok(State::Break)
             }
-            other => panic!("Unsupported tag: {}", other),
+            _ => {
+                context.report(reader.err_mismatched_end_tag(tag, "Container"))?;
+                Ok(State::Root)
+            }
         },
         Token::EOF => Err(reader.err_end_of_file()),
         _ => Ok(State::Root),
@@ -235,12 +516,10 @@ mod tests {
         let mut state = State::Layout;
         while state != State::Break {
             context.token = reader.read().unwrap();
-            let result = state.execute(&mut context, &mut reader);
-            if result.is_err() {
-                panic!("{}", result.err().unwrap());
-            } else {
-                state = result.unwrap();
-            }
+            state = match state.execute(&mut context, &mut reader) {
+                Ok(state) => state,
+                Err(error) => panic!("{error}"),
+            };
         }
 
         println!();
@@ -0,0 +1,41 @@
+use crate::lexer::Token;
+use crate::states::{FSMContext, State};
+use crate::{LayoutProperty, LayoutReader, XmlLayoutError};
+
+pub(super) fn resources_execute(
+    context: &mut FSMContext,
+    reader: &LayoutReader,
+    global: bool,
+) -> Result<State, XmlLayoutError> {
+    let (name, state) = if global {
+        ("GlobalResources", State::GlobalResources)
+    } else {
+        ("LocalResources", State::LocalResources)
+    };
+
+    match &context.token {
+        Token::TagEmpty(tag) if tag.qualified_name() == "Property" => {
+            match LayoutProperty::new(reader, tag, global) {
+                Ok(property) => context.layout.properties.push(property),
+                Err(error) => context.report(error)?,
+            }
+            Ok(state)
+        }
+        Token::TagEnd(tag) => {
+            if tag.qualified_name() != name {
+                context.report(reader.err_mismatched_end_tag(tag, name))?;
+            }
+            Ok(State::Content)
+        }
+        Token::TagStart(tag) => {
+            context.report(reader.err_unexpected_tag(tag.clone(), vec!["Property"]))?;
+            Ok(context.skip_tag(state))
+        }
+        Token::TagEmpty(tag) => {
+            context.report(reader.err_unexpected_tag(tag.clone(), vec!["Property"]))?;
+            Ok(state)
+        }
+        Token::EOF => Err(reader.err_end_of_file()),
+        _ => Ok(state),
+    }
+}
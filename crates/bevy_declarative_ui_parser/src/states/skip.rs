@@ -0,0 +1,22 @@
+use crate::lexer::Token;
+use crate::states::{FSMContext, State};
+use crate::{LayoutReader, XmlLayoutError};
+
+/// Consumes the content of a tag which was reported as an error
+pub(super) fn skip_execute(
+    context: &mut FSMContext,
+    reader: &LayoutReader,
+) -> Result<State, XmlLayoutError> {
+    match &context.token {
+        Token::TagStart(_) => context.skipped += 1,
+        Token::TagEnd(_) => {
+            context.skipped -= 1;
+            if context.skipped == 0 {
+                return Ok(context.resume);
+            }
+        }
+        Token::EOF => return Err(reader.err_end_of_file()),
+        _ => {}
+    }
+    Ok(State::Skip)
+}
@@ -0,0 +1,78 @@
+use crate::into::Tag;
+use crate::lexer::Token;
+use crate::states::{FSMContext, State};
+use crate::{LayoutReader, Style, XmlLayoutError};
+
+pub(super) fn styles_execute(
+    context: &mut FSMContext,
+    reader: &LayoutReader,
+) -> Result<State, XmlLayoutError> {
+    match &context.token {
+        Token::TagStart(tag) if tag.qualified_name() == "Style" => {
+            match Style::new(reader, tag, &context.layout.styles) {
+                Ok(style) => {
+                    context.layout.styles.push(style);
+                    Ok(State::Style)
+                }
+                Err(error) => {
+                    context.report(error)?;
+                    Ok(context.skip_tag(State::Styles))
+                }
+            }
+        }
+        Token::TagEmpty(tag) if tag.qualified_name() == "Style" => {
+            match Style::new(reader, tag, &context.layout.styles) {
+                Ok(style) => context.layout.styles.push(style),
+                Err(error) => context.report(error)?,
+            }
+            Ok(State::Styles)
+        }
+        Token::TagEnd(tag) => {
+            if tag.qualified_name() != "Styles" {
+                context.report(reader.err_mismatched_end_tag(tag, "Styles"))?;
+            }
+            Ok(State::Content)
+        }
+        Token::TagStart(tag) => {
+            context.report(reader.err_unexpected_tag(tag.clone(), vec!["Style"]))?;
+            Ok(context.skip_tag(State::Styles))
+        }
+        Token::TagEmpty(tag) => {
+            context.report(reader.err_unexpected_tag(tag.clone(), vec!["Style"]))?;
+            Ok(State::Styles)
+        }
+        Token::EOF => Err(reader.err_end_of_file()),
+        _ => Ok(State::Styles),
+    }
+}
+
+/// Components of the `<Style>`, written like the components of a container
+pub(super) fn style_execute(
+    context: &mut FSMContext,
+    reader: &LayoutReader,
+) -> Result<State, XmlLayoutError> {
+    match &context.token {
+        Token::TagEmpty(tag) => {
+            let (component, errors) = Tag::parse(reader, tag.clone());
+            if let Some(style) = context.layout.styles.last_mut() {
+                style.components.push(component);
+            }
+            errors
+                .into_iter()
+                .try_for_each(|error| context.report(error))?;
+            Ok(State::Style)
+        }
+        Token::TagEnd(tag) => {
+            if tag.qualified_name() != "Style" {
+                context.report(reader.err_mismatched_end_tag(tag, "Style"))?;
+            }
+            Ok(State::Styles)
+        }
+        Token::TagStart(tag) => {
+            context.report(reader.err_unexpected_tag(tag.clone(), vec!["Any component"]))?;
+            Ok(context.skip_tag(State::Style))
+        }
+        Token::EOF => Err(reader.err_end_of_file()),
+        _ => Ok(State::Style),
+    }
+}
@@ -3,40 +3,44 @@ use crate::{
     Id, LayoutReader, XmlLayout, XmlLayoutError,
     states::{FSMContext, State},
 };
-use std::sync::atomic::{AtomicU64, Ordering};
 
-static TEMPLATE_ID: AtomicU64 = AtomicU64::new(0);
-
-pub(super) fn template_execute(
-    context: &mut FSMContext,
-    reader: &mut LayoutReader,
+pub(super) fn template_execute<'a>(
+    context: &mut FSMContext<'a>,
+    reader: &mut LayoutReader<'a>,
 ) -> Result<State, XmlLayoutError> {
-    let template = context.layout.templates.last_mut().unwrap();
-    let (template_layout, id) = parse_template_layout(reader)?;
+    let (template_layout, id) = parse_template_layout(context, reader)?;
 
+    let template = context.layout.templates.last_mut().unwrap();
     template.id = id;
     template.nodes.extend(template_layout.root_nodes);
+    context.layout.includes.extend(template_layout.includes);
     Ok(State::Root)
 }
 
-fn parse_template_layout(reader: &mut LayoutReader) -> Result<(XmlLayout, Id), XmlLayoutError> {
-    let mut template_context: FSMContext = FSMContext::default();
+fn parse_template_layout<'a>(
+    context: &mut FSMContext<'a>,
+    reader: &mut LayoutReader<'a>,
+) -> Result<(XmlLayout, Id), XmlLayoutError> {
+    let mut template_context: FSMContext<'a> = context.nested();
 
     let mut state = State::Content;
     loop {
-        let token = reader.read()?;
+        let token = template_context.next_token(reader)?;
         template_context.token = token;
         if let Token::TagEnd(tag) = &template_context.token {
-            if tag.identifier() == "ItemTemplate" {
+            if tag.qualified_name() == "ItemTemplate" && state != State::Skip {
                 break;
             }
         }
         state = state.execute(&mut template_context, reader)?
     }
+    context.errors.append(&mut template_context.errors);
+    context.ids = template_context.ids;
 
-    let id = Id::Template(TEMPLATE_ID.fetch_add(1, Ordering::SeqCst));
-    let first = template_context.container_tmp.get_mut(0).unwrap();
-    first.inner.id = id.clone();
+    let id = context.next_template_id();
+    if let Some(first) = template_context.container_tmp.get_mut(0) {
+        first.inner.id = id.clone();
+    }
     template_context
         .layout
         .root_nodes
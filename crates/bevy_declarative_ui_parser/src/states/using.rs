@@ -1,19 +1,31 @@
-use crate::XmlLayoutError;
 use crate::lexer::Token;
 use crate::states::{FSMContext, State};
+use crate::{LayoutReader, XmlLayoutError};
 
-pub(super) fn use_execute(context: &mut FSMContext) -> Result<State, XmlLayoutError> {
+pub(super) fn use_execute(
+    context: &mut FSMContext,
+    reader: &LayoutReader,
+) -> Result<State, XmlLayoutError> {
     match &context.token {
-        Token::Text(text) => {
-            context.layout.usings.insert(text.clone());
+        Token::Text(text) if !text.trim().is_empty() => {
+            context.layout.usings.insert(text.to_string());
             Ok(State::Use)
         }
+        Token::Text(_) | Token::Comment(_) | Token::Instruction(_) => Ok(State::Use),
         Token::TagEnd(tag) => {
-            if tag.identifier() != "Use" {
-                panic!("TODO: Tag mismatch");
+            if tag.qualified_name() != "Use" {
+                context.report(reader.err_mismatched_end_tag(tag, "Use"))?;
             }
             Ok(State::Content)
         }
-        other => panic!("Unsupported token: {}", other),
+        Token::TagStart(tag) => {
+            context.report(reader.err_unexpected_tag(tag.clone(), vec!["Text"]))?;
+            Ok(context.skip_tag(State::Use))
+        }
+        Token::TagEmpty(tag) => {
+            context.report(reader.err_unexpected_tag(tag.clone(), vec!["Text"]))?;
+            Ok(State::Use)
+        }
+        Token::EOF => Err(reader.err_end_of_file()),
     }
 }
@@ -0,0 +1,140 @@
+use crate::into::Tag;
+use crate::values::AttributeValue;
+use crate::{UiNode, XmlLayout, XmlLayoutError, XmlTag, layout_reader::LayoutReader};
+
+/// Class declared with `<Style class="..">` in `<Styles>`, a set of components with their
+/// attributes. Containers apply classes with `class="panel primary"`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Style {
+    pub class: String,
+    pub components: Vec<Tag>,
+}
+
+impl Style {
+    pub fn new(
+        reader: &LayoutReader,
+        tag: &XmlTag,
+        styles: &[Style],
+    ) -> Result<Self, XmlLayoutError> {
+        let class = reader.parse_required_attribute(tag, "class")?;
+        let class = class.value();
+        if class.is_empty() {
+            return Err(reader.err_empty_attribute(tag, "class"));
+        }
+        if class.contains(char::is_whitespace) {
+            return Err(reader.err_invalid_style(tag, class, "a class is a single word"));
+        }
+        if styles.iter().any(|style| style.class == class) {
+            return Err(reader.err_invalid_style(tag, class, "it is already declared"));
+        }
+
+        Ok(Style {
+            class: class.to_string(),
+            components: vec![],
+        })
+    }
+}
+
+impl UiNode {
+    /// Names in the `class` attribute of the container
+    pub fn classes(&self) -> impl Iterator<Item = &str> {
+        self.tag
+            .attributes
+            .iter()
+            .filter(|attribute| attribute.name == "class")
+            .filter_map(|attribute| match &attribute.value {
+                AttributeValue::Value(value) => Some(value),
+                //Reported by the parser
+                _ => None,
+            })
+            .flat_map(|value| value.split_whitespace())
+    }
+
+    /// Components of the container with its classes applied. Components of the classes which
+    /// are not on the container come first, an attribute of a later class wins over the one of
+    /// an earlier class and an attribute written on the component wins over both
+    pub fn styled_components(&self, styles: &[Style]) -> Vec<Tag> {
+        let mut styled: Vec<Tag> = vec![];
+        self.classes()
+            .filter_map(|class| styles.iter().find(|style| style.class == class))
+            .flat_map(|style| &style.components)
+            .for_each(|component| {
+                match styled
+                    .iter_mut()
+                    .find(|styled| same_component(styled, component))
+                {
+                    Some(styled) => merge(styled, component),
+                    None => styled.push(component.clone()),
+                }
+            });
+
+        let components = self
+            .components
+            .iter()
+            .map(|component| {
+                match styled
+                    .iter()
+                    .position(|styled| same_component(styled, component))
+                {
+                    Some(index) => {
+                        let mut class = styled.remove(index);
+                        merge(&mut class, component);
+                        class
+                    }
+                    None => component.clone(),
+                }
+            })
+            .collect::<Vec<_>>();
+        styled.extend(components);
+        styled
+    }
+}
+
+impl XmlLayout {
+    /// Replaces the components of every container by its styled components, the `class`
+    /// attributes and the styles are removed
+    pub fn apply_styles(&mut self) {
+        fn apply(node: &mut UiNode, styles: &[Style]) {
+            node.components = node.styled_components(styles);
+            node.tag
+                .attributes
+                .retain(|attribute| attribute.name != "class");
+            node.children
+                .iter_mut()
+                .for_each(|child| apply(child, styles));
+        }
+
+        let styles = std::mem::take(&mut self.styles);
+        self.root_nodes
+            .iter_mut()
+            .chain(
+                self.templates
+                    .iter_mut()
+                    .flat_map(|template| &mut template.nodes),
+            )
+            .for_each(|node| apply(node, &styles));
+    }
+}
+
+fn same_component(tag: &Tag, other: &Tag) -> bool {
+    tag.name == other.name && tag.namespace == other.namespace
+}
+
+/// Attributes of `other` replace the ones of `tag` with the same name, the span of the later
+/// declaration is kept
+fn merge(tag: &mut Tag, other: &Tag) {
+    for attribute in &other.attributes {
+        match tag
+            .attributes
+            .iter_mut()
+            .find(|declared| declared.name == attribute.name)
+        {
+            Some(declared) => *declared = attribute.clone(),
+            None => tag.attributes.push(attribute.clone()),
+        }
+    }
+    #[cfg(feature = "node_span")]
+    {
+        tag.span = other.span;
+    }
+}
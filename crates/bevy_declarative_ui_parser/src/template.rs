@@ -3,7 +3,7 @@ use crate::{
     Id, UiNode, XmlTag, errors::XmlLayoutError, layout_reader::LayoutReader, values::AttributeValue,
 };
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ItemTemplate {
     ///Always equals Id::Template
     pub id: Id,
@@ -19,15 +19,15 @@ impl ItemTemplate {
             return Err(reader.err_empty_attribute(&tag, "source"));
         }
 
-        if let AttributeValue::Template(source) = AttributeValue::parse(reader, &source, true)? {
+        if let AttributeValue::Template(binding) = AttributeValue::parse(reader, &source, true)? {
             Ok(ItemTemplate {
                 id: Id::Template(0),
                 owner,
-                source,
+                source: binding,
                 nodes: vec![],
             })
         } else {
-            panic!("Invalid binding input: {:?}", source);
+            Err(reader.err_invalid_template_source(&source))
         }
     }
 }
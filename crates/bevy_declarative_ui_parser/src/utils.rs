@@ -1,21 +1,5 @@
 use std::collections::HashMap;
 
-pub(crate) trait IsCurlyBracesEnclosed {
-    fn is_curly_braces_enclosed(&self) -> bool;
-}
-
-impl IsCurlyBracesEnclosed for &str {
-    fn is_curly_braces_enclosed(&self) -> bool {
-        self.starts_with('{') && self.ends_with('}')
-    }
-}
-
-impl IsCurlyBracesEnclosed for String {
-    fn is_curly_braces_enclosed(&self) -> bool {
-        self.as_str().is_curly_braces_enclosed()
-    }
-}
-
 impl<V> GetOrInsertEmpty<V> for HashMap<String, V>
 where
     V: Default,
@@ -1,4 +1,6 @@
+use crate::lexer::Value;
 use crate::values::parse_params;
+use crate::{LayoutReader, XmlLayoutError};
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct Asset {
@@ -6,30 +8,41 @@ pub struct Asset {
 }
 
 impl Asset {
-    pub fn parse(params: &str) -> Self {
-        let (path, params_str) = match params.split_once(',') {
-            Some((p, params)) => (p.trim(), Some(params.trim())),
-            None => (params.trim(), None),
-        };
-
-        let path = if path.starts_with("Path") {
-            path.split_once('=').unwrap().1
-        } else {
-            path
-        };
+    pub fn parse(
+        reader: &LayoutReader,
+        source: &Value,
+        params: &str,
+    ) -> Result<Self, XmlLayoutError> {
+        let path = read_path(reader, source, "Asset", params)?;
+        Ok(Asset { path })
+    }
+}
 
-        let mut params = parse_params(params_str.unwrap_or_default());
-        if !path.is_empty() {
-            params.insert("Path", path);
-        }
+/// Reads `Path` given either as the first unnamed parameter or as `Path=...`
+pub(super) fn read_path(
+    reader: &LayoutReader,
+    source: &Value,
+    binding_type: &str,
+    params: &str,
+) -> Result<String, XmlLayoutError> {
+    let (path, params_str) = match params.split_once(',') {
+        Some((p, params)) => (p.trim(), Some(params.trim())),
+        None => (params.trim(), None),
+    };
 
-        if params.is_empty() {
-            panic!("Missing parameters: Path");
-        }
+    let path = match path.strip_prefix("Path").map(str::trim_start) {
+        Some(value) if value.starts_with('=') => value[1..].trim(),
+        _ => path,
+    };
 
-        println!();
-        Asset {
-            path: params.get("Path").unwrap().to_string(),
-        }
+    let mut params = parse_params(params_str.unwrap_or_default())
+        .map_err(|param| reader.err_unexpected_unnamed_parameter(source, param))?;
+    if !path.is_empty() {
+        params.insert("Path", path);
     }
+
+    params
+        .get("Path")
+        .map(|path| path.to_string())
+        .ok_or_else(|| reader.err_missing_parameter(source, binding_type, "Path"))
 }
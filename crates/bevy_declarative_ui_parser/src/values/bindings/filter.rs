@@ -3,6 +3,8 @@ pub enum Filter {
     With(String),
     Without(String),
     Changed(String),
+    Added(String),
+    Or(Vec<Filter>),
 }
 
 impl std::fmt::Display for Filter {
@@ -11,6 +13,27 @@ impl std::fmt::Display for Filter {
             Filter::With(comp) => write!(f, "With<{comp}>"),
             Filter::Without(comp) => write!(f, "Without<{comp}>"),
             Filter::Changed(comp) => write!(f, "Changed<{comp}>"),
+            Filter::Added(comp) => write!(f, "Added<{comp}>"),
+            Filter::Or(filters) if filters.len() == 1 => write!(f, "Or<({},)>", filters[0]),
+            Filter::Or(filters) => {
+                let content = filters
+                    .iter()
+                    .map(Filter::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "Or<({content})>")
+            }
+        }
+    }
+}
+
+impl Filter {
+    /// `Changed` and `Added` filters, directly or inside `Or`, depend on the frame
+    pub fn is_change(&self) -> bool {
+        match self {
+            Filter::Changed(_) | Filter::Added(_) => true,
+            Filter::Or(filters) => filters.iter().any(Filter::is_change),
+            Filter::With(_) | Filter::Without(_) => false,
         }
     }
 }
@@ -45,29 +68,151 @@ impl Filters {
     pub const fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
-}
 
-impl From<&str> for Filters {
-    fn from(input: &str) -> Self {
-        let input = if input.starts_with('{') && input.ends_with('}') {
-            &input[1..input.len() - 1]
+    pub fn iter(&self) -> impl Iterator<Item = &Filter> {
+        self.0.iter()
+    }
+
+    /// Parses the value of the `Filter` parameter.
+    ///
+    /// ```text
+    /// filters := filter (',' filter)* | '{' filters '}'
+    /// filter  := name | '!' name | 'Changed<' name '>' | 'Added<' name '>' | 'Or(' filters ')'
+    /// ```
+    pub fn parse(input: &str) -> Result<Self, FilterError> {
+        let mut parser = FilterParser { input, position: 0 };
+        parser.skip_whitespaces();
+        if parser.is_eof() {
+            return Ok(Filters::default());
+        }
+
+        let filters = if parser.eat('{') {
+            let filters = parser.list(Some('}'))?;
+            parser.expect('}')?;
+            filters
         } else {
-            input
+            parser.list(None)?
         };
 
-        let filters = input.split(',');
-        Filters(
-            filters
-                .into_iter()
-                .map(|filter| {
-                    let filter = filter.trim();
-                    return if filter.starts_with('!') {
-                        Filter::Without(filter[1..].to_string())
-                    } else {
-                        Filter::With(filter.to_string())
-                    };
+        parser.skip_whitespaces();
+        if !parser.is_eof() {
+            return Err(parser.error_here("Unexpected input after filters"));
+        }
+
+        Ok(Filters(filters))
+    }
+}
+
+/// Error produced by [`Filters::parse`], positioned in bytes relative to the parsed input
+#[derive(Clone, Debug, PartialEq)]
+pub struct FilterError {
+    pub start: usize,
+    pub length: usize,
+    pub message: String,
+}
+
+struct FilterParser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl FilterParser<'_> {
+    fn list(&mut self, terminator: Option<char>) -> Result<Vec<Filter>, FilterError> {
+        let mut filters = vec![self.filter()?];
+        loop {
+            self.skip_whitespaces();
+            if self.is_eof() || self.peek() == terminator {
+                return Ok(filters);
+            }
+            self.expect(',')?;
+            filters.push(self.filter()?);
+        }
+    }
+
+    fn filter(&mut self) -> Result<Filter, FilterError> {
+        self.skip_whitespaces();
+        if self.eat('!') {
+            self.skip_whitespaces();
+            return Ok(Filter::Without(self.name()?));
+        }
+
+        let start = self.position;
+        let name = self.name()?;
+        self.skip_whitespaces();
+        match name.as_str() {
+            "Changed" | "Added" if self.eat('<') => {
+                self.skip_whitespaces();
+                let component = self.name()?;
+                self.expect('>')?;
+                Ok(match name.as_str() {
+                    "Changed" => Filter::Changed(component),
+                    _ => Filter::Added(component),
                 })
-                .collect(),
-        )
+            }
+            "Or" if self.eat('(') => {
+                let filters = self.list(Some(')'))?;
+                self.expect(')')?;
+                Ok(Filter::Or(filters))
+            }
+            "Changed" | "Added" | "Or" => Err(FilterError {
+                start,
+                length: name.len(),
+                message: format!("Filter '{name}' expects arguments"),
+            }),
+            _ => Ok(Filter::With(name)),
+        }
+    }
+
+    fn name(&mut self) -> Result<String, FilterError> {
+        let start = self.position;
+        let length = self.input[start..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+            .unwrap_or(self.input.len() - start);
+
+        if length == 0 {
+            return Err(self.error_here("Expected component name"));
+        }
+
+        self.position += length;
+        Ok(self.input[start..self.position].to_string())
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), FilterError> {
+        self.skip_whitespaces();
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.error_here(format!("Expected '{expected}'")))
+        }
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.position += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn is_eof(&self) -> bool {
+        self.position == self.input.len()
+    }
+
+    fn skip_whitespaces(&mut self) {
+        let rest = &self.input[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn error_here(&self, message: impl Into<String>) -> FilterError {
+        FilterError {
+            start: self.position,
+            length: self.peek().map_or(1, char::len_utf8),
+            message: message.into(),
+        }
     }
 }
@@ -2,6 +2,7 @@ pub mod filter;
 mod kind;
 mod mode;
 pub mod params;
+pub mod path;
 mod raw_binding;
 
 pub use kind::BindingKind;
@@ -13,7 +14,6 @@ use crate::utils::{GetOrInsertEmpty, TrimExtension};
 use crate::values::bindings::params::Params;
 use crate::{LayoutReader, XmlLayoutError};
 use raw_binding::RawBinding;
-use regex::Regex;
 use std::collections::HashMap;
 use std::fmt::Debug;
 
@@ -43,15 +43,15 @@ where
 }
 
 #[derive(Debug)]
-pub struct NamedParameter {
-    pub name: Value,
-    pub value: Value,
+pub struct NamedParameter<'a> {
+    pub name: Value<'a>,
+    pub value: Value<'a>,
     pub span: Span,
     pub location: Location,
 }
 
-impl NamedParameter {
-    pub const fn new(name: Value, value: Value, span: Span, location: Location) -> Self {
+impl<'a> NamedParameter<'a> {
+    pub const fn new(name: Value<'a>, value: Value<'a>, span: Span, location: Location) -> Self {
         Self {
             name,
             value,
@@ -71,65 +71,45 @@ where
         source: &Value,
         target: Value,
         params: &str,
+        params_at: usize,
     ) -> Result<Self, XmlLayoutError> {
-        let diff = source.value().len() - params.len() - 1;
-
-        let mut location = source.location;
-        let mut params_span = source.span;
-        location.column += diff;
-        params_span.start += diff;
-        params_span.end -= 1; //Skip '}'
+        //`params_at` is the offset of `params` in the source value
+        let value_at = |offset: usize, inner: &str| {
+            source.slice(params_at + offset, params_at + offset + inner.len())
+        };
 
         let mut unnamed = None;
         let mut named = HashMap::<String, Vec<NamedParameter>>::new();
 
-        params.split(',').enumerate().for_each(|(i, raw)| {
+        let mut offset = 0;
+        let params = split_params(params);
+        params.iter().enumerate().try_for_each(|(i, raw)| {
             let trim_result = raw.trim_ext();
             let param = trim_result.string;
-            location.column += trim_result.before;
-            params_span.start += trim_result.before;
+            let begin = offset + trim_result.before;
+            offset += raw.len() + 1; //Skip ','
 
             if param.contains('=') {
                 let (key, value) = param.split_once('=').unwrap();
-
                 let key_new = key.trim_end();
-                params_span.end = params_span.start + key_new.len();
-                let key_result = Value::new(params_span, location, key_new);
-                location.column += key_new.len();
-
                 let value_new = value.trim_start();
-                let offset = key.len() - key_new.len() + value.len() - value_new.len() + 1;
-                location.column += offset;
-                params_span.start = params_span.end + offset;
-                params_span.end = params_span.start + value_new.len();
-                let value_result = Value::new(params_span, location, value_new);
+                let value_begin = begin + key.len() + 1 + value.len() - value_new.len();
 
                 let parameter = NamedParameter::new(
-                    key_result,
-                    value_result,
+                    value_at(begin, key_new),
+                    value_at(value_begin, value_new),
                     Span::new(0, 0),
                     Location::new(0, 0, 0),
                 );
                 named.get_or_insert(key_new, || vec![]).push(parameter);
-
-                let offset = value.len() - value_new.len() + 1; //Skip whitespaces with ','
-                params_span.start = params_span.end + offset;
-                location.column += value_new.len() + offset; // Skip "value[whitespaces],"
             } else if i == 0 {
-                unnamed = if param.is_empty() {
-                    None
-                } else {
-                    params_span.end = params_span.start + param.len();
-                    let value =
-                        Value::new(params_span, location, reader.substring_other(&params_span));
-                    params_span.start = params_span.end + 1;
-                    location.column += param.len() + 1;
-                    Some(value)
-                };
+                unnamed = (!param.is_empty()).then(|| value_at(begin, param));
             } else {
-                panic!("Warning: ignored unexpected unnamed parameter '{param}'");
+                let value = value_at(begin, param);
+                return Err(reader.err_unexpected_unnamed_parameter(&value, param));
             }
-        });
+            Ok(())
+        })?;
 
         create_binding(
             reader,
@@ -152,17 +132,34 @@ where
         "Component" => Binding::new(base_params, additional_params, BindingKind::Component),
         "Resource" => Binding::new(base_params, additional_params, BindingKind::Resource),
         "Item" => Binding::new(base_params, additional_params, BindingKind::Item),
-        _ => panic!("Unknown binding type: {}", raw.target.value()),
+        other => return Err(reader.err_unknown_binding_type(&raw.target, other)),
     };
 
     Ok(binding)
 }
 
-fn format_path(input: &str) -> String {
-    let array_regex = Regex::new(r"\[([a-zA-Z0-9:_]+)]").unwrap();
-    array_regex
-        .replace_all(input, |caps: &regex::Captures| {
-            format!("get({}).unwrap()", &caps[1])
-        })
-        .to_string()
+/// Splits binding parameters on the commas which are not nested into `{}`, `()` or `[]` and
+/// not quoted, `<` and `>` are plain characters so they can appear in values
+fn split_params(params: &str) -> Vec<&str> {
+    let mut result = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    params.char_indices().for_each(|(i, c)| match (quote, c) {
+        (Some(_), _) if escaped => escaped = false,
+        (Some(_), '\\') => escaped = true,
+        (Some(open), c) if c == open => quote = None,
+        (Some(_), _) => {}
+        (None, '"' | '\'') => quote = Some(c),
+        (None, '{' | '(' | '[') => depth += 1,
+        (None, '}' | ')' | ']') => depth = depth.saturating_sub(1),
+        (None, ',') if depth == 0 => {
+            result.push(&params[start..i]);
+            start = i + 1;
+        }
+        _ => {}
+    });
+    result.push(&params[start..]);
+    result
 }
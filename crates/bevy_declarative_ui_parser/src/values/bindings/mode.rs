@@ -1,3 +1,9 @@
+use crate::values::bindings::params::Params;
+use crate::values::bindings::raw_binding::RawBinding;
+use crate::{LayoutReader, XmlLayoutError};
+use std::fmt::Debug;
+use std::str::FromStr;
+
 #[derive(Copy, Clone, Default, Debug, PartialEq, Hash, Eq)]
 pub enum BindingMode {
     Read,
@@ -7,14 +13,36 @@ pub enum BindingMode {
     ReadOnce,
 }
 
-impl BindingMode {
-    pub fn from_str(value: &str) -> Self {
+impl FromStr for BindingMode {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
         match value {
-            "Read" => Self::Read,
-            "Write" => Self::Write,
-            "ReadWrite" => Self::ReadWrite,
-            "ReadOnce" => Self::ReadOnce,
-            _ => panic!(),
+            "Read" => Ok(Self::Read),
+            "Write" => Ok(Self::Write),
+            "ReadWrite" => Ok(Self::ReadWrite),
+            "ReadOnce" => Ok(Self::ReadOnce),
+            _ => Err(()),
+        }
+    }
+}
+
+impl BindingMode {
+    /// Reads the `Mode` parameter, `ReadOnce` if it is not specified
+    pub(crate) fn read<B>(
+        reader: &LayoutReader,
+        raw: &mut RawBinding<B>,
+    ) -> Result<Self, XmlLayoutError>
+    where
+        B: Clone + Debug + PartialEq + Params,
+    {
+        match raw.try_take("Mode") {
+            Some(param) => param
+                .value
+                .value()
+                .parse()
+                .map_err(|_| reader.err_unknown_binding_mode(&param.value)),
+            None => Ok(Self::default()),
         }
     }
 }
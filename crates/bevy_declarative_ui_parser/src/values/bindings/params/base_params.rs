@@ -1,13 +1,14 @@
+use crate::values::bindings::BindingMode;
 use crate::values::bindings::params::Params;
+use crate::values::bindings::path::BindingPath;
 use crate::values::bindings::raw_binding::RawBinding;
-use crate::values::bindings::{BindingMode, format_path};
 use crate::{LayoutReader, XmlLayoutError};
 use std::fmt::Debug;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct BaseParams {
     pub target: String,
-    pub path: String,
+    pub path: BindingPath,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -35,16 +36,16 @@ impl Params for BaseParams {
                 .to_string(),
         );
 
-        let path = Some(format_path(
-            raw.try_take("Path")
-                .ok_or(reader.err_missing_parameter(&raw.source, &raw.target.inner, "Path"))?
-                .value
-                .value(),
-        ));
+        let path = raw
+            .try_take("Path")
+            .ok_or(reader.err_missing_parameter(&raw.source, &raw.target.inner, "Path"))?
+            .value;
+        let path = BindingPath::parse(path.value())
+            .map_err(|error| reader.err_invalid_path(&path, error))?;
 
         Ok(BaseParams {
             target: target.unwrap(),
-            path: path.unwrap(),
+            path,
         })
     }
 }
@@ -54,13 +55,12 @@ impl Params for AdditionalParams {
         None
     }
 
-    fn read<B>(_: &LayoutReader, raw: &mut RawBinding<B>) -> Result<Self, XmlLayoutError>
+    fn read<B>(reader: &LayoutReader, raw: &mut RawBinding<B>) -> Result<Self, XmlLayoutError>
     where
         Self: Sized,
         B: Clone + Debug + PartialEq + Params,
     {
-        let mode =
-            BindingMode::from_str(&raw.try_take_value("Mode").unwrap_or("ReadOnce".to_string()));
+        let mode = BindingMode::read(reader, raw)?;
         let fallback = raw.try_take_value("Fallback");
         let converter = raw.try_take_value("Converter");
 
@@ -1,3 +1,4 @@
+use crate::values::bindings::BindingMode;
 use crate::values::bindings::filter::Filters;
 use crate::values::bindings::params::Params;
 use crate::values::bindings::raw_binding::RawBinding;
@@ -7,6 +8,9 @@ use std::fmt::Debug;
 #[derive(Clone, Debug, PartialEq)]
 pub struct ComponentParams {
     pub filters: Filters,
+    pub converter: Option<String>,
+    pub fallback: Option<String>,
+    pub mode: BindingMode,
 }
 
 impl Params for ComponentParams {
@@ -14,12 +18,24 @@ impl Params for ComponentParams {
         None
     }
 
-    fn read<B>(_: &LayoutReader, raw: &mut RawBinding<B>) -> Result<Self, XmlLayoutError>
+    fn read<B>(reader: &LayoutReader, raw: &mut RawBinding<B>) -> Result<Self, XmlLayoutError>
     where
         Self: Sized,
         B: Clone + Debug + PartialEq + Params,
     {
-        let filters = Filters::from(raw.try_take_value("Fallback").unwrap_or_default().as_str());
-        Ok(Self { filters })
+        let filters = match raw.try_take("Filter") {
+            Some(param) => Filters::parse(param.value.value())
+                .map_err(|error| reader.err_invalid_filter(&param.value, error))?,
+            None => Filters::default(),
+        };
+        let converter = raw.try_take_value("Converter");
+        let fallback = raw.try_take_value("Fallback");
+        let mode = BindingMode::read(reader, raw)?;
+        Ok(Self {
+            filters,
+            converter,
+            fallback,
+            mode,
+        })
     }
 }
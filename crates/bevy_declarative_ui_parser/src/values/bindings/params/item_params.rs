@@ -1,12 +1,12 @@
-use crate::values::bindings::format_path;
 use crate::values::bindings::params::Params;
+use crate::values::bindings::path::BindingPath;
 use crate::values::bindings::raw_binding::RawBinding;
 use crate::{LayoutReader, XmlLayoutError};
 use std::fmt::Debug;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ItemBaseParams {
-    pub path: String,
+    pub path: BindingPath,
 }
 
 impl Params for ItemBaseParams {
@@ -19,15 +19,13 @@ impl Params for ItemBaseParams {
         Self: Sized,
         B: Clone + Debug + PartialEq + Params,
     {
-        let path = Some(format_path(
-            raw.try_take("Path")
-                .ok_or(reader.err_missing_parameter(&raw.source, &raw.target.inner, "Path"))?
-                .value
-                .value(),
-        ));
+        let path = raw
+            .try_take("Path")
+            .ok_or(reader.err_missing_parameter(&raw.source, &raw.target.inner, "Path"))?
+            .value;
+        let path = BindingPath::parse(path.value())
+            .map_err(|error| reader.err_invalid_path(&path, error))?;
 
-        Ok(ItemBaseParams {
-            path: path.unwrap().to_string(),
-        })
+        Ok(ItemBaseParams { path })
     }
 }
@@ -0,0 +1,274 @@
+use crate::position::Span;
+
+/// Parsed value of the `Path` binding parameter
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct BindingPath {
+    pub segments: Vec<PathSegment>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathSegment {
+    pub access: PathAccess,
+    /// Segment is followed by `?`, the accessed value is an `Option` which is unwrapped
+    pub optional: bool,
+    /// Span of the segment in the `Path` value, without the leading `.`
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathAccess {
+    /// `name`
+    Field(String),
+    /// `0`
+    TupleIndex(usize),
+    /// `[0]`
+    Index(usize),
+    /// `["key"]` or `['key']`
+    Key(String),
+}
+
+/// Error produced by [`BindingPath::parse`], positioned in bytes relative to the parsed input
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathError {
+    pub start: usize,
+    pub length: usize,
+    pub message: String,
+}
+
+impl BindingPath {
+    /// Parses the value of the `Path` parameter.
+    ///
+    /// ```text
+    /// path    := (first next*)?
+    /// first   := (name | integer | index) '?'?
+    /// next    := ('.' (name | integer) | index) '?'?
+    /// index   := '[' (integer | '"' string '"' | '\'' string '\'') ']'
+    /// ```
+    pub fn parse(input: &str) -> Result<Self, PathError> {
+        let mut parser = PathParser { input, position: 0 };
+        let mut segments = vec![];
+        while !parser.is_eof() {
+            let dot = !segments.is_empty() && parser.eat('.');
+            segments.push(parser.segment(segments.is_empty() || dot)?);
+        }
+
+        Ok(BindingPath { segments })
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Any segment can fail at runtime, either by unwrapping an `Option` or by indexing
+    pub fn is_fallible(&self) -> bool {
+        self.segments.iter().any(|segment| {
+            segment.optional || matches!(segment.access, PathAccess::Index(_) | PathAccess::Key(_))
+        })
+    }
+
+    /// Rust expression of type `Option<&T>` which reads the path from `base`
+    pub fn to_rust(&self, base: &str) -> String {
+        //Every step stays a place expression, so the result is borrowed only once
+        let mut expression = base.to_string();
+        self.segments.iter().for_each(|segment| {
+            expression = match &segment.access {
+                PathAccess::Field(name) => format!("{expression}.{name}"),
+                PathAccess::TupleIndex(index) => format!("{expression}.{index}"),
+                PathAccess::Index(index) => format!("(*{expression}.get({index})?)"),
+                PathAccess::Key(key) => format!("(*{expression}.get({key:?})?)"),
+            };
+            if segment.optional {
+                expression = format!("(*{expression}.as_ref()?)");
+            }
+        });
+
+        if self.is_fallible() {
+            format!("(|| Some(&{expression}))()")
+        } else {
+            format!("Some(&{expression})")
+        }
+    }
+
+    /// Rust expression of type `Option<&mut T>` which reads the path from `base`
+    pub fn to_rust_mut(&self, base: &str) -> String {
+        //A closure can not return a mutable borrow of its capture, fallible steps are chained instead
+        fn borrow(place: &str, chain: Option<String>) -> String {
+            match chain {
+                None => format!("Some(&mut {place})"),
+                Some(chain) if place == "value" => chain,
+                Some(chain) => format!("{chain}.map(|value| &mut {place})"),
+            }
+        }
+        fn step(place: &mut String, chain: &mut Option<String>, call: String) {
+            let borrowed = borrow(place, chain.take());
+            *chain = Some(format!("{borrowed}.and_then(|value| value.{call})"));
+            *place = "value".to_string();
+        }
+
+        let mut place = base.to_string();
+        let mut chain: Option<String> = None;
+
+        self.segments.iter().for_each(|segment| {
+            match &segment.access {
+                PathAccess::Field(name) => place = format!("{place}.{name}"),
+                PathAccess::TupleIndex(index) => place = format!("{place}.{index}"),
+                PathAccess::Index(index) => {
+                    step(&mut place, &mut chain, format!("get_mut({index})"))
+                }
+                PathAccess::Key(key) => step(&mut place, &mut chain, format!("get_mut({key:?})")),
+            }
+            if segment.optional {
+                step(&mut place, &mut chain, "as_mut()".to_string());
+            }
+        });
+
+        borrow(&place, chain)
+    }
+}
+
+impl std::fmt::Display for BindingPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            match &segment.access {
+                PathAccess::Field(name) if i == 0 => write!(f, "{name}")?,
+                PathAccess::Field(name) => write!(f, ".{name}")?,
+                PathAccess::TupleIndex(index) if i == 0 => write!(f, "{index}")?,
+                PathAccess::TupleIndex(index) => write!(f, ".{index}")?,
+                PathAccess::Index(index) => write!(f, "[{index}]")?,
+                PathAccess::Key(key) => write!(f, "[{key:?}]")?,
+            }
+            if segment.optional {
+                write!(f, "?")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct PathParser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl PathParser<'_> {
+    fn segment(&mut self, member: bool) -> Result<PathSegment, PathError> {
+        let start = self.position;
+        let access = match self.peek() {
+            Some('[') => self.index()?,
+            Some(c) if member && c.is_ascii_digit() => PathAccess::TupleIndex(self.integer()?),
+            Some(c) if member && (c.is_alphabetic() || c == '_') => PathAccess::Field(self.name()),
+            _ if member => return Err(self.error_here("Expected field name or tuple index")),
+            _ => return Err(self.error_here("Expected '.' or '['")),
+        };
+        let optional = self.eat('?');
+
+        Ok(PathSegment {
+            access,
+            optional,
+            span: Span::new(start, self.position),
+        })
+    }
+
+    fn index(&mut self) -> Result<PathAccess, PathError> {
+        self.expect('[')?;
+        let access = match self.peek() {
+            Some(quote @ ('"' | '\'')) => PathAccess::Key(self.string(quote)?),
+            Some(c) if c.is_ascii_digit() => PathAccess::Index(self.integer()?),
+            _ => return Err(self.error_here("Expected index or string key")),
+        };
+        self.expect(']')?;
+        Ok(access)
+    }
+
+    fn name(&mut self) -> String {
+        let start = self.position;
+        let length = self.input[start..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(self.input.len() - start);
+        self.position += length;
+        self.input[start..self.position].to_string()
+    }
+
+    fn integer(&mut self) -> Result<usize, PathError> {
+        let start = self.position;
+        let length = self.input[start..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.input.len() - start);
+        self.position += length;
+        self.input[start..self.position]
+            .parse()
+            .map_err(|_| PathError {
+                start,
+                length,
+                message: "Index is too large".to_string(),
+            })
+    }
+
+    fn string(&mut self, quote: char) -> Result<String, PathError> {
+        let start = self.position;
+        self.expect(quote)?;
+        let mut result = String::new();
+        loop {
+            match self.next() {
+                Some(c) if c == quote => return Ok(result),
+                Some('\\') => match self.next() {
+                    Some(c @ ('"' | '\'' | '\\')) => result.push(c),
+                    _ => {
+                        return Err(PathError {
+                            start: self.position - 1,
+                            length: 1,
+                            message: "Unknown escape sequence".to_string(),
+                        });
+                    }
+                },
+                Some(c) => result.push(c),
+                None => {
+                    return Err(PathError {
+                        start,
+                        length: self.position - start,
+                        message: "Unterminated string key".to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), PathError> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.error_here(format!("Expected '{expected}'")))
+        }
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.position += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn is_eof(&self) -> bool {
+        self.position == self.input.len()
+    }
+
+    fn error_here(&self, message: impl Into<String>) -> PathError {
+        PathError {
+            start: self.position,
+            length: self.peek().map_or(1, char::len_utf8),
+            message: message.into(),
+        }
+    }
+}
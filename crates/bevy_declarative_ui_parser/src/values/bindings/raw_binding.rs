@@ -8,26 +8,26 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
-pub struct RawBinding<B>
+pub struct RawBinding<'a, B>
 where
     B: Clone + Debug + PartialEq + Params,
 {
-    pub source: Value,
-    pub target: Value,
-    pub params: Vec<NamedParameter>,
+    pub source: Value<'a>,
+    pub target: Value<'a>,
+    pub params: Vec<NamedParameter<'a>>,
     _marker: PhantomData<B>,
 }
 
-impl<B> RawBinding<B>
+impl<'a, B> RawBinding<'a, B>
 where
     B: Clone + Debug + PartialEq + Params,
 {
     pub fn new(
         reader: &LayoutReader,
-        source: Value,
-        target: Value,
-        unnamed: Option<Value>,
-        mut named: HashMap<String, Vec<NamedParameter>>,
+        source: Value<'a>,
+        target: Value<'a>,
+        unnamed: Option<Value<'a>>,
+        mut named: HashMap<String, Vec<NamedParameter<'a>>>,
     ) -> Result<Self, XmlLayoutError> {
         let mut result = Vec::new();
         if let Some(unnamed) = unnamed
@@ -65,7 +65,7 @@ where
             .is_some()
     }
 
-    pub fn get_duplicates(&self, key: &str) -> Vec<Value> {
+    pub fn get_duplicates(&self, key: &str) -> Vec<Value<'a>> {
         self.params
             .iter()
             .filter(|p| p.name.value().eq(key))
@@ -73,7 +73,7 @@ where
             .collect()
     }
 
-    pub fn try_take(&mut self, key: &str) -> Option<NamedParameter> {
+    pub fn try_take(&mut self, key: &str) -> Option<NamedParameter<'a>> {
         if let Some(pos) = self.params.iter().position(|p| p.name.value().eq(key)) {
             return Some(self.params.remove(pos));
         }
@@ -84,7 +84,7 @@ where
     pub fn try_take_value(&mut self, key: &str) -> Option<String> {
         if let Some(pos) = self.params.iter().position(|p| p.name.value().eq(key)) {
             let value = self.params.remove(pos);
-            return Some(value.value.inner);
+            return Some(value.value.into_inner());
         }
 
         None
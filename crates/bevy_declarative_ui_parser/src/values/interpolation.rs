@@ -0,0 +1,90 @@
+use crate::values::AttributeValue;
+
+/// Part of an attribute value which mixes text with bindings, e.g. `Score: {Resource Score, Path=current}`
+#[derive(Clone, PartialEq, Debug)]
+pub enum InterpolationPart {
+    /// Literal text, with `{{` and `}}` already unescaped
+    Text(String),
+    /// `Item`, `Resource` or `Component` binding
+    Binding(AttributeValue),
+}
+
+/// Error produced while splitting an attribute value, positioned in bytes relative to the value
+#[derive(Clone, Debug, PartialEq)]
+pub struct InterpolationError {
+    pub start: usize,
+    pub length: usize,
+    pub message: String,
+}
+
+pub(crate) enum RawPart {
+    Text(String),
+    /// Byte range of the binding including its braces
+    Binding(usize, usize),
+}
+
+/// Splits the value into text and `{...}` bindings, nested braces belong to the binding
+pub(crate) fn split(input: &str) -> Result<Vec<RawPart>, InterpolationError> {
+    let bytes = input.as_bytes();
+    let mut parts = vec![];
+    let mut text = String::new();
+    let mut position = 0;
+    while position < input.len() {
+        match bytes[position] {
+            b'{' if bytes.get(position + 1) == Some(&b'{') => {
+                text.push('{');
+                position += 2;
+            }
+            b'}' if bytes.get(position + 1) == Some(&b'}') => {
+                text.push('}');
+                position += 2;
+            }
+            b'{' => {
+                let end = closing_brace(input, position).ok_or(InterpolationError {
+                    start: position,
+                    length: 1,
+                    message: "Unclosed '{'".to_string(),
+                })?;
+                if !text.is_empty() {
+                    parts.push(RawPart::Text(std::mem::take(&mut text)));
+                }
+                parts.push(RawPart::Binding(position, end + 1));
+                position = end + 1;
+            }
+            b'}' => {
+                return Err(InterpolationError {
+                    start: position,
+                    length: 1,
+                    message: "Unexpected '}', use '}}' for a literal brace".to_string(),
+                });
+            }
+            _ => {
+                let c = input[position..].chars().next().unwrap();
+                text.push(c);
+                position += c.len_utf8();
+            }
+        }
+    }
+
+    if !text.is_empty() {
+        parts.push(RawPart::Text(text));
+    }
+    Ok(parts)
+}
+
+fn closing_brace(input: &str, start: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in input[start..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(start + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
@@ -1,4 +1,6 @@
-use crate::values::parse_params;
+use crate::lexer::Value;
+use crate::values::asset::read_path;
+use crate::{LayoutReader, XmlLayoutError};
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct Item {
@@ -6,29 +8,12 @@ pub struct Item {
 }
 
 impl Item {
-    pub fn parse(params: &str) -> Self {
-        let (path, params_str) = match params.split_once(',') {
-            Some((p, params)) => (p.trim(), Some(params.trim())),
-            None => (params.trim(), None),
-        };
-
-        let path = if path.starts_with("Path") {
-            path.split_once('=').unwrap().1
-        } else {
-            path
-        };
-
-        let mut params = parse_params(params_str.unwrap_or_default());
-        if !path.is_empty() {
-            params.insert("Path", path);
-        }
-
-        if params.is_empty() {
-            panic!("Missing parameters: Path");
-        }
-
-        Item {
-            path: params.get("Path").unwrap().to_string(),
-        }
+    pub fn parse(
+        reader: &LayoutReader,
+        source: &Value,
+        params: &str,
+    ) -> Result<Self, XmlLayoutError> {
+        let path = read_path(reader, source, "Item", params)?;
+        Ok(Item { path })
     }
 }
@@ -1,21 +1,23 @@
 mod asset;
 pub mod bindings;
 mod function;
+mod interpolation;
 mod item;
 
 pub use asset::Asset;
 pub use function::Function;
+pub use interpolation::{InterpolationError, InterpolationPart};
 pub use item::Item;
 
 use std::collections::HashMap;
 
 use crate::lexer::Value;
-use crate::utils::IsCurlyBracesEnclosed;
 use crate::utils::TrimExtension;
 use crate::values::bindings::Binding;
 use crate::values::bindings::params::ComponentParams;
 use crate::values::bindings::params::ItemBaseParams;
 use crate::values::bindings::params::{AdditionalParams, BaseParams};
+use crate::values::interpolation::RawPart;
 use crate::{LayoutReader, XmlLayoutError};
 
 #[derive(Clone, PartialEq, Debug)]
@@ -32,6 +34,10 @@ pub enum AttributeValue {
     Resource(Binding<BaseParams, AdditionalParams>),
     Component(Binding<BaseParams, ComponentParams>),
     Template(TemplateBinding),
+    /// Text mixed with bindings, re-rendered when any binding changes
+    Interpolated(Vec<InterpolationPart>),
+    /// `{Name}` reads the property declared in `<GlobalResources>` or `<LocalResources>`
+    Property(String),
 }
 
 impl AttributeValue {
@@ -41,48 +47,110 @@ impl AttributeValue {
         template: bool,
     ) -> Result<Self, XmlLayoutError> {
         let input = &value.inner;
-        if input.is_curly_braces_enclosed() {
-            let trim_result = input[1..input.len() - 1].trim_ext();
-            let unwrap_input = trim_result.string;
-            let (target, params) = unwrap_input
-                .split_once(char::is_whitespace)
-                .unwrap_or((unwrap_input, ""));
-
-            let mut location = value.location;
-            let mut target_span = value.span;
-            location.column += trim_result.before + 1;
-            target_span.start += trim_result.before + 1;
-            target_span.end = target_span.start + target.len();
-            let target = Value::new(target_span, location, target);
-            Ok(match target.inner.as_str() {
-                "Asset" => AttributeValue::Asset(Asset::parse(params)),
-                "Item" => AttributeValue::Item(Binding::parse(reader, value, target, params)?),
-                "Component" if !template => {
-                    AttributeValue::Component(Binding::parse(reader, value, target, params)?)
-                }
-                "Resource" if !template => {
-                    AttributeValue::Resource(Binding::parse(reader, value, target, params)?)
-                }
-                "Component" if template => AttributeValue::Template(TemplateBinding::Component(
-                    Binding::parse(reader, value, target, params)?,
-                )),
-                "Resource" if template => AttributeValue::Template(TemplateBinding::Resource(
-                    Binding::parse(reader, value, target, params)?,
-                )),
-                other => panic!("The actual input is not a valid attribute value: {other}"),
-            })
-        } else {
-            Ok(AttributeValue::Value(input.to_string()))
+        let parts = interpolation::split(input)
+            .map_err(|error| reader.err_invalid_interpolation(value, error))?;
+
+        match parts.as_slice() {
+            [] => Ok(AttributeValue::Value(String::new())),
+            [RawPart::Text(text)] => Ok(AttributeValue::Value(text.clone())),
+            [RawPart::Binding(start, end)] if *start == 0 && *end == input.len() => {
+                Self::parse_binding(reader, value, template)
+            }
+            _ if template => Err(reader.err_invalid_interpolation(
+                value,
+                InterpolationError {
+                    start: 0,
+                    length: input.len(),
+                    message: "ItemTemplate source must be a single binding".to_string(),
+                },
+            )),
+            _ => {
+                let parts = parts
+                    .into_iter()
+                    .map(|part| match part {
+                        RawPart::Text(text) => Ok(InterpolationPart::Text(text)),
+                        RawPart::Binding(start, end) => {
+                            let binding = value.slice(start, end);
+                            match Self::parse_binding(reader, &binding, false)? {
+                                parsed @ (AttributeValue::Item(_)
+                                | AttributeValue::Resource(_)
+                                | AttributeValue::Component(_)) => {
+                                    Ok(InterpolationPart::Binding(parsed))
+                                }
+                                _ => Err(reader.err_invalid_interpolation(
+                                    value,
+                                    InterpolationError {
+                                        start,
+                                        length: end - start,
+                                        message: "Only Item, Resource and Component bindings can be interpolated".to_string(),
+                                    },
+                                )),
+                            }
+                        }
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(AttributeValue::Interpolated(parts))
+            }
         }
     }
+
+    /// Parses a value which is exactly one `{...}` binding
+    fn parse_binding(
+        reader: &LayoutReader,
+        value: &Value,
+        template: bool,
+    ) -> Result<Self, XmlLayoutError> {
+        let input = &value.inner;
+        let trim_result = input[1..input.len() - 1].trim_ext();
+        let unwrap_input = trim_result.string;
+        let begin = trim_result.before + 1; //Skip '{'
+
+        //Offset of the parameters in the value, the end of the binding if there are none
+        let (target, params, params_at) = match unwrap_input.split_once(char::is_whitespace) {
+            Some((target, params)) => (target, params, begin + unwrap_input.len() - params.len()),
+            None => (unwrap_input, "", begin + unwrap_input.len()),
+        };
+
+        let target = value.slice(begin, begin + target.len());
+        Ok(match target.value() {
+            "Asset" => AttributeValue::Asset(Asset::parse(reader, value, params)?),
+            "Item" => {
+                AttributeValue::Item(Binding::parse(reader, value, target, params, params_at)?)
+            }
+            "Component" if !template => {
+                AttributeValue::Component(Binding::parse(reader, value, target, params, params_at)?)
+            }
+            "Resource" if !template => {
+                AttributeValue::Resource(Binding::parse(reader, value, target, params, params_at)?)
+            }
+            "Component" if template => AttributeValue::Template(TemplateBinding::Component(
+                Binding::parse(reader, value, target, params, params_at)?,
+            )),
+            "Resource" if template => AttributeValue::Template(TemplateBinding::Resource(
+                Binding::parse(reader, value, target, params, params_at)?,
+            )),
+            name if !template && params.trim().is_empty() && is_property_name(name) => {
+                AttributeValue::Property(name.to_string())
+            }
+            other => return Err(reader.err_unknown_binding_type(&target, other)),
+        })
+    }
+}
+
+pub(crate) fn is_property_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|char| char.is_alphanumeric() || char == '_')
 }
 
-fn parse_params(input: &str) -> HashMap<&str, &str> {
+/// Named `key=value` parameters, a parameter without `=` is returned as the error
+fn parse_params(input: &str) -> Result<HashMap<&str, &str>, &str> {
     let mut params = HashMap::new();
     let mut start = 0;
     let mut end = 0;
     let mut inside_quotes = false;
-    input.chars().into_iter().enumerate().for_each(|(i, c)| {
+    for (i, c) in input.chars().enumerate() {
         let is_eol = input.len() - 1 == i;
         end += 1;
 
@@ -92,14 +160,14 @@ fn parse_params(input: &str) -> HashMap<&str, &str> {
             }
         } else if is_eol {
             let param = &input[start..end];
-            let (name, value) = param.split_once('=').unwrap();
+            let (name, value) = param.split_once('=').ok_or(param.trim())?;
             params.insert(name.trim(), value.trim());
             start = end;
         } else if c == ',' {
             end -= 1;
 
             let param = &input[start..end];
-            let (name, value) = param.split_once('=').unwrap();
+            let (name, value) = param.split_once('=').ok_or(param.trim())?;
             params.insert(name.trim(), value.trim());
 
             end += 1;
@@ -107,7 +175,7 @@ fn parse_params(input: &str) -> HashMap<&str, &str> {
         } else if c == '{' {
             inside_quotes = true;
         }
-    });
+    }
 
-    params
+    Ok(params)
 }
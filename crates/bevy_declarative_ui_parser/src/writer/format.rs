@@ -0,0 +1,67 @@
+use crate::lexer::{Tag, Token};
+use crate::writer::{LayoutWriter, Printer, escape, start_tag};
+use crate::{LayoutReader, XmlLayoutError};
+
+impl LayoutWriter {
+    /// Reformats the source of a valid layout with one element per line, indented by its depth.
+    /// Comments, processing instructions and the order of the attributes are kept, values are
+    /// written decoded and escaped again, so the result does not depend on the original spacing
+    pub fn format(&self, content: &str, file: &str) -> Result<String, XmlLayoutError> {
+        LayoutReader::new(content, file).parse()?;
+
+        let mut reader = LayoutReader::new(content, file);
+        reader.lossless = true;
+        let mut tokens: Vec<Token> = vec![];
+        loop {
+            match (reader.read()?, tokens.last_mut()) {
+                (Token::EOF, _) => break,
+                //Texts around a CDATA section are one text
+                (Token::Text(text), Some(Token::Text(previous))) => {
+                    previous.to_mut().push_str(&text)
+                }
+                (token, _) => tokens.push(token),
+            }
+        }
+        //Whitespace between the elements is replaced by the indentation
+        tokens.retain(|token| !matches!(token, Token::Text(text) if text.trim().is_empty()));
+
+        let mut printer = Printer::new(&self.indent);
+        let mut index = 0;
+        while index < tokens.len() {
+            match (&tokens[index], tokens.get(index + 1), tokens.get(index + 2)) {
+                //An element with text only stays on one line, like `<Use>game::Globals</Use>`
+                (Token::TagStart(tag), Some(Token::Text(text)), Some(Token::TagEnd(_))) => {
+                    printer.line(&format!(
+                        "{}{}</{}>",
+                        print_tag(tag, false),
+                        escape(text.trim(), false),
+                        tag.qualified_name()
+                    ));
+                    index += 3;
+                    continue;
+                }
+                (Token::TagStart(tag), _, _) => printer.start(&print_tag(tag, false)),
+                (Token::TagEmpty(tag), _, _) => printer.line(&print_tag(tag, true)),
+                (Token::TagEnd(tag), _, _) => printer.end(&tag.qualified_name()),
+                (Token::Text(text), _, _) => printer.line(&escape(text.trim(), false)),
+                (Token::Comment(comment), _, _) => printer.line(&format!("<!--{comment}-->")),
+                (Token::Instruction(instruction), _, _) => {
+                    printer.line(&format!("<?{instruction}?>"))
+                }
+                _ => {}
+            }
+            index += 1;
+        }
+
+        Ok(printer.output)
+    }
+}
+
+/// The lossless reader keeps prefixes and namespace declarations in the names
+fn print_tag(tag: &Tag, empty: bool) -> String {
+    let attributes = tag
+        .attributes()
+        .iter()
+        .map(|attribute| (attribute.qualified_name(), attribute.value()));
+    start_tag(&tag.qualified_name(), attributes, empty)
+}
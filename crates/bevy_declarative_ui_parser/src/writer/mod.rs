@@ -0,0 +1,308 @@
+mod format;
+mod value;
+
+use crate::into::Tag;
+use crate::lexer::namespaces::qualified_name;
+use crate::{ItemTemplate, UiNode, XmlLayout};
+use value::{attribute_value, template_source};
+
+/// Writes layouts back to XML.
+///
+/// [`LayoutWriter::write`] prints a parsed [`XmlLayout`] in canonical form, so tools can change
+/// the tree and save it. [`LayoutWriter::format`] reformats the source of a layout and keeps its
+/// comments and the order of its attributes.
+pub struct LayoutWriter {
+    indent: String,
+}
+
+impl Default for LayoutWriter {
+    fn default() -> Self {
+        Self::new("    ")
+    }
+}
+
+impl LayoutWriter {
+    pub fn new(indent: impl Into<String>) -> Self {
+        Self {
+            indent: indent.into(),
+        }
+    }
+
+    /// `<Use>` directives are sorted, then come the properties, the styles and the tree.
+    /// Components are written before the templates and the containers of their parent, bindings
+    /// with their parameters in a fixed order and without the default `Mode`.
+    ///
+    /// The layout only keeps the expanded tree, so writing is lossy: `<Define>` elements are
+    /// dropped and their instances are written as containers with scoped ids. Use
+    /// [`LayoutWriter::format`] to rewrite a source file, it keeps them
+    pub fn write(&self, layout: &XmlLayout) -> String {
+        let prefixes = Prefixes::collect(layout);
+        let mut printer = Printer::new(&self.indent);
+        printer.start(&start_tag("Layout", prefixes.declarations(), false));
+
+        let mut usings = layout.usings.iter().collect::<Vec<_>>();
+        usings.sort();
+        usings.into_iter().for_each(|using| {
+            printer.line(&format!("<Use>{}</Use>", escape(using, false)));
+        });
+
+        for (name, global) in [("GlobalResources", true), ("LocalResources", false)] {
+            let mut properties = layout
+                .properties
+                .iter()
+                .filter(|property| property.global == global)
+                .peekable();
+            if properties.peek().is_none() {
+                continue;
+            }
+
+            printer.start(&format!("<{name}>"));
+            properties.for_each(|property| {
+                let attributes = [
+                    ("name", property.name.clone()),
+                    ("type", property.r#type.clone()),
+                    ("value", property.value.clone()),
+                ];
+                printer.line(&start_tag("Property", attributes, true));
+            });
+            printer.end(name);
+        }
+
+        if !layout.styles.is_empty() {
+            printer.start("<Styles>");
+            layout.styles.iter().for_each(|style| {
+                let empty = style.components.is_empty();
+                let tag = start_tag("Style", [("class", style.class.as_str())], empty);
+                if empty {
+                    printer.line(&tag);
+                    return;
+                }
+                printer.start(&tag);
+                style.components.iter().for_each(|component| {
+                    let name = prefixes.name(component);
+                    printer.line(&start_tag(&name, attributes(component), true));
+                });
+                printer.end("Style");
+            });
+            printer.end("Styles");
+        }
+
+        let writer = TreeWriter {
+            layout,
+            prefixes: &prefixes,
+        };
+        //The parser wraps the document into the `Root` container, its content is the document
+        layout
+            .root_nodes
+            .iter()
+            .for_each(|root| writer.content(&mut printer, root));
+
+        printer.end("Layout");
+        printer.output
+    }
+}
+
+struct TreeWriter<'a> {
+    layout: &'a XmlLayout,
+    prefixes: &'a Prefixes,
+}
+
+impl TreeWriter<'_> {
+    fn templates<'b>(&'b self, owner: &'b UiNode) -> impl Iterator<Item = &'b ItemTemplate> {
+        self.layout
+            .templates
+            .iter()
+            .filter(move |template| template.owner == owner.id)
+    }
+
+    fn content(&self, printer: &mut Printer, node: &UiNode) {
+        node.components.iter().for_each(|component| {
+            let name = self.prefixes.name(component);
+            printer.line(&start_tag(&name, attributes(component), true));
+        });
+        self.templates(node)
+            .for_each(|template| self.template(printer, template));
+        node.children
+            .iter()
+            .for_each(|child| self.container(printer, child));
+    }
+
+    fn container(&self, printer: &mut Printer, node: &UiNode) {
+        let empty = node.components.is_empty()
+            && node.children.is_empty()
+            && self.templates(node).next().is_none();
+        //Conditional containers and the `<Include>` elements of unresolved layouts keep their tag
+        let tag = start_tag(&node.tag.name, attributes(&node.tag), empty);
+        if empty {
+            printer.line(&tag);
+            return;
+        }
+
+        printer.start(&tag);
+        self.content(printer, node);
+        printer.end(&node.tag.name);
+    }
+
+    fn template(&self, printer: &mut Printer, template: &ItemTemplate) {
+        let source = [("source", template_source(&template.source))];
+        printer.start(&start_tag("ItemTemplate", source, false));
+        template
+            .nodes
+            .iter()
+            .for_each(|node| self.container(printer, node));
+        printer.end("ItemTemplate");
+    }
+}
+
+fn attributes(tag: &Tag) -> impl Iterator<Item = (&str, String)> {
+    tag.attributes
+        .iter()
+        .map(|attribute| (attribute.name.as_str(), attribute_value(&attribute.value)))
+}
+
+/// Prefixes of the component namespaces, declared on `<Layout>`
+struct Prefixes(Vec<(String, String)>);
+
+impl Prefixes {
+    /// Prefixes written in the source are kept. A namespace without one, like a default
+    /// namespace, gets the last segment of the namespace if it is a valid name, e.g. `game` for
+    /// `my_crate::game`, a number is appended to repeated prefixes
+    fn collect(layout: &XmlLayout) -> Self {
+        fn visit<'a>(components: &mut Vec<&'a Tag>, node: &'a UiNode) {
+            components.extend(&node.components);
+            node.children
+                .iter()
+                .for_each(|child| visit(components, child));
+        }
+
+        let mut components = layout
+            .styles
+            .iter()
+            .flat_map(|style| &style.components)
+            .collect::<Vec<_>>();
+        layout
+            .root_nodes
+            .iter()
+            .chain(layout.templates.iter().flat_map(|template| &template.nodes))
+            .for_each(|node| visit(&mut components, node));
+
+        let mut prefixes = Prefixes(vec![]);
+        //Source prefixes first, so an invented prefix can not take one of them
+        components.iter().for_each(|component| {
+            if let (Some(namespace), Some(prefix)) = (&component.namespace, &component.prefix) {
+                prefixes.add(namespace, prefix);
+            }
+        });
+        components
+            .iter()
+            .filter_map(|component| component.namespace.as_deref())
+            .for_each(|namespace| {
+                let last = namespace.rsplit("::").next().unwrap_or_default();
+                let valid = last.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                    && last.chars().all(|c| c.is_alphanumeric() || c == '_')
+                    && !last.to_ascii_lowercase().starts_with("xml");
+                prefixes.add(namespace, if valid { last } else { "ns" });
+            });
+        prefixes
+    }
+
+    /// Declares the namespace with the first free prefix among `base`, `base1`, `base2`..
+    fn add(&mut self, namespace: &str, base: &str) {
+        if self.0.iter().any(|(declared, _)| declared == namespace) {
+            return;
+        }
+
+        let taken = |prefix: &str| self.0.iter().any(|(_, declared)| declared == prefix);
+        let mut prefix = base.to_string();
+        let mut number = 1;
+        while taken(&prefix) {
+            prefix = format!("{base}{number}");
+            number += 1;
+        }
+        self.0.push((namespace.to_string(), prefix));
+    }
+
+    fn name(&self, tag: &Tag) -> String {
+        let prefix = tag.namespace.as_deref().and_then(|namespace| {
+            self.0
+                .iter()
+                .find(|(declared, _)| declared == namespace)
+                .map(|(_, prefix)| prefix.as_str())
+        });
+        qualified_name(prefix, &tag.name)
+    }
+
+    fn declarations(&self) -> impl Iterator<Item = (String, String)> {
+        self.0
+            .iter()
+            .map(|(namespace, prefix)| (format!("xmlns:{prefix}"), namespace.clone()))
+    }
+}
+
+/// Output indented by the depth of the open elements
+struct Printer<'a> {
+    indent: &'a str,
+    depth: usize,
+    output: String,
+}
+
+impl<'a> Printer<'a> {
+    fn new(indent: &'a str) -> Self {
+        Self {
+            indent,
+            depth: 0,
+            output: String::new(),
+        }
+    }
+
+    fn line(&mut self, line: &str) {
+        (0..self.depth).for_each(|_| self.output.push_str(self.indent));
+        self.output.push_str(line);
+        self.output.push('\n');
+    }
+
+    fn start(&mut self, tag: &str) {
+        self.line(tag);
+        self.depth += 1;
+    }
+
+    fn end(&mut self, name: &str) {
+        self.depth = self.depth.saturating_sub(1);
+        self.line(&format!("</{name}>"));
+    }
+}
+
+fn start_tag<N, V>(name: &str, attributes: impl IntoIterator<Item = (N, V)>, empty: bool) -> String
+where
+    N: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut tag = format!("<{name}");
+    attributes.into_iter().for_each(|(name, value)| {
+        tag.push_str(&format!(
+            " {}=\"{}\"",
+            name.as_ref(),
+            escape(value.as_ref(), true)
+        ));
+    });
+    tag.push_str(if empty { "/>" } else { ">" });
+    tag
+}
+
+/// Replaces the characters which can not appear as they are in text or in a quoted attribute,
+/// `<` is escaped in attributes too, so filters like `Changed<Health>` become `Changed&lt;Health>`.
+/// Line breaks and tabs of attributes are character references, a parser normalizes them to spaces
+fn escape(value: &str, attribute: bool) -> String {
+    let mut result = String::with_capacity(value.len());
+    value.chars().for_each(|c| match c {
+        '&' => result.push_str("&amp;"),
+        '<' => result.push_str("&lt;"),
+        '>' if !attribute => result.push_str("&gt;"),
+        '"' if attribute => result.push_str("&quot;"),
+        '\n' if attribute => result.push_str("&#10;"),
+        '\t' if attribute => result.push_str("&#9;"),
+        '\r' if attribute => result.push_str("&#13;"),
+        c => result.push(c),
+    });
+    result
+}
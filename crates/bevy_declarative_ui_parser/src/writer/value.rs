@@ -0,0 +1,144 @@
+use crate::values::bindings::BindingMode;
+use crate::values::bindings::filter::{Filter, Filters};
+use crate::values::bindings::path::BindingPath;
+use crate::values::{AttributeValue, InterpolationPart, TemplateBinding};
+
+/// Source of the attribute value, before XML escaping
+pub(super) fn attribute_value(value: &AttributeValue) -> String {
+    match value {
+        AttributeValue::Value(value) => escape_braces(value),
+        AttributeValue::Asset(asset) => format!("{{Asset {}}}", asset.path),
+        AttributeValue::Item(binding) => {
+            let params = &binding.additional_params;
+            let mut params = additional(params.mode, None, &params.converter, &params.fallback);
+            let path = &binding.base_params.path;
+            if path.is_empty() {
+                params.insert(0, ("Path", String::new()));
+                print_binding("Item", None, params)
+            } else {
+                print_binding("Item", Some(path.to_string()), params)
+            }
+        }
+        AttributeValue::Resource(binding) => {
+            let params = &binding.additional_params;
+            let mut base = base(&binding.base_params.path);
+            base.extend(additional(
+                params.mode,
+                None,
+                &params.converter,
+                &params.fallback,
+            ));
+            print_binding("Resource", Some(binding.base_params.target.clone()), base)
+        }
+        AttributeValue::Component(binding) => {
+            let params = &binding.additional_params;
+            let mut base = base(&binding.base_params.path);
+            base.extend(additional(
+                params.mode,
+                Some(&params.filters),
+                &params.converter,
+                &params.fallback,
+            ));
+            print_binding("Component", Some(binding.base_params.target.clone()), base)
+        }
+        AttributeValue::Template(binding) => template_source(binding),
+        AttributeValue::Interpolated(parts) => parts
+            .iter()
+            .map(|part| match part {
+                InterpolationPart::Text(text) => escape_braces(text),
+                InterpolationPart::Binding(binding) => attribute_value(binding),
+            })
+            .collect(),
+        AttributeValue::Property(name) => format!("{{{name}}}"),
+    }
+}
+
+/// Value of the `source` attribute of `<ItemTemplate>`
+pub(super) fn template_source(binding: &TemplateBinding) -> String {
+    match binding {
+        TemplateBinding::Resource(binding) => print_binding(
+            "Resource",
+            Some(binding.base_params.target.clone()),
+            base(&binding.base_params.path),
+        ),
+        TemplateBinding::Component(binding) => {
+            let params = &binding.additional_params;
+            let mut base = base(&binding.base_params.path);
+            base.extend(additional(
+                params.mode,
+                Some(&params.filters),
+                &params.converter,
+                &params.fallback,
+            ));
+            print_binding("Component", Some(binding.base_params.target.clone()), base)
+        }
+    }
+}
+
+fn base(path: &BindingPath) -> Vec<(&'static str, String)> {
+    vec![("Path", path.to_string())]
+}
+
+/// Parameters in a fixed order, `ReadOnce` is the default mode and is left out
+fn additional(
+    mode: BindingMode,
+    filters: Option<&Filters>,
+    converter: &Option<String>,
+    fallback: &Option<String>,
+) -> Vec<(&'static str, String)> {
+    let mut params = vec![];
+    if mode != BindingMode::ReadOnce {
+        params.push(("Mode", format!("{mode:?}")));
+    }
+    if let Some(filters) = filters.filter(|filters| !filters.is_empty()) {
+        params.push(("Filter", print_filters(filters)));
+    }
+    if let Some(converter) = converter {
+        params.push(("Converter", converter.clone()));
+    }
+    if let Some(fallback) = fallback {
+        params.push(("Fallback", fallback.clone()));
+    }
+    params
+}
+
+fn print_binding(kind: &str, unnamed: Option<String>, params: Vec<(&str, String)>) -> String {
+    let params = unnamed
+        .into_iter()
+        .chain(
+            params
+                .into_iter()
+                .map(|(name, value)| format!("{name}={value}")),
+        )
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{{{kind} {params}}}")
+}
+
+/// Several filters are grouped in braces, so their commas do not split the binding parameters
+fn print_filters(filters: &Filters) -> String {
+    let list = filters.iter().map(print_filter).collect::<Vec<_>>();
+    if list.len() == 1 {
+        list.into_iter().next().unwrap()
+    } else {
+        format!("{{{}}}", list.join(", "))
+    }
+}
+
+fn print_filter(filter: &Filter) -> String {
+    match filter {
+        Filter::With(component) => component.clone(),
+        Filter::Without(component) => format!("!{component}"),
+        Filter::Changed(component) => format!("Changed<{component}>"),
+        Filter::Added(component) => format!("Added<{component}>"),
+        Filter::Or(filters) => {
+            let list = filters.iter().map(print_filter).collect::<Vec<_>>();
+            format!("Or({})", list.join(", "))
+        }
+    }
+}
+
+/// Literal braces are doubled, otherwise they would start a binding
+fn escape_braces(text: &str) -> String {
+    text.replace('{', "{{").replace('}', "}}")
+}
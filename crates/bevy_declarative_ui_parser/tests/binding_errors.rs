@@ -0,0 +1,125 @@
+mod utils;
+
+use bevy_declarative_ui_parser::LayoutReader;
+use bevy_declarative_ui_parser::errors::{ErrorContext, XmlLayoutError};
+use bevy_declarative_ui_parser::position::{ErrorSpan, Location};
+use utils::*;
+
+fn parse_error(content: &str) -> XmlLayoutError {
+    let error = LayoutReader::new(content, "inline.xml")
+        .parse()
+        .unwrap_err();
+    println!("{error}");
+    error
+}
+
+#[test]
+fn unknown_binding_mode() {
+    let (content, file) = load("unknown_binding_mode.xml");
+    let error = LayoutReader::new(&content, &file).parse().unwrap_err();
+    println!("{error}");
+    assert_eq!(
+        error,
+        XmlLayoutError::UnknownBindingMode {
+            context: ErrorContext::new(
+                file,
+                Location::new(8, 2, 51),
+                ErrorSpan::new("Once".into(), 0, 4),
+            ),
+            name: "Once".into(),
+        }
+    );
+}
+
+#[test]
+fn unexpected_unnamed_parameter() {
+    let error =
+        parse_error(r#"<Layout><Text text="{Resource Score, Path=current, max}"/></Layout>"#);
+    match error {
+        XmlLayoutError::UnexpectedUnnamedParameter { parameter, .. } => {
+            assert_eq!(parameter, "max")
+        }
+        other => panic!("Expected unexpected unnamed parameter, found {other:?}"),
+    }
+}
+
+#[test]
+fn unknown_binding_type() {
+    let error = parse_error(r#"<Layout><Text text="{Global Score}"/></Layout>"#);
+    match error {
+        XmlLayoutError::UnknownBindingType { name, .. } => assert_eq!(name, "Global"),
+        other => panic!("Expected unknown binding type, found {other:?}"),
+    }
+}
+
+#[test]
+fn asset_without_path() {
+    let error = parse_error(r#"<Layout><ImageNode image="{Asset}"/></Layout>"#);
+    match error {
+        XmlLayoutError::MissingParameter { name, .. } => assert_eq!(name, "Path"),
+        other => panic!("Expected missing parameter, found {other:?}"),
+    }
+
+    let error = parse_error(r#"<Layout><ImageNode image="{Asset a.png, flip}"/></Layout>"#);
+    assert!(matches!(
+        error,
+        XmlLayoutError::UnexpectedUnnamedParameter { .. }
+    ));
+}
+
+#[test]
+fn invalid_template_source() {
+    let error = parse_error(
+        r#"<Layout><Container><ItemTemplate source="{Asset players.png}"></ItemTemplate></Container></Layout>"#,
+    );
+    assert!(matches!(
+        error,
+        XmlLayoutError::InvalidTemplateSource { .. }
+    ));
+}
+
+#[test]
+fn mismatched_end_tag() {
+    let error = parse_error(r#"<Layout><Container></Panel></Layout>"#);
+    match error {
+        XmlLayoutError::MismatchedEndTag {
+            current, expected, ..
+        } => {
+            assert_eq!(current, "Panel");
+            assert_eq!(expected, "Container");
+        }
+        other => panic!("Expected mismatched end tag, found {other:?}"),
+    }
+}
+
+/// Missing parameter error of a binding without parameters, reported on the binding type
+fn missing_parameter(content: &str) -> (String, usize) {
+    match parse_error(content) {
+        XmlLayoutError::MissingParameter { name, context } => (name, context.error.length()),
+        other => panic!("Expected missing parameter, found {other:?}"),
+    }
+}
+
+#[test]
+fn resource_without_params() {
+    let error = missing_parameter(r#"<Layout><Text text="{Resource}"/></Layout>"#);
+    assert_eq!(error, ("Target".to_string(), "Resource".len()));
+}
+
+#[test]
+fn resource_with_trailing_whitespace() {
+    let error = missing_parameter(r#"<Layout><Text text="{Resource }"/></Layout>"#);
+    assert_eq!(error, ("Target".to_string(), "Resource".len()));
+}
+
+#[test]
+fn item_without_params() {
+    let error = missing_parameter(
+        r#"<Layout>
+            <ItemTemplate source="{Resource Players, Path=list}">
+                <Container><Text text="{Item}"/></Container>
+            </ItemTemplate>
+        </Layout>"#,
+    );
+    assert_eq!(error, ("Path".to_string(), "Item".len()));
+}
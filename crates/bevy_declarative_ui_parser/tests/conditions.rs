@@ -0,0 +1,152 @@
+use bevy_declarative_ui_parser::errors::XmlLayoutError;
+use bevy_declarative_ui_parser::values::AttributeValue;
+use bevy_declarative_ui_parser::{
+    Condition, ConditionTest, Id, LayoutReader, LayoutWriter, UiNode, XmlLayout,
+};
+
+const LAYOUT: &str = r#"<Layout>
+    <If test="{Resource GameState, Path=paused}" id="Paused">
+        <Text text="Paused"/>
+    </If>
+    <Else>
+        <Container>
+            <Text text="Running"/>
+        </Container>
+    </Else>
+    <Switch value="{Resource GameState, Path=screen}">
+        <Node/>
+        <Case equals="Menu">
+            <Text text="Menu"/>
+        </Case>
+        <Case equals="Score"/>
+    </Switch>
+    <ItemTemplate source="{Resource Players, Path=online}">
+        <If test="{Item ready}">
+            <Text text="{Item name}"/>
+        </If>
+    </ItemTemplate>
+</Layout>"#;
+
+fn parse(content: &str) -> Result<XmlLayout, XmlLayoutError> {
+    LayoutReader::new(content, "layout.xml").parse()
+}
+
+fn value(node: &UiNode) -> &AttributeValue {
+    &node.condition.as_ref().unwrap().value
+}
+
+#[test]
+fn containers() {
+    let layout = parse(LAYOUT).unwrap();
+    let root = &layout.root_nodes[0];
+    assert_eq!(root.children.len(), 3);
+
+    let paused = &root.children[0];
+    assert_eq!(paused.tag.name, "If");
+    assert_eq!(paused.id, Id::Custom("Paused".into()));
+    assert_eq!(paused.components[0].name, "Text");
+    assert_eq!(paused.condition.as_ref().unwrap().test, ConditionTest::True);
+    assert!(matches!(value(paused), AttributeValue::Resource(_)));
+
+    let running = &root.children[1];
+    assert_eq!(running.tag.name, "Else");
+    assert_eq!(running.children.len(), 1);
+    assert_eq!(
+        running.condition,
+        Some(Condition {
+            value: value(paused).clone(),
+            test: ConditionTest::False,
+        })
+    );
+
+    let switch = &root.children[2];
+    assert_eq!(switch.tag.name, "Switch");
+    assert_eq!(switch.condition, None);
+    assert_eq!(switch.components[0].name, "Node");
+    let tests = switch
+        .children
+        .iter()
+        .map(|case| case.condition.as_ref().unwrap().test.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        tests,
+        [
+            ConditionTest::Equals("Menu".to_string()),
+            ConditionTest::Equals("Score".to_string()),
+        ]
+    );
+    assert!(switch.children[1].components.is_empty());
+
+    let template = &layout.templates[0];
+    assert_eq!(template.nodes[0].id, template.id);
+    assert!(matches!(value(&template.nodes[0]), AttributeValue::Item(_)));
+}
+
+#[test]
+fn write() {
+    let layout = parse(LAYOUT).unwrap();
+    let written = LayoutWriter::default().write(&layout);
+    assert!(written.contains(r#"<If test="{Resource GameState, Path=paused}" id="Paused">"#));
+    assert!(written.contains("<Else>"));
+    assert!(written.contains(r#"<Case equals="Score"/>"#));
+    assert!(written.contains(r#"<If test="{Item ready}">"#));
+
+    let reparsed = parse(&written).unwrap();
+    assert_eq!(LayoutWriter::default().write(&reparsed), written);
+}
+
+#[test]
+fn errors() {
+    let error = parse(r#"<Layout><If test="true"/></Layout>"#).unwrap_err();
+    assert!(matches!(error, XmlLayoutError::InvalidCondition { .. }));
+
+    let error = parse(r#"<Layout><If/></Layout>"#).unwrap_err();
+    assert!(matches!(
+        error,
+        XmlLayoutError::MissingAttribute {
+            attribute: "test",
+            ..
+        }
+    ));
+
+    let error = parse(r#"<Layout><Container/><Else/></Layout>"#).unwrap_err();
+    assert_eq!(
+        error.message(),
+        "<Else> must be placed directly after an <If>"
+    );
+
+    let error = parse(r#"<Layout><Case equals="Menu"/></Layout>"#).unwrap_err();
+    assert_eq!(error.message(), "<Case> must be placed inside a <Switch>");
+
+    let error = parse(
+        r#"<Layout><Switch value="{Resource State, Path=screen}"><Case equals="{Resource Menu, Path=name}"/></Switch></Layout>"#,
+    )
+    .unwrap_err();
+    assert!(matches!(error, XmlLayoutError::ExceptedValue { .. }));
+
+    let error = parse(r#"<Layout><If test="{Resource State, Path=paused}"></Container></Layout>"#)
+        .unwrap_err();
+    assert_eq!(
+        error.message(),
+        "Mismatched end tag. Expected If, but found Container"
+    );
+}
+
+#[test]
+fn recovered() {
+    let parsed = LayoutReader::new(
+        r#"<Layout>
+    <Else><Text text="Orphan"/></Else>
+    <If test="yes"/>
+    <Container id="Kept"></Container>
+</Layout>"#,
+        "layout.xml",
+    )
+    .parse_recovering();
+    assert_eq!(parsed.errors.len(), 2);
+
+    let root = &parsed.layout.root_nodes[0];
+    assert_eq!(root.children.len(), 3);
+    assert!(root.children.iter().all(|child| child.condition.is_none()));
+    assert_eq!(root.children[2].id, Id::Custom("Kept".into()));
+}
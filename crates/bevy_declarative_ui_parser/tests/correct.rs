@@ -5,6 +5,7 @@ use bevy_declarative_ui_parser::{
         bindings::{
             Binding, BindingKind, BindingMode,
             params::{AdditionalParams, BaseParams, ItemBaseParams},
+            path::BindingPath,
         },
     },
 };
@@ -46,7 +47,7 @@ fn test() {
         TemplateBinding::Resource(Binding {
             base_params: BaseParams {
                 target: "Players".into(),
-                path: "online".into(),
+                path: BindingPath::parse("online").unwrap(),
             },
             additional_params: (),
             kind: BindingKind::Resource,
@@ -62,7 +63,7 @@ fn test() {
         AttributeValue::Resource(Binding {
             base_params: BaseParams {
                 target: "Globals".into(),
-                path: "width".into(),
+                path: BindingPath::parse("width").unwrap(),
             },
             additional_params: AdditionalParams {
                 converter: None,
@@ -78,7 +79,7 @@ fn test() {
         "image",
         AttributeValue::Item(Binding {
             base_params: ItemBaseParams {
-                path: "avatar".into(),
+                path: BindingPath::parse("avatar").unwrap(),
             },
             additional_params: AdditionalParams {
                 converter: Some("AsSprite".into()),
@@ -0,0 +1,338 @@
+use bevy_declarative_ui_parser::errors::XmlLayoutError;
+use bevy_declarative_ui_parser::values::bindings::params::BaseParams;
+use bevy_declarative_ui_parser::values::bindings::path::BindingPath;
+use bevy_declarative_ui_parser::values::bindings::{Binding, BindingKind};
+use bevy_declarative_ui_parser::values::{AttributeValue, InterpolationPart, TemplateBinding};
+use bevy_declarative_ui_parser::{Id, LayoutReader, LayoutWriter, XmlLayout};
+
+mod utils;
+use utils::*;
+
+fn parse(content: &str) -> Result<XmlLayout, XmlLayoutError> {
+    LayoutReader::new(content, "layout.xml").parse()
+}
+
+fn value(value: &str) -> AttributeValue {
+    AttributeValue::Value(value.to_string())
+}
+
+#[test]
+fn instances() {
+    let layout = parse(&load("define.xml").0).unwrap();
+    let menu = &layout.root_nodes[0].children[0];
+    menu.has(1, 0, 2, Id::Custom("Menu".into()));
+
+    let play = &menu.children[0];
+    play.has(1, 2, 1, Id::Custom("Play".into()));
+    play.components[0].has("Button", 0);
+    play.components[1].has_attribute("image", value("ui/play.png"));
+    let label = &play.children[0];
+    label.has(0, 1, 0, Id::Default(0));
+    label.components[0].has_attribute("text", value("Play"));
+
+    let toolbar = &menu.children[1];
+    toolbar.has(0, 0, 2, Id::Default(1));
+    let pause = &toolbar.children[0];
+    pause.has(0, 2, 1, Id::Default(2));
+    pause.components[1].has_attribute("image", value("ui/pause.png"));
+    let AttributeValue::Resource(binding) = &pause.children[0].components[0].attributes[0].value
+    else {
+        panic!("label is not a binding");
+    };
+    assert_eq!(binding.base_params.target, "Labels");
+    assert_eq!(binding.base_params.path.to_string(), "pause");
+
+    let quit = &toolbar.children[1];
+    quit.has(0, 2, 1, Id::Default(4));
+    quit.children[0].components[0].has_attribute("text", value("Quit"));
+}
+
+#[test]
+fn scoped_ids() {
+    let layout = parse(
+        r#"<Layout>
+    <Define name="IconButton" params="icon">
+        <Button/>
+        <Container id="Icon">
+            <ImageNode image="{icon}"/>
+        </Container>
+    </Define>
+    <Container>
+        <IconButton id="Play" icon="play.png"/>
+        <IconButton icon="quit.png"/>
+    </Container>
+</Layout>"#,
+    )
+    .unwrap();
+    let buttons = &layout.root_nodes[0].children[0];
+
+    //Ids of the content are scoped by the instance id, or by the define and the instance number
+    let play = &buttons.children[0];
+    play.has(1, 1, 1, Id::Custom("Play".into()));
+    play.children[0].has(1, 1, 0, Id::Custom("Play.Icon".into()));
+    let quit = &buttons.children[1];
+    quit.has(0, 1, 1, Id::Default(1));
+    quit.children[0].has(1, 1, 0, Id::Custom("IconButton1.Icon".into()));
+}
+
+#[test]
+fn template() {
+    let layout = parse(
+        r#"<Layout>
+    <Define name="List" params="source">
+        <Node/>
+        <ItemTemplate source="{source}">
+            <Container>
+                <Text text="{Item name}"/>
+            </Container>
+        </ItemTemplate>
+    </Define>
+    <List id="Players" source="{Resource Players, Path=online}"/>
+    <List source="{Resource Players, Path=online}"/>
+</Layout>"#,
+    )
+    .unwrap();
+    let online = TemplateBinding::Resource(Binding {
+        base_params: BaseParams {
+            target: "Players".into(),
+            path: BindingPath::parse("online").unwrap(),
+        },
+        additional_params: (),
+        kind: BindingKind::Resource,
+    });
+    let root = &layout.root_nodes[0];
+    assert_eq!(layout.templates.len(), 2);
+    layout.templates[0].has(
+        Id::Template(0),
+        Id::Custom("Players".into()),
+        1,
+        online.clone(),
+    );
+    layout.templates[1].has(Id::Template(1), root.children[1].id.clone(), 1, online);
+}
+
+#[test]
+fn escaped_braces_and_other_names() {
+    let layout = parse(
+        r#"<Layout>
+    <Define name="Label" params="text">
+        <Text text="{{text}}: {text} {Resource Stats, Path=text}"/>
+    </Define>
+    <Label text="HP"/>
+</Layout>"#,
+    )
+    .unwrap();
+    let text = &layout.root_nodes[0].children[0].components[0].attributes[0];
+    let AttributeValue::Interpolated(parts) = &text.value else {
+        panic!("text is not interpolated");
+    };
+    assert!(matches!(&parts[0], InterpolationPart::Text(text) if text == "{text}: HP "));
+}
+
+#[test]
+fn used_before_declared() {
+    let layout = parse(
+        r#"<Layout>
+    <Container>
+        <Badge/>
+    </Container>
+    <Define name="Badge"><Node/></Define>
+</Layout>"#,
+    );
+    //The tree has started, `<Define>` is only read before it
+    assert!(matches!(
+        layout.unwrap_err(),
+        XmlLayoutError::UnexpectedTag { .. }
+    ));
+}
+
+#[test]
+fn write() {
+    let layout = parse(&load("define.xml").0).unwrap();
+    let written = LayoutWriter::default().write(&layout);
+    assert!(!written.contains("Define"));
+    assert!(written.contains(r#"<Container id="Play">"#));
+    assert!(written.contains(r#"<ImageNode image="ui/pause.png"/>"#));
+
+    let reparsed = parse(&written).unwrap();
+    assert_eq!(LayoutWriter::default().write(&reparsed), written);
+}
+
+#[test]
+fn format() {
+    let (content, file) = load("define_slots.xml");
+    let formatted = LayoutWriter::default().format(&content, &file).unwrap();
+    //Defines, slots and fills are kept
+    assert!(formatted.contains(r#"<Define name="Card" params="title">"#));
+    assert!(formatted.contains(r#"<Slot name="content"/>"#));
+    assert!(formatted.contains(r#"<Fill slot="content">"#));
+    //The expanded layout is the same
+    let writer = LayoutWriter::default();
+    assert_eq!(
+        writer.write(&parse(&formatted).unwrap()),
+        writer.write(&parse(&content).unwrap())
+    );
+}
+
+#[test]
+fn errors() {
+    let define = r#"<Define name="Badge" params="icon, text"><Node/></Define>"#;
+
+    let error = parse(&format!(
+        r#"<Layout>{define}<Badge icon="a.png"/></Layout>"#
+    ))
+    .unwrap_err();
+    assert_eq!(error.message(), "Missing parameter text of <Badge>");
+
+    let error = parse(&format!(
+        r#"<Layout>{define}<Badge icon="a.png" text="A" color="red"/></Layout>"#
+    ))
+    .unwrap_err();
+    assert_eq!(error.message(), "Unknown parameter color of <Badge>");
+
+    let error = parse(&format!(r#"<Layout>{define}{define}</Layout>"#)).unwrap_err();
+    assert_eq!(
+        error.message(),
+        "Invalid <Define> 'Badge': it is already defined"
+    );
+
+    let error = parse(r#"<Layout><Define name="Container"/></Layout>"#).unwrap_err();
+    assert_eq!(
+        error.message(),
+        "Invalid <Define> 'Container': it is a layout tag"
+    );
+
+    let error =
+        parse(r#"<Layout><Define name="Badge" params="icon, my-text"/></Layout>"#).unwrap_err();
+    assert!(matches!(error, XmlLayoutError::InvalidDefine { .. }));
+
+    let error = parse(r#"<Layout><Define/></Layout>"#).unwrap_err();
+    assert!(matches!(
+        error,
+        XmlLayoutError::MissingAttribute {
+            attribute: "name",
+            ..
+        }
+    ));
+}
+
+#[test]
+fn recovered() {
+    let parsed = LayoutReader::new(
+        r#"<Layout>
+    <Define name="Badge" params="icon"><ImageNode image="{icon}"/></Define>
+    <Define name="Container"><Node/></Define>
+    <Badge/>
+    <Badge icon="a.png"/>
+</Layout>"#,
+        "layout.xml",
+    )
+    .parse_recovering();
+    assert_eq!(parsed.errors.len(), 2);
+
+    let root = &parsed.layout.root_nodes[0];
+    assert_eq!(root.children.len(), 1);
+    root.children[0].components[0].has_attribute("image", value("a.png"));
+}
+
+#[test]
+fn slots() {
+    let layout = parse(&load("define_slots.xml").0).unwrap();
+    let cards = &layout.root_nodes[0].children[0];
+
+    //Fills of a slot follow each other, their content keeps its braces
+    let settings = &cards.children[0];
+    settings.has(1, 3, 2, Id::Custom("Settings".into()));
+    settings.components[2].has("Button", 0);
+    let volume = &settings.children[0];
+    volume.has(1, 1, 0, Id::Custom("Settings.Volume".into()));
+    volume.components[0].has_attribute("text", value("Volume {title}"));
+    let footer = &settings.children[1];
+    footer.has(1, 1, 0, Id::Custom("Settings.Footer".into()));
+    footer.components[0].has_attribute("text", value("Settings footer"));
+
+    //Unfilled slots are empty or keep their default content
+    let empty = &cards.children[1];
+    empty.has(0, 2, 1, Id::Default(1));
+    empty.children[0].has(1, 1, 0, Id::Custom("Card1.Footer".into()));
+    empty.children[0].components[0].has_attribute("text", value("Empty footer"));
+}
+
+#[test]
+fn filled_ids() {
+    let layout = parse(
+        r#"<Layout>
+    <Define name="Card">
+        <Slot name="content"/>
+    </Define>
+    <Container>
+        <Card id="First">
+            <Fill slot="content"><Container id="Body"><Node/></Container></Fill>
+        </Card>
+        <Card>
+            <Fill slot="content"><Container id="Body"><Node/></Container></Fill>
+        </Card>
+    </Container>
+</Layout>"#,
+    )
+    .unwrap();
+    let cards = &layout.root_nodes[0].children[0];
+
+    //The same fill content gets the ids of its instance
+    cards.children[0].children[0].has(1, 1, 0, Id::Custom("First.Body".into()));
+    cards.children[1].children[0].has(1, 1, 0, Id::Custom("Card1.Body".into()));
+}
+
+#[test]
+fn slot_errors() {
+    let define = r#"<Define name="Card"><Slot name="content"/></Define>"#;
+
+    let error = parse(&format!(
+        r#"<Layout>{define}<Card><Fill slot="title"><Node/></Fill></Card></Layout>"#
+    ))
+    .unwrap_err();
+    assert_eq!(error.message(), "Unknown slot title of <Card>");
+
+    let error = parse(&format!(r#"<Layout>{define}<Card><Node/></Card></Layout>"#)).unwrap_err();
+    assert!(matches!(error, XmlLayoutError::UnexpectedTag { .. }));
+
+    let error =
+        parse(r#"<Layout><Container><Slot name="content"/></Container></Layout>"#).unwrap_err();
+    assert_eq!(error.message(), "<Slot> must be placed inside a <Define>");
+
+    let error = parse(r#"<Layout><Fill slot="content"><Node/></Fill></Layout>"#).unwrap_err();
+    assert_eq!(
+        error.message(),
+        "<Fill> must be placed directly inside an element of a <Define>"
+    );
+
+    let error = parse(r#"<Layout><Define name="Card"><Slot/></Define></Layout>"#).unwrap_err();
+    assert!(matches!(
+        error,
+        XmlLayoutError::MissingAttribute {
+            attribute: "name",
+            ..
+        }
+    ));
+}
+
+#[test]
+fn slots_recovered() {
+    let parsed = LayoutReader::new(
+        r#"<Layout>
+    <Define name="Card"><Slot name="content"/></Define>
+    <Card>
+        <Node/>
+        <Fill slot="title"><Button/></Fill>
+        <Fill slot="content"><ImageNode/></Fill>
+    </Card>
+</Layout>"#,
+        "layout.xml",
+    )
+    .parse_recovering();
+    assert_eq!(parsed.errors.len(), 2);
+
+    let card = &parsed.layout.root_nodes[0].children[0];
+    card.has(0, 1, 0, Id::Default(0));
+    card.components[0].has("ImageNode", 0);
+}
@@ -0,0 +1,107 @@
+use bevy_declarative_ui_parser::LayoutReader;
+use bevy_declarative_ui_parser::errors::XmlLayoutError;
+
+fn parse_error(content: &str) -> XmlLayoutError {
+    let error = LayoutReader::new(content, "inline.xml")
+        .parse()
+        .unwrap_err();
+    println!("{error}");
+    error
+}
+
+#[test]
+fn code_frame() {
+    let error = parse_error(concat!(
+        "<Layout>\n",
+        r#"<Text text="{Resource Score, Path=a..b}"/></Layout>"#
+    ));
+    assert_eq!(error.code(), "E0306");
+    assert_eq!(
+        error.to_string(),
+        concat!(
+            "error[E0306]: Invalid path: Expected field name or tuple index\n",
+            " --> inline.xml:2:37\n",
+            "  |\n",
+            "2 | a..b\n",
+            "  |   ^\n",
+        )
+    );
+}
+
+#[test]
+fn duplicates() {
+    let error = parse_error(concat!(
+        "<Layout>\n",
+        r#"<Text text="{Resource Score, Path=a, Path=b}"/></Layout>"#
+    ));
+    assert_eq!(
+        error.to_string(),
+        concat!(
+            "error[E0304]: Parameter 'Path' specified more than once\n",
+            " --> inline.xml:2:30\n",
+            "  |\n",
+            "2 | {Resource Score, Path=a, Path=b}\n",
+            "  |                  ^^^^    ---- specified again here\n",
+            "  = help: Remove all but one of them\n",
+        )
+    );
+
+    let diagnostic = error.diagnostic();
+    assert_eq!(diagnostic.spans.len(), 2);
+    assert!(diagnostic.spans[0].primary);
+    assert_eq!(
+        (diagnostic.spans[1].line, diagnostic.spans[1].column),
+        (2, 38)
+    );
+    assert_eq!(
+        diagnostic.to_json(),
+        concat!(
+            r#"{"code":"E0304","message":"Parameter 'Path' specified more than once","#,
+            r#""file":"inline.xml","line":2,"column":30,"#,
+            r#""source":"{Resource Score, Path=a, Path=b}","spans":["#,
+            r#"{"start":17,"length":4,"line":2,"column":30,"primary":true,"message":null},"#,
+            r#"{"start":25,"length":4,"line":2,"column":38,"primary":false,"message":"specified again here"}"#,
+            r#"],"help":"Remove all but one of them"}"#,
+        )
+    );
+}
+
+#[test]
+fn multi_line_duplicates() {
+    let error = parse_error(concat!(
+        "<Layout>\n",
+        "<Text text=\"{Resource Score,\n",
+        "    Path=a,\n",
+        "    Path=b\n",
+        "}\"/></Layout>",
+    ));
+    assert_eq!(
+        error.to_string(),
+        concat!(
+            "error[E0304]: Parameter 'Path' specified more than once\n",
+            " --> inline.xml:3:5\n",
+            "  |\n",
+            "2 | {Resource Score,\n",
+            "3 |     Path=a,\n",
+            "  |     ^^^^\n",
+            "4 |     Path=b\n",
+            "  |     ---- specified again here\n",
+            "5 | }\n",
+            "  = help: Remove all but one of them\n",
+        )
+    );
+}
+
+#[test]
+fn without_location() {
+    let error = parse_error("<Text/>");
+    assert_eq!(
+        error.to_string(),
+        concat!(
+            "error[E0200]: Missing <Layout> tag\n",
+            " --> inline.xml\n",
+            "  = help: Put the content into <Layout>...</Layout>\n",
+        )
+    );
+    assert_eq!(error.diagnostic().line, None);
+}
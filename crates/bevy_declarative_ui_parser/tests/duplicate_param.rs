@@ -10,7 +10,7 @@ fn single_line() {
     let (content, file) = load("single_line_duplicate_param.xml");
     let result = LayoutReader::new(&content, &file).parse();
     assert_eq!(result.err().unwrap(), XmlLayoutError::DuplicateParam {
-        context: Duplicates::new(
+        context: Box::new(Duplicates::new(
             file,
             Location::new(8, 2, 18),
             "{Component Enemy, Target=PlayerEnemy, Target=Camera, Mode=Read, Fallback=100px, Converter=Round, Target=Target}".into(),
@@ -20,7 +20,7 @@ fn single_line() {
                 SimpleErrorSpan::new(97, 6),
                 SimpleErrorSpan::new(11, 5),
             ]
-        ),
+        )),
         name: "Target".to_string(),
     });
 }
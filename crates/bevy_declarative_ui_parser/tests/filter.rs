@@ -0,0 +1,79 @@
+mod utils;
+
+use bevy_declarative_ui_parser::LayoutReader;
+use bevy_declarative_ui_parser::errors::{ErrorContext, XmlLayoutError};
+use bevy_declarative_ui_parser::position::{ErrorSpan, Location};
+use bevy_declarative_ui_parser::values::AttributeValue;
+use bevy_declarative_ui_parser::values::bindings::filter::{Filter, Filters};
+use utils::*;
+
+fn filters(value: &AttributeValue) -> Vec<Filter> {
+    match value {
+        AttributeValue::Component(binding) => {
+            binding.additional_params.filters.iter().cloned().collect()
+        }
+        other => panic!("Expected component binding, found {other:?}"),
+    }
+}
+
+#[test]
+fn parse() {
+    let (content, file) = load("filter.xml");
+    let layout = LayoutReader::new(&content, &file).parse();
+    if let Err(error) = layout {
+        panic!("{}", error);
+    }
+    let layout = layout.unwrap();
+
+    let container = &layout.root_nodes[0].children[0];
+    let attributes = container
+        .components
+        .iter()
+        .map(|component| &component.attributes[0].value)
+        .collect::<Vec<_>>();
+
+    assert_eq!(filters(attributes[0]), vec![Filter::With("Player".into())]);
+    assert_eq!(
+        filters(attributes[1]),
+        vec![
+            Filter::With("Player".into()),
+            Filter::Without("Enemy".into()),
+            Filter::Changed("Name".into()),
+        ]
+    );
+    assert_eq!(
+        filters(attributes[2]),
+        vec![Filter::Or(vec![
+            Filter::Added("Tint".into()),
+            Filter::Changed("Health".into()),
+        ])]
+    );
+}
+
+#[test]
+fn filter_bundle() {
+    let filters = Filters::parse("{Player, Or(!Enemy, Added<Health>)}").unwrap();
+    assert_eq!(
+        filters.to_filter_bundle(),
+        "(With<Player>, Or<(Without<Enemy>, Added<Health>)>)"
+    );
+    assert!(Filters::parse("").unwrap().is_empty());
+}
+
+#[test]
+fn invalid() {
+    let (content, file) = load("invalid_filter.xml");
+    let result = LayoutReader::new(&content, &file).parse();
+    let error = result.err().unwrap();
+    assert_eq!(
+        error,
+        XmlLayoutError::InvalidFilter {
+            context: ErrorContext::new(
+                file,
+                Location::new(8, 2, 72),
+                ErrorSpan::new("Changed<Health".into(), 14, 1),
+            ),
+            message: "Expected '>'".into(),
+        }
+    );
+}
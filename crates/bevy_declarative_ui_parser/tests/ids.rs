@@ -0,0 +1,79 @@
+use bevy_declarative_ui_parser::{Id, LayoutReader, UiNode, XmlLayout};
+
+const LAYOUT: &str = r#"
+<Layout>
+    <Container>
+        <Node/>
+        <Container id="List">
+            <Node/>
+        </Container>
+        <ItemTemplate source="{Resource Players, Path=online}">
+            <Container>
+                <Container>
+                    <Text text="{Item name}"/>
+                </Container>
+            </Container>
+        </ItemTemplate>
+    </Container>
+    <ItemTemplate source="{Resource Players, Path=offline}">
+        <Container>
+            <Text text="{Item name}"/>
+        </Container>
+    </ItemTemplate>
+    <Container/>
+</Layout>
+"#;
+
+fn parse(content: &str) -> XmlLayout {
+    LayoutReader::new(content, "ids.xml").parse().unwrap()
+}
+
+fn ids(node: &UiNode, ids: &mut Vec<Id>) {
+    ids.push(node.id.clone());
+    node.children.iter().for_each(|child| self::ids(child, ids));
+}
+
+fn all_ids(layout: &XmlLayout) -> Vec<Id> {
+    let mut result = vec![];
+    layout
+        .root_nodes
+        .iter()
+        .chain(layout.templates.iter().flat_map(|template| &template.nodes))
+        .for_each(|node| ids(node, &mut result));
+    result
+}
+
+/// The first container of a template takes the template id
+#[test]
+fn numbered_in_document_order() {
+    let layout = parse(LAYOUT);
+
+    assert_eq!(
+        all_ids(&layout),
+        vec![
+            Id::Custom("Root".into()),
+            Id::Default(0),
+            Id::Custom("List".into()),
+            Id::Template(0),
+            Id::Default(2),
+            Id::Template(1),
+        ]
+    );
+}
+
+#[test]
+fn independent_of_parse_order() {
+    let first = parse(LAYOUT);
+    parse(r#"<Layout><Container><Container/></Container></Layout>"#);
+    let second = parse(LAYOUT);
+
+    assert_eq!(all_ids(&first), all_ids(&second));
+    let templates = |layout: &XmlLayout| -> Vec<Id> {
+        layout
+            .templates
+            .iter()
+            .map(|template| template.id.clone())
+            .collect()
+    };
+    assert_eq!(templates(&first), templates(&second));
+}
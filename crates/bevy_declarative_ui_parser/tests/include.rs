@@ -0,0 +1,305 @@
+use bevy_declarative_ui_parser::errors::XmlLayoutError;
+use bevy_declarative_ui_parser::values::bindings::params::BaseParams;
+use bevy_declarative_ui_parser::values::bindings::path::BindingPath;
+use bevy_declarative_ui_parser::values::bindings::{Binding, BindingKind};
+use bevy_declarative_ui_parser::values::{AttributeValue, TemplateBinding};
+use bevy_declarative_ui_parser::{
+    Id, IncludedFiles, LayoutProperty, LayoutReader, LayoutWriter, XmlLayout, include_path,
+};
+use std::collections::HashMap;
+
+mod utils;
+use utils::*;
+
+/// Layout of `tests/assets/include`
+fn asset(path: &str) -> String {
+    load(&format!("include/{path}")).0
+}
+
+fn resolve_files(file: &str, files: &[(&str, &str)]) -> Result<XmlLayout, XmlLayoutError> {
+    let files = files.iter().copied().collect::<HashMap<_, _>>();
+    let layout = LayoutReader::new(files[file], file).parse()?;
+    IncludedFiles::new(file, &layout)
+        .read_with(|path| {
+            files
+                .get(path)
+                .map(|content| content.to_string())
+                .ok_or_else(|| "not found".to_string())
+        })
+        .resolve(file, layout)
+}
+
+#[test]
+fn paths() {
+    assert_eq!(
+        include_path("ui/main.xml", "widgets/header.xml"),
+        "ui/widgets/header.xml"
+    );
+    assert_eq!(include_path("ui/main.xml", "./header.xml"), "ui/header.xml");
+    assert_eq!(
+        include_path("ui/widgets/header.xml", "../close.xml"),
+        "ui/close.xml"
+    );
+    assert_eq!(include_path("main.xml", "../close.xml"), "../close.xml");
+    assert_eq!(
+        include_path("/assets/ui/main.xml", "header.xml"),
+        "/assets/ui/header.xml"
+    );
+}
+
+#[test]
+fn unresolved() {
+    let main = asset("ui/main.xml");
+    let layout = LayoutReader::new(&main, "ui/main.xml").parse().unwrap();
+    assert_eq!(layout.includes.len(), 2);
+    assert_eq!(layout.includes[0].path, "widgets/header.xml");
+    assert_eq!(layout.includes[0].node, Id::Custom("Header".into()));
+    assert_eq!(
+        layout.includes[0].overrides,
+        vec![("Title".to_string(), "Inventory".to_string())]
+    );
+
+    let inventory = &layout.root_nodes[0].children[0];
+    assert_eq!(inventory.children[0].tag.name, "Include");
+    assert!(
+        LayoutWriter::default()
+            .write(&layout)
+            .contains(r#"<Include path="widgets/header.xml" id="Header" Title="Inventory"/>"#)
+    );
+}
+
+#[test]
+fn resolve() {
+    let (main, header, close) = (
+        asset("ui/main.xml"),
+        asset("ui/widgets/header.xml"),
+        asset("ui/icons/close.xml"),
+    );
+    let layout = resolve_files(
+        "ui/main.xml",
+        &[
+            ("ui/main.xml", &main),
+            ("ui/widgets/header.xml", &header),
+            ("ui/icons/close.xml", &close),
+        ],
+    )
+    .unwrap();
+    assert!(layout.includes.is_empty());
+    assert!(layout.usings.contains("game::Stats"));
+
+    let root = &layout.root_nodes[0];
+    let inventory = &root.children[0];
+    let header = &inventory.children[0];
+    header.has(1, 1, 2, Id::Custom("Header".into()));
+    header.components[0].has_attribute("text", AttributeValue::Property("Title".into()));
+    header.children[1].components[0].has("Button", 0);
+    assert_eq!(inventory.children[1].id, Id::Default(0));
+
+    //Both includes get their own ids after the ones of the including layout
+    let second = &root.children[1];
+    second.has(0, 1, 2, Id::Default(1));
+    let mut ids = vec![
+        &header.children[0].id,
+        &header.children[1].id,
+        &second.children[0].id,
+        &second.children[1].id,
+    ];
+    ids.sort_by_key(|id| id.to_string());
+    ids.dedup();
+    assert_eq!(ids.len(), 4);
+
+    assert_eq!(layout.templates.len(), 2);
+    assert_ne!(layout.templates[0].id, layout.templates[1].id);
+    let online = TemplateBinding::Resource(Binding {
+        base_params: BaseParams {
+            target: "Players".into(),
+            path: BindingPath::parse("online").unwrap(),
+        },
+        additional_params: (),
+        kind: BindingKind::Resource,
+    });
+    let template = &layout.templates[0];
+    template.has(
+        template.id.clone(),
+        header.children[0].id.clone(),
+        1,
+        online.clone(),
+    );
+    assert_eq!(template.nodes[0].id, template.id);
+    let template = &layout.templates[1];
+    template.has(
+        template.id.clone(),
+        second.children[0].id.clone(),
+        1,
+        online,
+    );
+
+    //The first include sets the property
+    assert_eq!(
+        layout.properties,
+        vec![LayoutProperty {
+            name: "Title".to_string(),
+            r#type: "String".to_string(),
+            value: "Inventory".to_string(),
+            global: false,
+        }]
+    );
+}
+
+#[test]
+fn scoped_ids() {
+    let main = r#"<Layout>
+    <Container id="Icon"><Node/></Container>
+    <Include path="icon.xml" id="First"/>
+    <Include path="icon.xml"/>
+    <Include path="row.xml" id="Row"/>
+</Layout>"#;
+    let icon = r#"<Layout>
+    <Container id="Icon">
+        <ItemTemplate source="{Resource Players, Path=online}">
+            <Container>
+                <Text text="{Item name}"/>
+            </Container>
+        </ItemTemplate>
+    </Container>
+</Layout>"#;
+    let row = r#"<Layout>
+    <Include path="icon.xml" id="Left"/>
+    <Include path="icon.xml"/>
+</Layout>"#;
+    let layout = resolve_files(
+        "main.xml",
+        &[("main.xml", main), ("icon.xml", icon), ("row.xml", row)],
+    )
+    .unwrap();
+
+    //Custom ids of an included layout are scoped by the id of its `<Include>`
+    let root = &layout.root_nodes[0];
+    assert_eq!(root.children[0].id, Id::Custom("Icon".into()));
+    let first = &root.children[1];
+    assert_eq!(first.children[0].id, Id::Custom("First.Icon".into()));
+    let second = &root.children[2];
+    assert!(matches!(second.id, Id::Default(_)));
+    assert_eq!(
+        second.children[0].id,
+        Id::Custom(format!("{}.Icon", second.id))
+    );
+
+    //Nested includes are scoped by the final ids of their elements
+    let row = &root.children[3];
+    let left = &row.children[0];
+    assert_eq!(left.id, Id::Custom("Row.Left".into()));
+    assert_eq!(left.children[0].id, Id::Custom("Row.Left.Icon".into()));
+    let unnamed = &row.children[1];
+    assert_eq!(
+        unnamed.children[0].id,
+        Id::Custom(format!("{}.Icon", unnamed.id))
+    );
+
+    let owners = layout
+        .templates
+        .iter()
+        .map(|template| template.owner.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(owners.len(), 4);
+    assert!(owners.contains(&"First.Icon".to_string()));
+    assert!(owners.contains(&"Row.Left.Icon".to_string()));
+    let mut ids = owners.clone();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), 4);
+}
+
+#[test]
+fn cycle() {
+    let a = r#"<Layout>
+    <Include path="b.xml"/>
+</Layout>"#;
+    let b = r#"<Layout>
+    <Container>
+        <Include path="a.xml"/>
+    </Container>
+</Layout>"#;
+    let error = resolve_files("a.xml", &[("a.xml", a), ("b.xml", b)]).unwrap_err();
+    let XmlLayoutError::InIncludedLayout { file, error, .. } = &error else {
+        panic!("Expected an error of the included layout, found {error:?}");
+    };
+    assert_eq!(file, "a.xml");
+    match error.as_ref() {
+        XmlLayoutError::IncludeCycle { context, cycle } => {
+            assert_eq!(context.file, "b.xml");
+            assert_eq!(context.location.line(), 3);
+            assert_eq!(cycle, &["a.xml", "b.xml", "a.xml"]);
+        }
+        other => panic!("Expected an include cycle, found {other:?}"),
+    }
+
+    let itself = r#"<Layout>
+    <Include path="./a.xml"/>
+</Layout>"#;
+    let error = resolve_files("a.xml", &[("a.xml", itself)]).unwrap_err();
+    assert!(matches!(error, XmlLayoutError::IncludeCycle { .. }));
+}
+
+#[test]
+fn errors_name_both_files() {
+    let main = r#"<Layout>
+    <Include path="header.xml"/>
+</Layout>"#;
+    let header = r#"<Layout>
+    <Text text="{Resource Title, Path=text, Mode=Sideways}"/>
+</Layout>"#;
+    let error =
+        resolve_files("main.xml", &[("main.xml", main), ("header.xml", header)]).unwrap_err();
+    assert_eq!(error.code(), "E0302");
+    assert_eq!(error.context().unwrap().file, "header.xml");
+    assert!(error.message().ends_with("(included from main.xml:2:6)"));
+    let diagnostic = error.diagnostic();
+    assert_eq!(diagnostic.file.as_deref(), Some("header.xml"));
+    assert_eq!(diagnostic.message, error.message());
+
+    let error = resolve_files("main.xml", &[("main.xml", main)]).unwrap_err();
+    match error {
+        XmlLayoutError::UnreadableInclude {
+            context,
+            path,
+            message,
+        } => {
+            assert_eq!(context.file, "main.xml");
+            assert_eq!(path, "header.xml");
+            assert_eq!(message, "not found");
+        }
+        other => panic!("Expected an unreadable include, found {other:?}"),
+    }
+}
+
+#[test]
+fn unknown_property() {
+    let main = r#"<Layout>
+    <Include path="close.xml" Title="Close"/>
+</Layout>"#;
+    let close = asset("ui/icons/close.xml");
+    let error =
+        resolve_files("main.xml", &[("main.xml", main), ("close.xml", &close)]).unwrap_err();
+    match error {
+        XmlLayoutError::UnknownIncludeProperty { path, name, .. } => {
+            assert_eq!(path, "close.xml");
+            assert_eq!(name, "Title");
+        }
+        other => panic!("Expected an unknown property, found {other:?}"),
+    }
+}
+
+#[test]
+fn missing_path() {
+    let error = LayoutReader::new(r#"<Layout><Include id="Header"/></Layout>"#, "main.xml")
+        .parse()
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        XmlLayoutError::MissingAttribute {
+            attribute: "path",
+            ..
+        }
+    ));
+}
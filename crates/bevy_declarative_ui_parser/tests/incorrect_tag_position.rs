@@ -1,10 +1,19 @@
 mod utils;
 
-use bevy_declarative_ui_parser::LayoutReader;
+use bevy_declarative_ui_parser::{LayoutReader, XmlLayoutError};
 use utils::*;
 
 #[test]
 fn test() {
     let (content, file) = load("incorrect_tag_position.xml");
     let layout = LayoutReader::new(&content, &file).parse();
+    match layout.unwrap_err() {
+        XmlLayoutError::UnexpectedTag {
+            current, expected, ..
+        } => {
+            assert_eq!(current, "ImageNode");
+            assert_eq!(expected, vec!["Container"]);
+        }
+        other => panic!("Expected unexpected tag, found {other:?}"),
+    }
 }
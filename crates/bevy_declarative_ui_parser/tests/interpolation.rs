@@ -0,0 +1,73 @@
+mod utils;
+
+use bevy_declarative_ui_parser::LayoutReader;
+use bevy_declarative_ui_parser::errors::{ErrorContext, XmlLayoutError};
+use bevy_declarative_ui_parser::position::{ErrorSpan, Location};
+use bevy_declarative_ui_parser::values::{AttributeValue, InterpolationPart};
+use utils::*;
+
+fn parts(value: &AttributeValue) -> &[InterpolationPart] {
+    match value {
+        AttributeValue::Interpolated(parts) => parts,
+        other => panic!("Expected interpolated value, found {other:?}"),
+    }
+}
+
+fn path(part: &InterpolationPart) -> String {
+    match part {
+        InterpolationPart::Binding(AttributeValue::Resource(binding)) => {
+            binding.base_params.path.to_string()
+        }
+        InterpolationPart::Binding(AttributeValue::Component(binding)) => {
+            binding.base_params.path.to_string()
+        }
+        other => panic!("Expected binding, found {other:?}"),
+    }
+}
+
+#[test]
+fn parse() {
+    let (content, file) = load("interpolation.xml");
+    let layout = LayoutReader::new(&content, &file).parse();
+    if let Err(error) = layout {
+        panic!("{}", error);
+    }
+    let layout = layout.unwrap();
+
+    let container = &layout.root_nodes[0].children[0];
+    let score = parts(&container.components[0].attributes[0].value);
+    assert_eq!(score.len(), 4);
+    assert_eq!(score[0], InterpolationPart::Text("Score: ".into()));
+    assert_eq!(path(&score[1]), "current");
+    assert_eq!(score[2], InterpolationPart::Text(" / ".into()));
+    assert_eq!(path(&score[3]), "max");
+
+    let health = parts(&container.components[1].attributes[0].value);
+    assert_eq!(health.len(), 3);
+    assert_eq!(health[0], InterpolationPart::Text("{literal} ".into()));
+    assert_eq!(path(&health[1]), "value");
+    assert_eq!(health[2], InterpolationPart::Text("%".into()));
+
+    assert!(matches!(
+        container.components[2].attributes[0].value,
+        AttributeValue::Resource(_)
+    ));
+}
+
+#[test]
+fn invalid() {
+    let (content, file) = load("invalid_interpolation.xml");
+    let result = LayoutReader::new(&content, &file).parse();
+    let error = result.err().unwrap();
+    assert_eq!(
+        error,
+        XmlLayoutError::InvalidInterpolation {
+            context: ErrorContext::new(
+                file,
+                Location::new(8, 2, 24),
+                ErrorSpan::new("Score: {Resource Score, Path=current".into(), 7, 1),
+            ),
+            message: "Unclosed '{'".into(),
+        }
+    );
+}
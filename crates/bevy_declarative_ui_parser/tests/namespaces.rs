@@ -0,0 +1,106 @@
+mod utils;
+
+use bevy_declarative_ui_parser::Id;
+use bevy_declarative_ui_parser::LayoutReader;
+use bevy_declarative_ui_parser::errors::{ErrorContext, XmlLayoutError};
+use bevy_declarative_ui_parser::position::{ErrorSpan, Location};
+use bevy_declarative_ui_parser::values::AttributeValue;
+use utils::*;
+
+#[test]
+fn resolve() {
+    let (content, file) = load("namespaces.xml");
+    let layout = LayoutReader::new(&content, &file).parse();
+    if let Err(error) = layout {
+        panic!("{}", error);
+    }
+    let layout = layout.unwrap();
+
+    let stats = &layout.root_nodes[0].children[0];
+    stats.has(1, 3, 0, Id::Custom("Stats".into()));
+    assert_eq!(stats.tag.namespace.as_deref(), Some("bevy"));
+
+    let node = &stats.components[0];
+    node.has("Node", 1);
+    assert_eq!(node.namespace.as_deref(), Some("bevy"));
+
+    let game = &stats.components[1];
+    game.has("HealthBar", 2);
+    game.has_attribute("value", AttributeValue::Value("10".into()));
+    game.has_attribute("max", AttributeValue::Value("20".into()));
+    assert_eq!(game.namespace.as_deref(), Some("game"));
+
+    let third_party = &stats.components[2];
+    //`xml:lang` is not addressed to the component
+    third_party.has("HealthBar", 0);
+    assert_eq!(third_party.namespace.as_deref(), Some("third_party"));
+
+    let plain = &layout.root_nodes[0].children[1];
+    plain.has(1, 1, 0, Id::Custom("Plain".into()));
+    assert_eq!(plain.components[0].namespace, None);
+}
+
+#[test]
+fn unbound_prefix() {
+    let (content, file) = load("unbound_prefix.xml");
+    let result = LayoutReader::new(&content, &file).parse();
+    let error = result.err().unwrap();
+    println!("{error}");
+    assert_eq!(
+        error,
+        XmlLayoutError::UnboundPrefix {
+            context: ErrorContext::new(
+                file,
+                Location::new(101, 6, 10),
+                ErrorSpan::new("ui:HealthBar".into(), 0, 2),
+            ),
+            prefix: "ui".into(),
+        }
+    );
+}
+
+#[test]
+fn out_of_scope_prefix() {
+    let content = r#"<Layout><Container xmlns:ui="ui"/><ui:Text/></Layout>"#;
+    let result = LayoutReader::new(content, "inline.xml").parse();
+    assert!(matches!(
+        result.err().unwrap(),
+        XmlLayoutError::UnboundPrefix { .. }
+    ));
+}
+
+#[test]
+fn prefixed_structural_tag_is_a_component() {
+    let content = r#"<Layout xmlns:game="game"><Container><game:Container/></Container></Layout>"#;
+    let layout = LayoutReader::new(content, "inline.xml").parse().unwrap();
+    let container = &layout.root_nodes[0].children[0];
+    container.components[0].has("Container", 0);
+    assert_eq!(container.components[0].namespace.as_deref(), Some("game"));
+}
+
+#[test]
+fn end_tag_prefix_must_match() {
+    let content = r#"<Layout xmlns:game="game"><Container></game:Container></Layout>"#;
+    match LayoutReader::new(content, "inline.xml")
+        .parse()
+        .err()
+        .unwrap()
+    {
+        XmlLayoutError::MismatchedEndTag {
+            current, expected, ..
+        } => {
+            assert_eq!(current, "game:Container");
+            assert_eq!(expected, "Container");
+        }
+        other => panic!("Expected mismatched end tag, found {other:?}"),
+    }
+}
+
+#[test]
+fn prefixed_attribute_of_other_namespace() {
+    let content = r#"<Layout xmlns:game="game" xmlns:ui="ui"><Container><game:Bar ui:value="1" value="2"/></Container></Layout>"#;
+    let layout = LayoutReader::new(content, "inline.xml").parse().unwrap();
+    let bar = &layout.root_nodes[0].children[0].components[0];
+    bar.has("Bar", 1);
+    bar.has_attribute("value", AttributeValue::Value("2".into()));
+}
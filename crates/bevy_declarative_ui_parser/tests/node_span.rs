@@ -0,0 +1,53 @@
+#![cfg(feature = "node_span")]
+
+use bevy_declarative_ui_parser::LayoutReader;
+
+const LAYOUT: &str = "<Layout>
+    <Container id=\"Panel\">
+        <Node width=\"10px\"/>
+    </Container>
+</Layout>";
+
+#[test]
+fn positions() {
+    let layout = LayoutReader::new(LAYOUT, "layout.xml").parse().unwrap();
+    let root = &layout.root_nodes[0];
+    let panel = &root.children[0];
+
+    let location = panel.tag.span.location();
+    assert_eq!((location.line(), location.column()), (2, 6));
+    let source = &LAYOUT[panel.span.span().start()..panel.span.span().end()];
+    assert!(source.starts_with("<Container"));
+    assert!(source.ends_with("</Container>"));
+
+    let node = &panel.components[0];
+    let location = node.span.location();
+    assert_eq!((location.line(), location.column()), (3, 10));
+
+    let width = &node.attributes[0];
+    let name = width.name_span.location();
+    assert_eq!((name.line(), name.column()), (3, 15));
+    let value = width.value_span.location();
+    assert_eq!((value.line(), value.column()), (3, 22));
+    assert_eq!(
+        &LAYOUT[width.value_span.span().start()..width.value_span.span().end()],
+        "10px"
+    );
+}
+
+#[test]
+fn filled_instance() {
+    let content = "<Layout>
+    <Define name=\"Card\"><Slot name=\"content\"/></Define>
+    <Card id=\"Panel\">
+        <Fill slot=\"content\"><Node/></Fill>
+    </Card>
+</Layout>";
+    let layout = LayoutReader::new(content, "layout.xml").parse().unwrap();
+    let panel = &layout.root_nodes[0].children[0];
+
+    //The instance spans from its start tag to its end tag
+    let source = &content[panel.span.span().start()..panel.span.span().end()];
+    assert!(source.starts_with("<Card id=\"Panel\">"));
+    assert!(source.ends_with("</Card>"));
+}
@@ -0,0 +1,138 @@
+mod utils;
+
+use bevy_declarative_ui_parser::LayoutReader;
+use bevy_declarative_ui_parser::errors::{ErrorContext, XmlLayoutError};
+use bevy_declarative_ui_parser::position::{ErrorSpan, Location, Span};
+use bevy_declarative_ui_parser::values::AttributeValue;
+use bevy_declarative_ui_parser::values::bindings::path::{BindingPath, PathAccess, PathSegment};
+use utils::*;
+
+fn path(value: &AttributeValue) -> &BindingPath {
+    match value {
+        AttributeValue::Resource(binding) => &binding.base_params.path,
+        AttributeValue::Component(binding) => &binding.base_params.path,
+        other => panic!("Expected resource or component binding, found {other:?}"),
+    }
+}
+
+#[test]
+fn parse() {
+    let (content, file) = load("path.xml");
+    let layout = LayoutReader::new(&content, &file).parse();
+    if let Err(error) = layout {
+        panic!("{}", error);
+    }
+    let layout = layout.unwrap();
+
+    let container = &layout.root_nodes[0].children[0];
+    let players = path(&container.components[0].attributes[0].value);
+    assert_eq!(
+        players.segments,
+        vec![
+            PathSegment {
+                access: PathAccess::Field("players".into()),
+                optional: false,
+                span: Span::new(0, 7),
+            },
+            PathSegment {
+                access: PathAccess::Index(0),
+                optional: false,
+                span: Span::new(7, 10),
+            },
+            PathSegment {
+                access: PathAccess::Field("health".into()),
+                optional: true,
+                span: Span::new(11, 18),
+            },
+            PathSegment {
+                access: PathAccess::TupleIndex(0),
+                optional: false,
+                span: Span::new(19, 20),
+            },
+        ]
+    );
+
+    let sword = path(&container.components[1].attributes[0].value);
+    assert_eq!(sword.to_string(), r#"items["sword"]?.name"#);
+}
+
+#[test]
+fn to_rust() {
+    let path = BindingPath::parse("stats.0").unwrap();
+    assert_eq!(path.to_rust("target"), "Some(&target.stats.0)");
+
+    let path = BindingPath::parse(r#"players[1].name?.first["key \"quoted\""]"#).unwrap();
+    assert_eq!(
+        path.to_rust("target"),
+        r#"(|| Some(&(*(*(*target.players.get(1)?).name.as_ref()?).first.get("key \"quoted\"")?)))()"#
+    );
+
+    assert!(BindingPath::parse("").unwrap().is_empty());
+    assert_eq!(
+        BindingPath::parse("").unwrap().to_rust("target"),
+        "Some(&target)"
+    );
+}
+
+#[test]
+fn to_rust_mut() {
+    let path = BindingPath::parse("stats.0").unwrap();
+    assert_eq!(path.to_rust_mut("target"), "Some(&mut target.stats.0)");
+
+    let path = BindingPath::parse(r#"players[1].name?.first["key"]"#).unwrap();
+    assert_eq!(
+        path.to_rust_mut("target"),
+        concat!(
+            "Some(&mut target.players).and_then(|value| value.get_mut(1))",
+            ".map(|value| &mut value.name).and_then(|value| value.as_mut())",
+            ".map(|value| &mut value.first).and_then(|value| value.get_mut(\"key\"))",
+        )
+    );
+}
+
+#[test]
+fn parse_errors() {
+    let message = |input: &str| BindingPath::parse(input).unwrap_err().message;
+    assert_eq!(message("a..b"), "Expected field name or tuple index");
+    assert_eq!(message("a b"), "Expected '.' or '['");
+    assert_eq!(message("a[x]"), "Expected index or string key");
+    assert_eq!(message("a['key"), "Unterminated string key");
+    assert_eq!(message("get_text()"), "Expected '.' or '['");
+}
+
+#[test]
+fn invalid() {
+    let (content, file) = load("invalid_path.xml");
+    let result = LayoutReader::new(&content, &file).parse();
+    let error = result.err().unwrap();
+    assert_eq!(
+        error,
+        XmlLayoutError::InvalidPath {
+            context: ErrorContext::new(
+                file,
+                Location::new(8, 2, 49),
+                ErrorSpan::new("players[0.name".into(), 9, 1),
+            ),
+            message: "Expected ']'".into(),
+        }
+    );
+}
+
+#[test]
+fn quoted_params() {
+    let content = r#"<Layout><Container>
+        <Node width="{Resource Stats, Path=sizes['(, x'], Fallback=<5px, Mode=Read}"/>
+    </Container></Layout>"#;
+    let layout = LayoutReader::new(content, "inline.xml").parse().unwrap();
+
+    let value = &layout.root_nodes[0].children[0].components[0].attributes[0].value;
+    assert_eq!(path(value).to_string(), r#"sizes["(, x"]"#);
+    let AttributeValue::Resource(binding) = value else {
+        panic!("Expected resource binding, found {value:?}");
+    };
+    assert_eq!(binding.additional_params.fallback.as_deref(), Some("<5px"));
+    assert_eq!(
+        binding.additional_params.mode,
+        bevy_declarative_ui_parser::values::bindings::BindingMode::Read
+    );
+}
@@ -0,0 +1,44 @@
+mod utils;
+
+use bevy_declarative_ui_parser::LayoutReader;
+use bevy_declarative_ui_parser::errors::XmlLayoutError;
+use bevy_declarative_ui_parser::values::AttributeValue;
+use utils::*;
+
+#[test]
+fn skip() {
+    let (content, file) = load("prolog.xml");
+    let layout = LayoutReader::new(&content, &file).parse();
+    if let Err(error) = layout {
+        panic!("{}", error);
+    }
+    let layout = layout.unwrap();
+
+    assert_eq!(layout.usings.len(), 1);
+    assert!(layout.usings.contains("game::ui::*"));
+    assert_eq!(layout.root_nodes.len(), 1);
+
+    let labels = &layout.root_nodes[0].children[0];
+    assert_eq!(labels.components.len(), 1);
+    labels.components[0].has_attribute("text", AttributeValue::Value("Ready".into()));
+}
+
+#[test]
+fn unterminated_cdata() {
+    let content = r#"<Layout><Use><![CDATA[game::*]]</Use></Layout>"#;
+    let result = LayoutReader::new(content, "inline.xml").parse();
+    assert!(matches!(
+        result.err().unwrap(),
+        XmlLayoutError::EndOfFile { .. }
+    ));
+}
+
+#[test]
+fn unterminated_instruction() {
+    let content = r#"<?xml version="1.0"<Layout></Layout>"#;
+    let result = LayoutReader::new(content, "inline.xml").parse();
+    assert!(matches!(
+        result.err().unwrap(),
+        XmlLayoutError::EndOfFile { .. }
+    ));
+}
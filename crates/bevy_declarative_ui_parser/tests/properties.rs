@@ -0,0 +1,67 @@
+use bevy_declarative_ui_parser::values::AttributeValue;
+use bevy_declarative_ui_parser::{LayoutProperty, LayoutReader, XmlLayoutError};
+
+const LAYOUT: &str = r#"<Layout>
+    <GlobalResources>
+        <Property name="Count" type="u32" value="1000"/>
+    </GlobalResources>
+    <LocalResources>
+        <!-- Overrides the global value -->
+        <Property name="Count" type="u32" value="100"/>
+    </LocalResources>
+    <Counter value="{Count}"/>
+</Layout>"#;
+
+#[test]
+fn parse() {
+    let layout = LayoutReader::new(LAYOUT, "inline.xml").parse().unwrap();
+    assert_eq!(
+        layout.properties,
+        vec![
+            LayoutProperty {
+                name: "Count".to_string(),
+                r#type: "u32".to_string(),
+                value: "1000".to_string(),
+                global: true,
+            },
+            LayoutProperty {
+                name: "Count".to_string(),
+                r#type: "u32".to_string(),
+                value: "100".to_string(),
+                global: false,
+            },
+        ]
+    );
+
+    let counter = &layout.root_nodes[0].components[0];
+    assert_eq!(
+        counter.attributes[0].value,
+        AttributeValue::Property("Count".to_string())
+    );
+}
+
+#[test]
+fn missing_type() {
+    let layout = r#"<Layout>
+    <LocalResources>
+        <Property name="Count" value="100"/>
+    </LocalResources>
+</Layout>"#;
+    match LayoutReader::new(layout, "inline.xml").parse().unwrap_err() {
+        XmlLayoutError::MissingAttribute { attribute, .. } => assert_eq!(attribute, "type"),
+        other => panic!("Expected missing attribute, found {other:?}"),
+    }
+}
+
+#[test]
+fn unexpected_tag() {
+    let layout = r#"<Layout>
+    <GlobalResources>
+        <Counter value="1"/>
+    </GlobalResources>
+</Layout>"#;
+    match LayoutReader::new(layout, "inline.xml").parse().unwrap_err() {
+        XmlLayoutError::UnexpectedTag { expected, .. } => assert_eq!(expected, vec!["Property"]),
+        other => panic!("Expected unexpected tag, found {other:?}"),
+    }
+}
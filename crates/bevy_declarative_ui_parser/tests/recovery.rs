@@ -0,0 +1,68 @@
+mod utils;
+
+use bevy_declarative_ui_parser::LayoutReader;
+use bevy_declarative_ui_parser::errors::XmlLayoutError;
+use bevy_declarative_ui_parser::values::AttributeValue;
+use utils::*;
+
+#[test]
+fn collect_all_errors() {
+    let (content, file) = load("recovery.xml");
+    let parsed = LayoutReader::new(&content, &file).parse_recovering();
+    parsed.errors.iter().for_each(|error| println!("{error}"));
+
+    assert!(!parsed.is_ok());
+    assert!(matches!(
+        parsed.errors.as_slice(),
+        [
+            XmlLayoutError::DuplicateParam { .. },
+            XmlLayoutError::UnexpectedTag { .. },
+            XmlLayoutError::InvalidPath { .. },
+            XmlLayoutError::ExceptedValue { .. },
+            XmlLayoutError::EmptyAttribute { .. },
+            XmlLayoutError::InvalidFilter { .. },
+        ]
+    ));
+
+    let layout = &parsed.layout;
+    assert!(layout.usings.contains("game::ui::*"));
+    assert!(layout.templates.is_empty());
+
+    let root = &layout.root_nodes[0];
+    assert_eq!(root.children.len(), 2);
+
+    let stats = &root.children[0];
+    assert_eq!(stats.components.len(), 2);
+    stats.components[0].has("Node", 1);
+    stats.components[0].has_attribute("height", AttributeValue::Value("100%".into()));
+    stats.components[1].has("Text", 0);
+
+    let second = &root.children[1];
+    assert_eq!(second.components.len(), 1);
+    second.components[0].has("Node", 0);
+}
+
+#[test]
+fn parse_stops_at_first_error() {
+    let (content, file) = load("recovery.xml");
+    let error = LayoutReader::new(&content, &file).parse().unwrap_err();
+    assert!(matches!(error, XmlLayoutError::DuplicateParam { .. }));
+
+    let parsed = LayoutReader::new(&content, &file).parse_recovering();
+    assert_eq!(parsed.into_result().unwrap_err(), error);
+}
+
+#[test]
+fn keep_partial_tree_after_syntax_error() {
+    let content = r#"<Layout><Container id="Open"><Panel/><Text text="a"/><Node"#;
+    let parsed = LayoutReader::new(content, "inline.xml").parse_recovering();
+
+    assert!(matches!(
+        parsed.errors.as_slice(),
+        [XmlLayoutError::EndOfFile { .. }]
+    ));
+    let root = &parsed.layout.root_nodes[0];
+    let open = &root.children[0];
+    open.components[0].has("Panel", 0);
+    open.components[1].has("Text", 1);
+}
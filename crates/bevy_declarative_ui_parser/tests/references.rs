@@ -0,0 +1,103 @@
+mod utils;
+
+use bevy_declarative_ui_parser::LayoutReader;
+use bevy_declarative_ui_parser::errors::{ErrorContext, XmlLayoutError};
+use bevy_declarative_ui_parser::position::{ErrorSpan, Location};
+use bevy_declarative_ui_parser::values::{AttributeValue, InterpolationPart};
+use utils::*;
+
+#[test]
+fn decode() {
+    let (content, file) = load("references.xml");
+    let layout = LayoutReader::new(&content, &file).parse();
+    if let Err(error) = layout {
+        panic!("{}", error);
+    }
+    let layout = layout.unwrap();
+
+    assert!(layout.usings.contains("game::ui::*"));
+
+    let text = &layout.root_nodes[0].children[0].components[0];
+    text.has_attribute(
+        "text",
+        AttributeValue::Value("Tom & Jerry <3 \u{2764}A\"'>".into()),
+    );
+}
+
+#[test]
+fn invalid() {
+    let (content, file) = load("invalid_reference.xml");
+    let result = LayoutReader::new(&content, &file).parse();
+    let error = result.err().unwrap();
+    assert_eq!(
+        error,
+        XmlLayoutError::InvalidReference {
+            context: ErrorContext::new(
+                file,
+                Location::new(8, 2, 22),
+                ErrorSpan::new("&chips;".into(), 0, 7),
+            ),
+            reference: "&chips;".into(),
+        }
+    );
+}
+
+#[test]
+fn unterminated() {
+    let content = r#"<Layout><Text text="a &amp b"/></Layout>"#;
+    let result = LayoutReader::new(content, "inline.xml").parse();
+    match result.err().unwrap() {
+        XmlLayoutError::InvalidReference { reference, .. } => assert_eq!(reference, "&amp"),
+        other => panic!("Expected invalid reference, found {other:?}"),
+    }
+}
+
+fn resource_target(part: &InterpolationPart) -> &str {
+    match part {
+        InterpolationPart::Binding(AttributeValue::Resource(binding)) => {
+            &binding.base_params.target
+        }
+        other => panic!("Expected resource binding, found {other:?}"),
+    }
+}
+
+#[test]
+fn binding_after_reference() {
+    let content = r#"<Layout><Text text="&amp;{Resource Globals, Path=x}"/></Layout>"#;
+    let layout = LayoutReader::new(content, "inline.xml").parse().unwrap();
+    match &layout.root_nodes[0].components[0].attributes[0].value {
+        AttributeValue::Interpolated(parts) => {
+            assert_eq!(parts[0], InterpolationPart::Text("&".into()));
+            assert_eq!(resource_target(&parts[1]), "Globals");
+        }
+        other => panic!("Expected interpolated value, found {other:?}"),
+    }
+}
+
+#[test]
+fn error_column_after_reference() {
+    let column = |value: &str| {
+        let content = format!(r#"<Layout><Text text="{value}"/></Layout>"#);
+        let error = LayoutReader::new(&content, "inline.xml")
+            .parse()
+            .err()
+            .unwrap();
+        assert!(matches!(error, XmlLayoutError::UnknownBindingMode { .. }));
+        error.context().unwrap().location.column()
+    };
+
+    let plain = column("{Resource Globals, Path=x, Mode=Sideways}");
+    let decoded = column("&amp;{Resource Globals, Path=x, Mode=Sideways}");
+    assert_eq!(decoded, plain + "&amp;".len());
+}
+
+#[test]
+fn binding_on_multiline_tag() {
+    let content =
+        "<Layout>\n    <Text\n        text=\"&lt;{Resource Sizes, Path=height}\"/>\n</Layout>";
+    let layout = LayoutReader::new(content, "inline.xml").parse().unwrap();
+    match &layout.root_nodes[0].components[0].attributes[0].value {
+        AttributeValue::Interpolated(parts) => assert_eq!(resource_target(&parts[1]), "Sizes"),
+        other => panic!("Expected interpolated value, found {other:?}"),
+    }
+}
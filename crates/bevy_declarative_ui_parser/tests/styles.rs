@@ -0,0 +1,149 @@
+use bevy_declarative_ui_parser::errors::XmlLayoutError;
+use bevy_declarative_ui_parser::values::AttributeValue;
+use bevy_declarative_ui_parser::{IncludedFiles, LayoutReader, LayoutWriter, XmlLayout};
+
+mod utils;
+use utils::*;
+
+const LAYOUT: &str = r#"<Layout>
+    <Styles>
+        <Style class="panel">
+            <Node width="100%" padding="10px"/>
+            <BackgroundColor color="Gray"/>
+        </Style>
+        <Style class="primary">
+            <BackgroundColor color="Blue"/>
+            <Node padding="5px"/>
+        </Style>
+    </Styles>
+    <Container id="Menu" class="panel primary">
+        <Node width="50%"/>
+        <Text text="Menu"/>
+    </Container>
+    <ItemTemplate source="{Resource Players, Path=online}">
+        <Container class="panel">
+            <Text text="{Item name}"/>
+        </Container>
+    </ItemTemplate>
+</Layout>"#;
+
+fn parse(content: &str) -> Result<XmlLayout, XmlLayoutError> {
+    LayoutReader::new(content, "layout.xml").parse()
+}
+
+fn value(value: &str) -> AttributeValue {
+    AttributeValue::Value(value.to_string())
+}
+
+#[test]
+fn styled_components() {
+    let layout = parse(LAYOUT).unwrap();
+    assert_eq!(layout.styles.len(), 2);
+    assert_eq!(layout.styles[0].class, "panel");
+    assert_eq!(layout.styles[0].components.len(), 2);
+
+    let menu = &layout.root_nodes[0].children[0];
+    assert_eq!(menu.classes().collect::<Vec<_>>(), ["panel", "primary"]);
+    let components = menu.styled_components(&layout.styles);
+    assert_eq!(components.len(), 3);
+
+    //Components of the classes come first, a later class wins
+    components[0].has("BackgroundColor", 1);
+    components[0].has_attribute("color", value("Blue"));
+
+    //The inline attribute wins over the classes
+    let node = &components[1];
+    node.has("Node", 2);
+    node.has_attribute("width", value("50%"));
+    node.has_attribute("padding", value("5px"));
+    components[2].has("Text", 1);
+}
+
+#[test]
+fn apply_styles() {
+    let mut layout = parse(LAYOUT).unwrap();
+    layout.apply_styles();
+    assert!(layout.styles.is_empty());
+
+    let menu = &layout.root_nodes[0].children[0];
+    menu.has(
+        1,
+        3,
+        0,
+        bevy_declarative_ui_parser::Id::Custom("Menu".into()),
+    );
+
+    let item = &layout.templates[0].nodes[0];
+    assert_eq!(item.components.len(), 3);
+    item.components[0].has_attribute("padding", value("10px"));
+}
+
+#[test]
+fn write() {
+    let layout = parse(LAYOUT).unwrap();
+    let written = LayoutWriter::default().write(&layout);
+    assert!(written.contains(
+        r#"    <Styles>
+        <Style class="panel">
+            <Node width="100%" padding="10px"/>"#
+    ));
+    assert!(written.contains(r#"<Container id="Menu" class="panel primary">"#));
+
+    let reparsed = parse(&written).unwrap();
+    assert_eq!(reparsed.styles, layout.styles);
+    assert_eq!(LayoutWriter::default().write(&reparsed), written);
+}
+
+#[test]
+fn included_styles() {
+    let main = r#"<Layout>
+    <Styles>
+        <Style class="panel"><Node width="10px"/></Style>
+    </Styles>
+    <Container class="panel">
+        <Include path="card.xml"/>
+    </Container>
+</Layout>"#;
+    let card = r#"<Layout>
+    <Styles>
+        <Style class="panel"><Node width="20px"/></Style>
+    </Styles>
+    <Container class="panel">
+        <Text text="Card"/>
+    </Container>
+</Layout>"#;
+
+    let layout = parse(main).unwrap();
+    let layout = IncludedFiles::new("main.xml", &layout)
+        .read_with(|_| Ok(card.to_string()))
+        .resolve("main.xml", layout)
+        .unwrap();
+
+    //The classes of the included file are resolved with its own styles
+    let container = &layout.root_nodes[0].children[0];
+    container.styled_components(&layout.styles)[0].has_attribute("width", value("10px"));
+    let included = &container.children[0].children[0];
+    assert_eq!(included.classes().count(), 0);
+    included.components[0].has_attribute("width", value("20px"));
+}
+
+#[test]
+fn errors() {
+    let error = parse(r#"<Layout><Container class="panel"></Container></Layout>"#).unwrap_err();
+    assert_eq!(error.message(), "Unknown class panel");
+
+    let error =
+        parse(r#"<Layout><Styles><Style class="panel"/><Style class="panel"/></Styles></Layout>"#)
+            .unwrap_err();
+    assert_eq!(
+        error.message(),
+        "Invalid <Style> 'panel': it is already declared"
+    );
+
+    let error =
+        parse(r#"<Layout><Node/><Styles><Style class="panel"/></Styles></Layout>"#).unwrap_err();
+    assert!(matches!(error, XmlLayoutError::UnexpectedTag { .. }));
+
+    let error = parse(r#"<Layout><Styles><Node/></Styles></Layout>"#).unwrap_err();
+    assert!(matches!(error, XmlLayoutError::UnexpectedTag { .. }));
+}
@@ -49,3 +49,14 @@ fn component() {
         }
     );
 }
+
+#[test]
+fn unclosed_comment() {
+    let content = "<Layout>\n    <!-- never closed\n</Layout>";
+    let error = LayoutReader::new(content, "inline.xml").parse().unwrap_err();
+
+    println!("{error}");
+    assert!(matches!(error, XmlLayoutError::UnclosedComment { .. }));
+    let location = error.context().unwrap().location;
+    assert_eq!((location.line(), location.column()), (2, 5));
+}
@@ -0,0 +1,88 @@
+use bevy_declarative_ui_parser::errors::XmlLayoutError;
+use bevy_declarative_ui_parser::values::AttributeValue;
+use bevy_declarative_ui_parser::{Id, LayoutReader};
+
+mod utils;
+use utils::*;
+
+#[test]
+fn names_and_values() {
+    let content = r#"<Layout xmlns:ü="spiel::widgets">
+    <Use>spiel::größe</Use>
+    <Container id="Übersicht">
+        <ü:Schaltfläche beschriftung="Größe: 10 – ✓" wert="&#x2764; ❤"/>
+    </Container>
+</Layout>"#;
+    let layout = LayoutReader::new(content, "layout.xml").parse().unwrap();
+
+    assert!(layout.usings.contains("spiel::größe"));
+    let button = &layout.root_nodes[0].children[0].components[0];
+    button.has("Schaltfläche", 2);
+    assert_eq!(button.namespace.as_deref(), Some("spiel::widgets"));
+    button.has_attribute(
+        "beschriftung",
+        AttributeValue::Value("Größe: 10 – ✓".into()),
+    );
+    button.has_attribute("wert", AttributeValue::Value("❤ ❤".into()));
+}
+
+#[test]
+fn columns_after_multibyte_characters() {
+    //Columns count characters, the spans count bytes
+    let content = "<Layout>\n    <Container>\n        <Text text=\"✓\" value=\"{Resource Globals, Path=x, Mode=Sideways}\"/>\n    </Container>\n</Layout>";
+    let error = LayoutReader::new(content, "layout.xml")
+        .parse()
+        .err()
+        .unwrap();
+    assert!(matches!(error, XmlLayoutError::UnknownBindingMode { .. }));
+    let location = error.context().unwrap().location;
+    let line = content.lines().nth(2).unwrap();
+    assert_eq!(location.line(), 3);
+    let before = &line[..line.find("Sideways").unwrap()];
+    assert_eq!(location.column(), before.chars().count() + 1);
+    assert_eq!(error.context().unwrap().error.source(), "Sideways");
+}
+
+#[test]
+fn lexer_error_after_multibyte_characters() {
+    let content = "<Layout>\n    <Container id=\"Größe\" ✓/>\n</Layout>";
+    let error = LayoutReader::new(content, "layout.xml")
+        .parse()
+        .err()
+        .unwrap();
+    let location = error.context().unwrap().location;
+    let line = content.lines().nth(1).unwrap();
+    let before = &line[..line.find('✓').unwrap()];
+    assert_eq!(location.line(), 2);
+    assert_eq!(location.column(), before.chars().count() + 1);
+}
+
+#[test]
+fn invalid_text_character() {
+    let content = "<Layout>\n    <Use>spiel::\u{FFFE}</Use>\n</Layout>";
+    let error = LayoutReader::new(content, "layout.xml")
+        .parse()
+        .err()
+        .unwrap();
+    match error {
+        XmlLayoutError::InvalidChar { char, .. } => assert_eq!(char, '\u{FFFE}'),
+        other => panic!("Expected invalid character, found {other:?}"),
+    }
+}
+
+#[test]
+fn single_quoted_values() {
+    let content = r#"<Layout>
+    <Container id='Menu'>
+        <Text text='http://a > b "quoted"'/>
+    </Container>
+</Layout>"#;
+    let layout = LayoutReader::new(content, "layout.xml").parse().unwrap();
+
+    let menu = &layout.root_nodes[0].children[0];
+    menu.has(1, 1, 0, Id::Custom("Menu".into()));
+    menu.components[0].has_attribute(
+        "text",
+        AttributeValue::Value(r#"http://a > b "quoted""#.into()),
+    );
+}
@@ -1,4 +1,3 @@
-use bevy_declarative_ui_parser::attribute::Attribute;
 use bevy_declarative_ui_parser::into::Tag;
 use bevy_declarative_ui_parser::values::{AttributeValue, TemplateBinding};
 use bevy_declarative_ui_parser::{Id, ItemTemplate, UiNode};
@@ -36,10 +35,11 @@ impl ComponentAssert for Tag {
     }
 
     fn has_attribute(&self, name: &'static str, value: AttributeValue) {
-        assert!(self.attributes.contains(&Attribute {
-            name: name.to_string(),
-            value,
-        }));
+        assert!(
+            self.attributes
+                .iter()
+                .any(|attribute| attribute.name == name && attribute.value == value)
+        );
     }
 }
 
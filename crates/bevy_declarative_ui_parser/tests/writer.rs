@@ -0,0 +1,152 @@
+use bevy_declarative_ui_parser::{LayoutReader, LayoutWriter};
+use std::path::{Path, PathBuf};
+
+mod utils;
+use utils::*;
+
+fn write(content: &str) -> String {
+    let layout = LayoutReader::new(content, "layout.xml").parse().unwrap();
+    LayoutWriter::default().write(&layout)
+}
+
+fn format(content: &str) -> String {
+    LayoutWriter::default()
+        .format(content, "layout.xml")
+        .unwrap()
+}
+
+#[test]
+fn canonical() {
+    let written = write(
+        r#"<Layout xmlns:g="game::widgets">
+        <Use>game::Stats</Use>
+        <GlobalResources><Property name="Max" type="f32" value="10"/></GlobalResources>
+        <Container id="Panel"><ItemTemplate source="{Resource Players,Path=online}"><Container>
+            <Text text="{Item name , Mode=Read}"/></Container></ItemTemplate>
+            <Node width="{Resource Target=Globals, Path=width, Fallback=10px, Mode=ReadOnce}"/>
+            <Text text="{{HP}}: {Component Player, Path=hp, Filter={!Enemy, Changed<Health>}} &amp; more"/>
+            <g:Bar value="{Max}"/>
+        </Container>
+        </Layout>"#,
+    );
+
+    assert_eq!(
+        written,
+        r#"<Layout xmlns:g="game::widgets">
+    <Use>game::Stats</Use>
+    <GlobalResources>
+        <Property name="Max" type="f32" value="10"/>
+    </GlobalResources>
+    <Container id="Panel">
+        <Node width="{Resource Globals, Path=width, Fallback=10px}"/>
+        <Text text="{{HP}}: {Component Player, Path=hp, Filter={!Enemy, Changed&lt;Health>}} &amp; more"/>
+        <g:Bar value="{Max}"/>
+        <ItemTemplate source="{Resource Players, Path=online}">
+            <Container>
+                <Text text="{Item name, Mode=Read}"/>
+            </Container>
+        </ItemTemplate>
+    </Container>
+</Layout>
+"#
+    );
+}
+
+#[test]
+fn invented_prefixes() {
+    let written = write(
+        r#"<Layout xmlns="game::widgets" xmlns:widgets="other">
+        <Container><Bar/><widgets:Label/></Container>
+        </Layout>"#,
+    );
+    //The default namespace has no prefix, its invented one can not take a source prefix
+    assert!(
+        written.starts_with(r#"<Layout xmlns:widgets="other" xmlns:widgets1="game::widgets">"#)
+    );
+    assert!(written.contains("<widgets1:Bar/>"));
+    assert!(written.contains("<widgets:Label/>"));
+}
+
+#[test]
+fn attribute_line_breaks() {
+    let content = r#"<Layout><Container><Text text="a&#10;b&#9;c&#13;"/></Container></Layout>"#;
+    let written = write(content);
+    assert!(written.contains(r#"<Text text="a&#10;b&#9;c&#13;"/>"#));
+    assert_eq!(write(&written), written);
+    assert!(format(content).contains(r#"<Text text="a&#10;b&#9;c&#13;"/>"#));
+}
+
+#[test]
+fn write_is_stable() {
+    let (content, _) = load("correct.xml");
+    let written = write(&content);
+    assert_eq!(write(&written), written);
+
+    let (content, _) = load("interpolation.xml");
+    let written = write(&content);
+    assert_eq!(write(&written), written);
+}
+
+#[test]
+fn format_keeps_comments_and_attribute_order() {
+    let formatted = format(
+        r#"<?xml version="1.0"?>
+<!-- Score screen -->
+<Layout xmlns:g="game"   xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="game layout.xsd">
+<Use>  game::Stats </Use>
+  <Container   id="Score"><!--title-->
+<Text text="Score: {Resource Stats, Path=score}"   id_hint="&#x41;"/>
+        <g:Bar value="5" max="10" /></Container>
+</Layout>"#,
+    );
+
+    assert_eq!(
+        formatted,
+        r#"<?xml version="1.0"?>
+<!-- Score screen -->
+<Layout xmlns:g="game" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="game layout.xsd">
+    <Use>game::Stats</Use>
+    <Container id="Score">
+        <!--title-->
+        <Text text="Score: {Resource Stats, Path=score}" id_hint="A"/>
+        <g:Bar value="5" max="10"/>
+    </Container>
+</Layout>
+"#
+    );
+    assert_eq!(format(&formatted), formatted);
+}
+
+#[test]
+fn format_rejects_invalid_layouts() {
+    let result = LayoutWriter::default().format("<Layout><Container></Layout>", "layout.xml");
+    assert!(result.is_err());
+}
+
+#[test]
+fn format_is_idempotent() {
+    fn files(dir: &Path, found: &mut Vec<PathBuf>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                files(&path, found);
+            } else {
+                found.push(path);
+            }
+        }
+    }
+
+    let mut found = vec![];
+    files(Path::new("tests/assets"), &mut found);
+    let mut formatted_files = 0;
+    for path in found {
+        let content = std::fs::read_to_string(&path).unwrap();
+        //Assets of the error tests are not valid layouts
+        let Ok(formatted) = LayoutWriter::default().format(&content, "layout.xml") else {
+            continue;
+        };
+        assert_eq!(format(&formatted), formatted, "{}", path.display());
+        formatted_files += 1;
+    }
+    assert!(formatted_files > 10);
+}
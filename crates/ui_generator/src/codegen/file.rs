@@ -1,9 +1,9 @@
+use crate::codegen::Function;
 use crate::codegen::module::Module;
 use crate::codegen::structs::Struct;
 use crate::codegen::using::Using;
-use crate::codegen::Function;
 use itertools::Itertools;
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 use std::io::{Read, Seek, Write};
 use std::process::Command;
 use tempfile::NamedTempFile;
@@ -13,7 +13,7 @@ pub struct RustFile {
     modules: Vec<Module>,
     structs: Vec<Struct>,
     functions: Vec<Function>,
-    usings: HashSet<Using>,
+    usings: BTreeSet<Using>,
 }
 
 impl RustFile {
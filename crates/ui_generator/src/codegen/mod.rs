@@ -117,6 +117,10 @@ impl Function {
         }
     }
 
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     pub const fn access(&mut self, access: Access) -> &mut Self {
         self.access = access;
         self
@@ -138,23 +142,21 @@ impl Function {
         self
     }
 
-    pub fn query_ref_arg(
+    /// `Query<{data}, {filters}>`, `data` is written as is, e.g. `Ref<Player>`
+    pub fn query_arg(
         &mut self,
         arg_name: impl Into<String>,
-        arg_type: impl Into<String>,
+        data: &str,
         filters: &Filters,
+        mutable: bool,
     ) -> &mut Self {
         let arg_type = if filters.is_empty() {
-            format!("Query<&{0}>", arg_type.into())
+            format!("Query<{data}>")
         } else {
-            format!(
-                "Query<&{0}, {1}>",
-                arg_type.into(),
-                filters.to_filter_bundle()
-            )
+            format!("Query<{data}, {}>", filters.to_filter_bundle())
         };
 
-        let argument = Argument::new(arg_name, arg_type, false, Ownership::Move);
+        let argument = Argument::new(arg_name, arg_type, mutable, Ownership::Move);
         self.args.push(argument);
         self
     }
@@ -247,23 +249,6 @@ impl Function {
         self
     }
 
-    pub fn single_mut_arg(
-        &mut self,
-        arg_name: impl Into<String>,
-        arg_type: &str,
-        filters: &Filters,
-    ) -> &mut Self {
-        let arg_type = if filters.is_empty() {
-            format!("Single<&mut {arg_type}>")
-        } else {
-            let bundle = filters.to_filter_bundle();
-            format!("Single<&mut {arg_type}, {bundle}>")
-        };
-        let argument = Argument::new(arg_name, arg_type, true, Ownership::Move);
-        self.args.push(argument);
-        self
-    }
-
     pub fn resource_arg(
         &mut self,
         res_type: impl Into<String>,
@@ -282,19 +267,20 @@ impl Function {
         self
     }
 
-    pub fn resource_mut(
+    /// `Option<Res<T>>` or `Option<ResMut<T>>`, the resource may not exist yet
+    pub fn optional_resource_arg(
         &mut self,
-        res_name: impl Into<String>,
+        res_type: impl Into<String>,
         arg_name: impl Into<String>,
+        mutable: bool,
     ) -> &mut Self {
-        let arg_type = format!("ResMut<{}>", res_name.into());
-        let argument = Argument {
-            name: arg_name.into(),
-            arg_type,
-            mutable: true,
-            ownership: Ownership::Move,
+        let res_type = res_type.into();
+        let arg_type = if mutable {
+            format!("Option<ResMut<{res_type}>>")
+        } else {
+            format!("Option<Res<{res_type}>>")
         };
-
+        let argument = Argument::new(arg_name, arg_type, false, Ownership::Move);
         self.args.push(argument);
         self
     }
@@ -26,6 +26,10 @@ impl Module {
         }
     }
 
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     pub const fn access(&mut self, access: Access) -> &mut Self {
         self.access = access;
         self
@@ -1,4 +1,4 @@
-#[derive(Eq, Hash, PartialEq)]
+#[derive(Eq, Hash, PartialEq, Ord, PartialOrd)]
 pub struct Using {
     path: String,
 }
@@ -1,5 +1,4 @@
 use bevy_declarative_ui_parser::UiNode;
-use bevy_declarative_ui_parser::values::AttributeValue;
 use core::panic;
 use std::{collections::HashMap, str::FromStr};
 
@@ -1,4 +1,4 @@
-use bevy_declarative_ui_parser::{LayoutReader, UiNode, XmlLayout, values::AttributeValue};
+use bevy_declarative_ui_parser::{IncludedFiles, LayoutReader, UiNode, XmlLayout};
 use std::fs::{self, File};
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -14,6 +14,10 @@ mod module;
 mod r#static;
 mod utils;
 
+pub use r#static::{
+    GeneratorError, StaticLayout, generate_static_layout, generate_static_layout_with,
+};
+
 fn collect_xml_files(dir: &Path, files: &mut Vec<PathBuf>) {
     if dir.is_dir() {
         for entry in fs::read_dir(dir).unwrap() {
@@ -51,26 +55,32 @@ pub fn generate_modules(assets_dir: PathBuf, output_dir: &str) -> Vec<PathBuf> {
         };
 
         let content = fs::read_to_string(&path).unwrap();
-        let mut reader = LayoutReader::new(&content, path.to_str().unwrap());
-        let result = reader.parse();
-        if result.is_err() {
-            panic!("{}", result.unwrap_err());
-            //continue;
+        let file = path.to_str().unwrap();
+        let mut reader = LayoutReader::new(&content, file);
+        let result = reader.parse().and_then(|layout| {
+            IncludedFiles::new(file, &layout)
+                .read_with(|included| {
+                    fs::read_to_string(included).map_err(|error| error.to_string())
+                })
+                .resolve(file, layout)
+        });
+        match result {
+            Ok(layout) => generate_module(layout, &module, &module_dir),
+            Err(error) => panic!("{error}"),
         }
-        generate_module(result.unwrap(), &module, &module_dir);
     }
 
     xml_files
 }
 
-fn generate_module(layout: XmlLayout, module: &Module, module_dir: &PathBuf) {
+fn generate_module(layout: XmlLayout, _module: &Module, module_dir: &Path) {
     let mut output: String = String::new();
     output.push_str("use bevy::ecs::system::*;");
     output.push_str("use bevy_declarative_ui::prelude::*;");
     output.push_str("use bevy::prelude::{Mut, App, Plugin};");
     output.push_str(&join_usings(&layout.usings));
 
-    let mut function_registrations =
+    let function_registrations =
         generate_function_registrations(&layout.root_nodes, &mut output, &String::new());
     let binding_registrations = generate_binding_registration(&layout.root_nodes);
 
@@ -103,7 +113,7 @@ fn generate_module(layout: XmlLayout, module: &Module, module_dir: &PathBuf) {
 fn generate_binding_registration(nodes: &Vec<UiNode>) -> String {
     let mut output: String = String::new();
     nodes.iter().for_each(|node| {
-        node.tag.attributes.iter().for_each(|attribute| {
+        node.tag.attributes.iter().for_each(|_attribute| {
             //TODO fix
             //match &attribute.value {
             //    AttributeValue::Binding(value) => {
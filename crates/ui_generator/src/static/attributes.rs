@@ -0,0 +1,434 @@
+//! Fields of the built-in components, mirrors the parsers of `bevy_declarative_ui::base`
+//! so literals can be checked while the layout is generated
+
+use bevy_declarative_ui_parser::into::Tag;
+
+/// Kind of the value stored in a field, decides how a literal is parsed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ValueKind {
+    Val,
+    UiRect,
+    F32,
+    /// `Option<f32>`, a literal is always `Some`
+    OptionF32,
+    Bool,
+    Color,
+    LineHeight,
+    String,
+    /// Unit enum, the literal is the name of the variant
+    Enum(&'static [&'static str]),
+    /// Asset path, loaded with the asset server
+    Asset,
+}
+
+pub(crate) struct KnownComponent {
+    pub name: &'static str,
+    pub path: &'static str,
+    /// Attribute name, field of the component, value kind
+    pub attributes: &'static [(&'static str, &'static str, ValueKind)],
+}
+
+/// Attribute resolved to a field of the component
+pub(crate) struct Field {
+    /// Place expression relative to the component, e.g. `overflow.x`
+    pub place: String,
+    /// `None` for the components which are not built in, their literals are parsed at runtime
+    pub kind: Option<ValueKind>,
+}
+
+const DISPLAY: &[&str] = &["Flex", "None", "Block", "Grid"];
+const BOX_SIZING: &[&str] = &["BorderBox", "ContentBox"];
+const POSITION_TYPE: &[&str] = &["Absolute", "Relative"];
+const OVERFLOW_AXIS: &[&str] = &["Visible", "Hidden", "Scroll", "Clip"];
+const OVERFLOW_CLIP_BOX: &[&str] = &["ContentBox", "PaddingBox", "BorderBox"];
+const ALIGN_ITEMS: &[&str] = &[
+    "Default",
+    "Start",
+    "End",
+    "FlexStart",
+    "FlexEnd",
+    "Center",
+    "Baseline",
+    "Stretch",
+];
+const JUSTIFY_ITEMS: &[&str] = &["Default", "Start", "End", "Center", "Baseline", "Stretch"];
+const ALIGN_SELF: &[&str] = &[
+    "Auto",
+    "Start",
+    "End",
+    "FlexStart",
+    "FlexEnd",
+    "Center",
+    "Baseline",
+    "Stretch",
+];
+const JUSTIFY_SELF: &[&str] = &["Auto", "Start", "End", "Center", "Baseline", "Stretch"];
+const ALIGN_CONTENT: &[&str] = &[
+    "Default",
+    "Start",
+    "End",
+    "FlexStart",
+    "FlexEnd",
+    "Center",
+    "Stretch",
+    "SpaceBetween",
+    "SpaceEvenly",
+    "SpaceAround",
+];
+const JUSTIFY_CONTENT: &[&str] = ALIGN_CONTENT;
+const FLEX_DIRECTION: &[&str] = &["Row", "Column", "RowReverse", "ColumnReverse"];
+const FLEX_WRAP: &[&str] = &["NoWrap", "Wrap", "WrapReverse"];
+const JUSTIFY_TEXT: &[&str] = &["Left", "Center", "Right", "Justified"];
+const LINE_BREAK: &[&str] = &["WordBoundary", "AnyCharacter", "WordOrCharacter", "NoWrap"];
+const FONT_SMOOTHING: &[&str] = &["None", "AntiAliased"];
+const COLORS: &[&str] = &["White", "Black", "Red", "Green", "Blue"];
+
+const COMPONENTS: &[KnownComponent] = &[
+    KnownComponent {
+        name: "Node",
+        path: "::bevy::prelude::Node",
+        attributes: &[
+            ("overflow_x", "overflow.x", ValueKind::Enum(OVERFLOW_AXIS)),
+            ("overflow_y", "overflow.y", ValueKind::Enum(OVERFLOW_AXIS)),
+            (
+                "overflow_clip_visual_box",
+                "overflow_clip_margin.visual_box",
+                ValueKind::Enum(OVERFLOW_CLIP_BOX),
+            ),
+            (
+                "overflow_clip_margin",
+                "overflow_clip_margin.margin",
+                ValueKind::F32,
+            ),
+            ("display", "display", ValueKind::Enum(DISPLAY)),
+            ("box_sizing", "box_sizing", ValueKind::Enum(BOX_SIZING)),
+            (
+                "position_type",
+                "position_type",
+                ValueKind::Enum(POSITION_TYPE),
+            ),
+            ("left", "left", ValueKind::Val),
+            ("right", "right", ValueKind::Val),
+            ("top", "top", ValueKind::Val),
+            ("bottom", "bottom", ValueKind::Val),
+            ("width", "width", ValueKind::Val),
+            ("height", "height", ValueKind::Val),
+            ("min_width", "min_width", ValueKind::Val),
+            ("min_height", "min_height", ValueKind::Val),
+            ("max_width", "max_width", ValueKind::Val),
+            ("max_height", "max_height", ValueKind::Val),
+            ("aspect_ratio", "aspect_ratio", ValueKind::OptionF32),
+            ("align_items", "align_items", ValueKind::Enum(ALIGN_ITEMS)),
+            (
+                "justify_items",
+                "justify_items",
+                ValueKind::Enum(JUSTIFY_ITEMS),
+            ),
+            ("align_self", "align_self", ValueKind::Enum(ALIGN_SELF)),
+            (
+                "justify_self",
+                "justify_self",
+                ValueKind::Enum(JUSTIFY_SELF),
+            ),
+            (
+                "align_content",
+                "align_content",
+                ValueKind::Enum(ALIGN_CONTENT),
+            ),
+            (
+                "justify_content",
+                "justify_content",
+                ValueKind::Enum(JUSTIFY_CONTENT),
+            ),
+            ("margin", "margin", ValueKind::UiRect),
+            ("padding", "padding", ValueKind::UiRect),
+            ("border", "border", ValueKind::UiRect),
+            (
+                "flex_direction",
+                "flex_direction",
+                ValueKind::Enum(FLEX_DIRECTION),
+            ),
+            ("flex_wrap", "flex_wrap", ValueKind::Enum(FLEX_WRAP)),
+            ("flex_grow", "flex_grow", ValueKind::F32),
+            ("flex_shrink", "flex_shrink", ValueKind::F32),
+            ("flex_basis", "flex_basis", ValueKind::Val),
+            ("row_gap", "row_gap", ValueKind::Val),
+            ("column_gap", "column_gap", ValueKind::Val),
+        ],
+    },
+    KnownComponent {
+        name: "BackgroundColor",
+        path: "::bevy::prelude::BackgroundColor",
+        attributes: &[("value", "0", ValueKind::Color)],
+    },
+    KnownComponent {
+        name: "TextColor",
+        path: "::bevy::prelude::TextColor",
+        attributes: &[("value", "0", ValueKind::Color)],
+    },
+    KnownComponent {
+        name: "Text",
+        path: "::bevy::prelude::Text",
+        attributes: &[("text", "0", ValueKind::String)],
+    },
+    KnownComponent {
+        name: "ImageNode",
+        path: "::bevy::prelude::ImageNode",
+        attributes: &[
+            ("flip_x", "flip_x", ValueKind::Bool),
+            ("flip_y", "flip_y", ValueKind::Bool),
+            ("color", "color", ValueKind::Color),
+            ("image", "image", ValueKind::Asset),
+        ],
+    },
+    KnownComponent {
+        name: "TextFont",
+        path: "::bevy::prelude::TextFont",
+        attributes: &[
+            ("font", "font", ValueKind::Asset),
+            ("font_size", "font_size", ValueKind::F32),
+            ("line_height", "line_height", ValueKind::LineHeight),
+            (
+                "font_smoothing",
+                "font_smoothing",
+                ValueKind::Enum(FONT_SMOOTHING),
+            ),
+        ],
+    },
+    KnownComponent {
+        name: "TextLayout",
+        path: "::bevy::prelude::TextLayout",
+        attributes: &[
+            ("justify", "justify", ValueKind::Enum(JUSTIFY_TEXT)),
+            ("linebreak", "linebreak", ValueKind::Enum(LINE_BREAK)),
+        ],
+    },
+    KnownComponent {
+        name: "Button",
+        path: "::bevy::prelude::Button",
+        attributes: &[],
+    },
+];
+
+pub(crate) fn known_component(name: &str) -> Option<&'static KnownComponent> {
+    COMPONENTS.iter().find(|component| component.name == name)
+}
+
+/// Namespace of the built-in components, the default namespace of the runtime library
+const DEFAULT_NAMESPACE: &str = "bevy";
+
+/// Rust path of the component of a tag, the namespace of a prefixed tag is the module of the type
+pub(crate) fn tag_path(tag: &Tag) -> Result<String, String> {
+    match tag.namespace.as_deref() {
+        None | Some(DEFAULT_NAMESPACE) => Ok(component_path(&tag.name)),
+        Some(namespace) => {
+            let path = format!("{namespace}::{}", tag.name);
+            match syn::parse_str::<syn::Path>(&path) {
+                Ok(_) => Ok(path),
+                Err(_) => Err(format!("Namespace '{namespace}' is not a module path")),
+            }
+        }
+    }
+}
+
+/// Same as [`resolve_field`], components of other namespaces are never the built-in ones
+pub(crate) fn resolve_tag_field(tag: &Tag, attribute: &str) -> Result<Field, String> {
+    match tag.namespace.as_deref() {
+        None | Some(DEFAULT_NAMESPACE) => resolve_field(&tag.name, attribute),
+        Some(_) => Ok(Field {
+            place: field_identifier(attribute)?,
+            kind: None,
+        }),
+    }
+}
+
+/// Rust path of the component type
+pub(crate) fn component_path(name: &str) -> String {
+    match known_component(name) {
+        Some(component) => component.path.to_string(),
+        None => name.to_string(),
+    }
+}
+
+/// Built-in attributes map to their fields, any other attribute is the field of the same name
+pub(crate) fn resolve_field(component: &str, attribute: &str) -> Result<Field, String> {
+    if let Some(known) = known_component(component) {
+        return known
+            .attributes
+            .iter()
+            .find(|(name, _, _)| *name == attribute)
+            .map(|(_, place, kind)| Field {
+                place: place.to_string(),
+                kind: Some(*kind),
+            })
+            .ok_or_else(|| format!("Unknown attribute '{attribute}'"));
+    }
+
+    Ok(Field {
+        place: field_identifier(attribute)?,
+        kind: None,
+    })
+}
+
+/// Keywords are written as raw identifiers, the ones which can not be raw are rejected
+fn field_identifier(attribute: &str) -> Result<String, String> {
+    let mut chars = attribute.chars();
+    let valid = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_');
+    if !valid || attribute == "_" {
+        return Err(format!("'{attribute}' is not a valid field name"));
+    }
+
+    if syn::parse_str::<syn::Ident>(attribute).is_ok() {
+        return Ok(attribute.to_string());
+    }
+
+    let raw = format!("r#{attribute}");
+    match syn::parse_str::<syn::Ident>(&raw) {
+        Ok(_) => Ok(raw),
+        Err(_) => Err(format!("'{attribute}' can not be used as a field name")),
+    }
+}
+
+impl ValueKind {
+    /// Mirrors the `FromStrTyped` implementations of the runtime
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        match self {
+            ValueKind::Val => validate_val(value),
+            ValueKind::UiRect => {
+                let values = value.split_whitespace().collect::<Vec<_>>();
+                if values.is_empty() || values.len() > 4 {
+                    return Err(format!("Expected 1 to 4 values: {value}"));
+                }
+                values.into_iter().try_for_each(validate_val)
+            }
+            ValueKind::F32 | ValueKind::OptionF32 => value
+                .parse::<f32>()
+                .map(|_| ())
+                .map_err(|error| error.to_string()),
+            ValueKind::Bool => value
+                .parse::<bool>()
+                .map(|_| ())
+                .map_err(|error| error.to_string()),
+            ValueKind::Color => validate_color(value),
+            ValueKind::LineHeight => {
+                let value = value.trim();
+                match value.strip_suffix("px").or(value.strip_suffix("rl")) {
+                    Some(number) if number.trim().parse::<f32>().is_ok() => Ok(()),
+                    _ => Err(format!(
+                        "String `{value}` does not match the pattern `<number>px` or `<number>rl`"
+                    )),
+                }
+            }
+            ValueKind::String => Ok(()),
+            ValueKind::Enum(variants) if variants.contains(&value) => Ok(()),
+            ValueKind::Enum(variants) => Err(format!(
+                "Unknown value: {value}, expected one of {}",
+                variants.join(", ")
+            )),
+            ValueKind::Asset => Ok(()),
+        }
+    }
+
+    /// Expression which builds the field value from a literal checked by [`ValueKind::validate`]
+    pub fn literal(kind: Option<ValueKind>, value: &str, attribute: &str) -> String {
+        match kind {
+            Some(ValueKind::Asset) => format!("server.load({value:?})"),
+            Some(ValueKind::OptionF32) => {
+                format!("Some({})", ValueKind::literal(None, value, attribute))
+            }
+            Some(ValueKind::String) => format!("{value:?}.to_string()"),
+            _ => format!(
+                "::bevy_declarative_ui::prelude::parse_or_default({value:?}, {attribute:?})"
+            ),
+        }
+    }
+}
+
+fn validate_val(value: &str) -> Result<(), String> {
+    let number = if value == "auto" {
+        return Ok(());
+    } else if let Some(number) = value.strip_suffix("px").or(value.strip_suffix('%')) {
+        number
+    } else {
+        ["vw", "vh", "vmin", "vmax"]
+            .iter()
+            .find_map(|unit| {
+                value
+                    .strip_prefix(unit)
+                    .and_then(|value| value.strip_prefix('('))
+                    .and_then(|value| value.strip_suffix(')'))
+            })
+            .ok_or_else(|| format!("Unknown value: {value}"))?
+    };
+
+    number
+        .parse::<f32>()
+        .map(|_| ())
+        .map_err(|_| format!("Invalid number in value: {value}"))
+}
+
+fn validate_color(value: &str) -> Result<(), String> {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix('#') {
+        return match hex.len() {
+            6 | 8 if hex.chars().all(|c| c.is_ascii_hexdigit()) => Ok(()),
+            6 | 8 => Err(format!("Invalid hex color format: {value}")),
+            length => Err(format!("Unexpected hex color length: {length}")),
+        };
+    }
+
+    let lower = value.to_ascii_lowercase();
+    if lower.starts_with("rgb(") || lower.starts_with("rgba(") {
+        let parts = lower
+            .trim_start_matches("rgba(")
+            .trim_start_matches("rgb(")
+            .trim_end_matches(')')
+            .split(',')
+            .map(str::trim)
+            .collect::<Vec<_>>();
+        let channels = |parts: &[&str]| parts.iter().all(|part| part.parse::<u8>().is_ok());
+        return match parts.as_slice() {
+            [_, _, _] if channels(&parts) => Ok(()),
+            [r, g, b, a] if channels(&[r, g, b]) && a.parse::<f32>().is_ok() => Ok(()),
+            [_, _, _] | [_, _, _, _] => Err(format!("Invalid rgb/rgba color values: {value}")),
+            _ => Err(format!("Invalid rgb/rgba format: {value}")),
+        };
+    }
+
+    if COLORS.contains(&value) {
+        Ok(())
+    } else {
+        Err(format!("Unknown color value: {value}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::r#static::attributes::{ValueKind, resolve_field};
+
+    #[test]
+    fn validate_literals() {
+        assert!(ValueKind::Val.validate("10px").is_ok());
+        assert!(ValueKind::Val.validate("vmin(5)").is_ok());
+        assert!(ValueKind::Val.validate("10pz").is_err());
+        assert!(ValueKind::UiRect.validate("1px 2% auto").is_ok());
+        assert!(ValueKind::UiRect.validate("").is_err());
+        assert!(ValueKind::Color.validate("#ff00ff").is_ok());
+        assert!(ValueKind::Color.validate("rgba(1, 2, 3, 0.5)").is_ok());
+        assert!(ValueKind::Color.validate("Purple").is_err());
+        assert!(ValueKind::LineHeight.validate("1.2rl").is_ok());
+    }
+
+    #[test]
+    fn resolve_fields() {
+        assert_eq!(
+            resolve_field("Node", "overflow_x").unwrap().place,
+            "overflow.x"
+        );
+        assert!(resolve_field("Node", "colour").is_err());
+        assert_eq!(resolve_field("Player", "type").unwrap().place, "r#type");
+        assert!(resolve_field("Player", "self").is_err());
+        assert!(resolve_field("Player", "1st").is_err());
+    }
+}
@@ -1,101 +1,51 @@
-use crate::codegen::{Access, Argument, Function, Ownership, Struct};
-use crate::r#static::binding::{prepare_filters, Observer};
-use crate::utils::ToSnakeCase;
+use crate::codegen::Function;
+use crate::r#static::attributes::component_path;
+use crate::r#static::binding::Observer;
+use bevy_declarative_ui_parser::values::bindings::BindingMode;
 use bevy_declarative_ui_parser::values::bindings::filter::{Filter, Filters};
 
-pub(super) fn binding_component_read_printer(
-    name: &str,
-    observers: Vec<Observer>,
-    mut filters: Filters,
-) -> Function {
-    let mut function = Function::new(format!("component_{}_binding_read", name.to_snake_case()));
-    let filters = filters.with(Filter::Changed(name.to_string()));
-    function.single_ref_arg("target", name, filters);
-
-    for (index, observer) in observers.iter().enumerate() {
-        let target = observer.target;
-        let set = &observer.set;
-        let get = &observer.get;
-        let filters = prepare_filters(observer, &observers);
-        function.single_mut_arg(format!("observer_{index}"), target, &filters);
-        function.push_line_to_body(format!("observer_{index}.{set} = target.{get};"));
-    }
-
-    function
-}
-
-pub(super) fn binding_component_write_printer(
-    name: &str,
-    targets: Vec<Observer>,
-    filters: Filters,
-) -> Function {
-    let mut function = Function::new(format!("component_{}_binding_write", name.to_snake_case()));
-    function.single_mut_arg("observer", name, &filters);
-
-    for (index, target) in targets.iter().enumerate() {
-        let component = target.target;
-        let set = &target.set;
-        let get = &target.get;
-        let mut filters = prepare_filters(target, &targets);
-        filters.with(Filter::Changed(component.to_string()));
-        function.single_ref_arg(format!("target_{index}"), component, &filters);
-        function.push_line_to_body(format!("observer.{set} = target_{index}.{get};"));
-    }
-
-    function
-}
-
-pub(super) fn binding_component_read_write_printer(
-    name: &str,
-    targets: Vec<Observer>,
-    filters: Filters,
-) -> (Struct, Function) {
-    let mut r#struct: Struct = Struct::new(format!("Component{name}BindingReadWrite"));
-    r#struct.derive("Default");
-    r#struct.field(Access::None, "cmp0", "bool");
-
-    let mut function = Function::new(format!(
-        "component_{}_binding_read_write",
-        name.to_snake_case()
-    ));
-    function.arg(Argument::new(
-        "local",
-        format!("Local<Component{name}BindingReadWrite>"),
-        true,
-        Ownership::Move,
-    ));
-    function.single_mut_arg("cmp0", name, &filters);
-
-    let mut index = 1;
-    function.push_line_to_body("if cmp0.is_changed() && !local.cmp0 {");
-    for target in &targets {
-        let set = &target.set;
-        let get = &target.get;
-        function.push_line_to_body(format!("cmp{index}.{set} = cmp0.{get};"));
-        index += 1;
-    }
-    function.push_line_to_body("local.cmp0 = true;");
-    function.push_line_to_body("}\n");
-
-    index = 1;
-    for target in &targets {
-        let set = &target.set;
-        let get = &target.get;
-        let filters = prepare_filters(target, &targets);
-        let identifier = format!("cmp{index}");
-        r#struct.field(Access::None, &identifier, "bool");
-        function.single_mut_arg(&identifier, target.target, &filters);
+///The source is the single entity which matches the filters, the system assigns the fallbacks
+///and waits for it. `Changed` and `Added` filters only skip frames, they do not make the source absent
+pub(super) fn access_source(
+    function: &mut Function,
+    component: &str,
+    filters: &Filters,
+    mode: BindingMode,
+    observers: &[Observer],
+    absent: &str,
+) {
+    let component = component_path(component);
+    let mut filters = filters.clone();
+    observers
+        .iter()
+        .filter(|observer| observer.component == component)
+        .for_each(|observer| {
+            filters.with(Filter::Without(observer.id.to_string()));
+        });
+
+    let absent = if absent.is_empty() || !filters.iter().any(Filter::is_change) {
+        absent.to_string()
+    } else {
+        let mut present = Filters::single(Filter::With(component.clone()));
+        filters
+            .iter()
+            .filter(|filter| !filter.is_change())
+            .for_each(|filter| {
+                present.with(filter.clone());
+            });
+        function.query_arg("present", "()", &present, false);
+        format!("if present.is_empty() {{\n{absent}}}\n")
+    };
+
+    if mode == BindingMode::Read {
+        function.query_arg("target", &format!("Ref<{component}>"), &filters, false);
+        function.push_line_to_body(format!(
+            "let Ok(target) = target.single() else {{\n{absent}return;\n}};"
+        ));
+    } else {
+        function.query_arg("target", &format!("&mut {component}"), &filters, true);
         function.push_line_to_body(format!(
-            "if {identifier}.is_changed() && !local.{identifier} {{"
+            "let Ok(mut target) = target.single_mut() else {{\n{absent}return;\n}};"
         ));
-        function.push_line_to_body(format!("cmp0.{get} = {identifier}.{set};"));
-        function.push_line_to_body(format!("local.{identifier} = true;"));
-        function.push_line_to_body("}");
-        function.push_line_to_body(format!("else if local.{identifier} {{"));
-        function.push_line_to_body(format!("local.{identifier} = false;"));
-        function.push_line_to_body("}\n");
-        index += 1;
     }
-
-    (r#struct, function)
 }
@@ -0,0 +1,88 @@
+use crate::codegen::Function;
+use crate::r#static::converter_path;
+use crate::r#static::required::{Required, RequiredInterpolation, Source};
+use bevy_declarative_ui_parser::values::bindings::filter::{Filter, Filters};
+use bevy_declarative_ui_parser::values::bindings::path::BindingPath;
+use bevy_declarative_ui_parser::values::{AttributeValue, InterpolationPart};
+
+///Renders the text every frame, the observer is only written when the text differs
+pub(super) fn print_interpolation(function: &mut Function, interpolation: &RequiredInterpolation) {
+    let mut sources = Required::default();
+    let mut format = String::new();
+    let mut arguments = String::new();
+    interpolation.parts.iter().for_each(|part| match part {
+        InterpolationPart::Text(text) => {
+            format.push_str(&text.replace('{', "{{").replace('}', "}}"))
+        }
+        InterpolationPart::Binding(AttributeValue::Resource(binding)) => {
+            let params = &binding.additional_params;
+            let source = Source::Resource(binding.base_params.target.clone());
+            let target = format!("{}.as_deref()", sources.source_variable(source));
+            format.push_str("{}");
+            arguments.push_str(&part_value(
+                &mut sources,
+                &target,
+                &binding.base_params.path,
+                params.converter.as_deref(),
+                params.fallback.as_deref(),
+            ));
+        }
+        InterpolationPart::Binding(AttributeValue::Component(binding)) => {
+            let params = &binding.additional_params;
+            let source =
+                Source::Component(binding.base_params.target.clone(), params.filters.clone());
+            let target = sources.source_variable(source);
+            format.push_str("{}");
+            arguments.push_str(&part_value(
+                &mut sources,
+                &target,
+                &binding.base_params.path,
+                params.converter.as_deref(),
+                params.fallback.as_deref(),
+            ));
+        }
+        //Rejected by the spawn function
+        InterpolationPart::Binding(_) => unreachable!(),
+    });
+
+    sources.take_arguments(function);
+    function.query_arg(
+        "observer",
+        &format!("&mut {}", interpolation.component),
+        &Filters::single(Filter::With(interpolation.id.clone())),
+        true,
+    );
+
+    let place = &interpolation.field.place;
+    function.push_line_to_body(format!(
+        "let Some(value) = (|| Some(format!({format:?}{arguments})))() else {{\nreturn;\n}};"
+    ));
+    function.push_line_to_body(format!(
+        "for mut observer in &mut observer {{\nif observer.{place} != value {{\nobserver.{place} = value.clone();\n}}\n}}"
+    ));
+}
+
+///Format argument of a binding, a missing value without fallback skips the whole text
+fn part_value(
+    sources: &mut Required,
+    target: &str,
+    path: &BindingPath,
+    converter: Option<&str>,
+    fallback: Option<&str>,
+) -> String {
+    let value = format!("{target}.and_then(|target| {})", path.to_rust("(*target)"));
+    let value = match converter {
+        Some(converter) => {
+            sources.asset_server = true;
+            format!(
+                "{value}.and_then(|value| ::bevy_declarative_ui::prelude::ConvertValue::convert_to(value, &{}::default(), &server)).map(|value| value.to_string())",
+                converter_path(converter)
+            )
+        }
+        None => format!("{value}.map(|value| value.to_string())"),
+    };
+    match fallback {
+        Some(fallback) => format!(", {value}.unwrap_or_else(|| {fallback:?}.to_string())"),
+        None => format!(", {value}?"),
+    }
+}
@@ -1,196 +1,262 @@
 mod component;
+mod interpolation;
 mod resource;
 
-use crate::codegen::Module;
-use crate::r#static::required::RequiredBinding;
-use bevy_declarative_ui_parser::utils::GetOrInsertEmpty;
+use crate::codegen::{Function, Module};
+use crate::r#static::attributes::ValueKind;
+use crate::r#static::converter_path;
+use crate::r#static::register_system;
+use crate::r#static::required::{RequiredBinding, RequiredInterpolation, Source};
+use crate::utils::ToSnakeCase;
+use bevy_declarative_ui_parser::values::bindings::BindingMode;
 use bevy_declarative_ui_parser::values::bindings::filter::{Filter, Filters};
-use bevy_declarative_ui_parser::values::bindings::params::BaseParams;
-use bevy_declarative_ui_parser::values::bindings::{BindingKind, BindingMode};
-use std::collections::HashMap;
-
-fn create_observers(bindings: &HashMap<String, Vec<RequiredBinding>>) -> ObserverCollection {
-    let mut collection = ObserverCollection::default();
-    bindings.iter().for_each(|(id, bindings)| {
-        bindings.iter().for_each(|binding| {
-            //match &binding.inner {
-            //    BindingKind::Component => {
-            //        let observer = create_observer(&binding, &binding..base, id.clone());
-            //        let (list, _) = collection.components.get_or_insert(&params.base.target, ||init_cmp_hash_map(&params.filters)).get_mut(&params.base.mode).unwrap();
-            //        list.push(observer);
-            //    },
-            //    BindingKind::Resource => {
-            //        let observer = create_observer(&binding, &params, id.clone());
-            //        collection.resources.get_or_insert(&params.target, init_res_hash_map).get_mut(&params.mode).unwrap().push(observer);
-            //    }
-            //}
-        });
-    });
 
-    collection
-}
+///One system per source and mode, every observing component is a query of the system.
+///Interpolated attributes get a system each
+pub(super) fn binding_printer(
+    bindings: &[RequiredBinding],
+    interpolations: &[RequiredInterpolation],
+    systems: &mut Vec<String>,
+) -> Module {
+    let mut module = Module::new("bindings");
+    module.with_using("super::*");
+    module.with_using("bevy::prelude::*");
+    module.with_using("super::ids::*");
 
-fn create_observer<'a>(
-    binding: &'a RequiredBinding,
-    params: &BaseParams,
-    id: String,
-) -> Observer<'a> {
-    /*
-    let (get, set) = match params.mode {
-        BindingMode::Read => {
-            let mut get = params.path.to_string();
-            let set = binding.field_name.clone();
-
-            if let Some(converter) = &params.converter {
-                get.push_str(&format!(".convert_to({converter}::default())"))
+    let mut names = vec![];
+    group_by_source(bindings)
+        .into_iter()
+        .for_each(|(source, mode, bindings)| {
+            let observers = group_by_observer(&bindings);
+            let mut function = Function::new(system_name(source, mode, &mut names));
+            let absent = absent_fallbacks(mode, &observers);
+            match source {
+                Source::Resource(resource) => {
+                    resource::access_source(&mut function, resource, mode, &absent)
+                }
+                Source::Component(component, filters) => component::access_source(
+                    &mut function,
+                    component,
+                    filters,
+                    mode,
+                    &observers,
+                    &absent,
+                ),
             }
+            print_observers(&mut function, mode, &observers);
+            register_system(&mut module, systems, function);
+        });
 
-            (get, set)
-        },
-        BindingMode::ReadWrite => {
-            let mut get = params.path.to_string();
-            let mut set = binding.field_name.clone();
+    interpolations.iter().for_each(|interpolation| {
+        let base = format!(
+            "{}_{}_interpolation",
+            interpolation.id.to_snake_case(),
+            interpolation.field.place.replace('.', "_")
+        );
+        let mut function = Function::new(unique_name(base, &mut names));
+        interpolation::print_interpolation(&mut function, interpolation);
+        register_system(&mut module, systems, function);
+    });
 
-            if let Some(converter) = &params.converter {
-                //get.push_str(&format!(".convert_to({}::default())", params.converter()));
-                get.push_str(&format!(".convert_from({converter}::default())"));
-            }
+    module
+}
 
-            (get, set)
-        },
-        BindingMode::Write => {
-            let mut get = binding.field_name.clone();
-            let set = params.path.to_string();
+///Component of a node which observes the source
+struct Observer<'a> {
+    id: &'a str,
+    component: &'a str,
+    bindings: Vec<&'a RequiredBinding>,
+}
 
-            if let Some(converter) = &params.converter {
-                get.push_str(&format!(".convert_from({converter}::default())"));
-            }
+fn group_by_source(
+    bindings: &[RequiredBinding],
+) -> Vec<(&Source, BindingMode, Vec<&RequiredBinding>)> {
+    let mut groups: Vec<(&Source, BindingMode, Vec<&RequiredBinding>)> = vec![];
+    bindings.iter().for_each(|binding| {
+        let group = groups
+            .iter_mut()
+            .find(|(source, mode, _)| **source == binding.source && *mode == binding.mode);
+        match group {
+            Some((_, _, group)) => group.push(binding),
+            None => groups.push((&binding.source, binding.mode, vec![binding])),
+        }
+    });
+    groups
+}
+
+fn group_by_observer<'a>(bindings: &[&'a RequiredBinding]) -> Vec<Observer<'a>> {
+    let mut observers: Vec<Observer> = vec![];
+    bindings.iter().for_each(|binding| {
+        let observer = observers
+            .iter_mut()
+            .find(|observer| observer.id == binding.id && observer.component == binding.component);
+        match observer {
+            Some(observer) => observer.bindings.push(binding),
+            None => observers.push(Observer {
+                id: &binding.id,
+                component: &binding.component,
+                bindings: vec![binding],
+            }),
+        }
+    });
+    observers
+}
 
-            (get, set)
-        },
+fn system_name(source: &Source, mode: BindingMode, names: &mut Vec<String>) -> String {
+    let (kind, name) = match source {
+        Source::Resource(name) => ("resource", name),
+        Source::Component(name, _) => ("component", name),
+    };
+    let name = name.rsplit("::").next().unwrap_or(name).to_snake_case();
+    let mode = match mode {
+        BindingMode::Read => "read",
+        BindingMode::Write => "write",
+        BindingMode::ReadWrite => "read_write",
         BindingMode::ReadOnce => unreachable!(),
     };
 
-    Observer {
-        target: &binding.component,
-        set,
-        get,
-        id,
-    }
-    */
-
-    Observer {
-        target: &binding.component,
-        id,
-        set: String::new(),
-        get: String::new(),
-    }
+    unique_name(format!("{kind}_{name}_binding_{mode}"), names)
 }
 
-fn init_res_hash_map<'a>() -> HashMap<BindingMode, Vec<Observer<'a>>> {
-    let mut map = HashMap::new();
-    map.insert(BindingMode::Read, vec![]);
-    map.insert(BindingMode::Write, vec![]);
-    map.insert(BindingMode::ReadWrite, vec![]);
-    map
+pub(super) fn unique_name(base: String, names: &mut Vec<String>) -> String {
+    let mut name = base.clone();
+    let mut index = 1;
+    while names.contains(&name) {
+        name = format!("{base}_{index}");
+        index += 1;
+    }
+    names.push(name.clone());
+    name
 }
 
-fn init_cmp_hash_map<'a>(filters: &Filters) -> HashMap<BindingMode, (Vec<Observer<'a>>, Filters)> {
-    let mut map = HashMap::new();
-    map.insert(BindingMode::Read, (vec![], filters.clone()));
-    map.insert(BindingMode::Write, (vec![], filters.clone()));
-    map.insert(BindingMode::ReadWrite, (vec![], filters.clone()));
-    map
+///Observers of the same component must be disjoint, otherwise the queries conflict
+fn observer_filters(current: &Observer, observers: &[Observer]) -> Filters {
+    let mut filters = Filters::single(Filter::With(current.id.to_string()));
+    observers
+        .iter()
+        .filter(|observer| observer.component == current.component && observer.id != current.id)
+        .for_each(|observer| {
+            filters.with(Filter::Without(observer.id.to_string()));
+        });
+    filters
 }
 
-pub(super) fn binding_printer(bindings: &HashMap<String, Vec<RequiredBinding>>) -> Module {
-    let mut module = Module::new("bindings");
-    module.with_using("bevy::prelude::*");
-    module.with_using("super::ids::*");
-
-    let collection = create_observers(bindings);
-    collection.resources.iter().for_each(|(resource, maps)| {
-        maps.iter().for_each(|map| {
-            if map.1.is_empty() {
-                return;
-            }
-            let function = match map.0 {
-                BindingMode::Read => resource::binding_resource_read_printer(resource, map.1),
-                BindingMode::Write => resource::binding_resource_write_printer(resource, map.1),
-                BindingMode::ReadWrite => {
-                    let (r#struct, function) =
-                        resource::binding_resource_read_write_printer(resource, map.1);
-                    module.with_struct(r#struct);
-                    function
-                }
-                BindingMode::ReadOnce => unreachable!(),
-            };
-            module.with_function(function);
+fn print_observers(function: &mut Function, mode: BindingMode, observers: &[Observer]) {
+    let loads = observers
+        .iter()
+        .flat_map(|observer| &observer.bindings)
+        .any(|binding| {
+            binding.converter.is_some()
+                || (binding.fallback.is_some() && binding.field.kind == Some(ValueKind::Asset))
         });
-    });
+    if loads {
+        function.asset_server_arg();
+    }
 
-    collection
-        .components
-        .into_iter()
-        .for_each(|(component, maps)| {
-            maps.into_iter().for_each(|map| {
-                let (observers, filters) = map.1;
-                if observers.is_empty() {
-                    return;
-                }
-                let function = match map.0 {
-                    BindingMode::Read => {
-                        component::binding_component_read_printer(&component, observers, filters)
-                    }
-                    BindingMode::Write => {
-                        component::binding_component_write_printer(&component, observers, filters)
-                    }
-                    BindingMode::ReadWrite => {
-                        let (r#struct, function) = component::binding_component_read_write_printer(
-                            &component, observers, filters,
-                        );
-                        module.with_struct(r#struct);
-                        function
-                    }
-                    BindingMode::ReadOnce => unreachable!(),
-                };
-                module.with_function(function);
-            });
-        });
+    if mode != BindingMode::Write {
+        function.push_line_to_body("let changed = target.is_changed();");
+    }
 
-    module
-}
+    for (index, observer) in observers.iter().enumerate() {
+        let name = format!("observer_{index}");
+        let filters = observer_filters(observer, observers);
+        let component = observer.component;
+        let reads = observer
+            .bindings
+            .iter()
+            .map(|binding| read(binding))
+            .collect::<String>();
+        let writes = observer
+            .bindings
+            .iter()
+            .map(|binding| write(binding))
+            .collect::<String>();
 
-pub struct Observer<'a> {
-    target: &'a str,
-    id: String,
-    set: String,
-    get: String,
+        match mode {
+            BindingMode::Read => {
+                function.query_arg(&name, &format!("&mut {component}"), &filters, true);
+                function.push_line_to_body(format!(
+                    "for mut observer in &mut {name} {{\nif !changed && !observer.is_added() {{\ncontinue;\n}}\n{reads}}}"
+                ));
+            }
+            BindingMode::Write => {
+                function.query_arg(&name, &format!("Ref<{component}>"), &filters, false);
+                function.push_line_to_body(format!(
+                    "for observer in &{name} {{\nif observer.is_added() || !observer.is_changed() {{\ncontinue;\n}}\n{writes}}}"
+                ));
+            }
+            BindingMode::ReadWrite => {
+                function.query_arg(&name, &format!("&mut {component}"), &filters, true);
+                function.push_line_to_body(format!(
+                    "for mut observer in &mut {name} {{\nif changed || observer.is_added() {{\n{reads}}} else if observer.is_changed() {{\n{writes}}}\n}}"
+                ));
+            }
+            BindingMode::ReadOnce => unreachable!(),
+        }
+    }
 }
 
-#[derive(Default)]
-struct ObserverCollection<'a> {
-    resources: HashMap<String, HashMap<BindingMode, Vec<Observer<'a>>>>,
-    components: HashMap<String, HashMap<BindingMode, (Vec<Observer<'a>>, Filters)>>,
+///Copies the source value to the observer, unchanged values are skipped to keep change detection quiet
+fn read(binding: &RequiredBinding) -> String {
+    let value = binding.path.to_rust("(*target)");
+    let value = match &binding.converter {
+        Some(converter) => format!(
+            "{value}.and_then(|value| ::bevy_declarative_ui::prelude::ConvertValue::convert_to(value, &{}::default(), &server))",
+            converter_path(converter)
+        ),
+        None => format!("{value}.cloned()"),
+    };
+    let value = match &binding.fallback {
+        Some(fallback) => format!("{value}.or_else(|| Some({fallback}))"),
+        None => value,
+    };
+    let place = &binding.field.place;
+    format!(
+        "if let Some(value) = {value} {{\nif observer.{place} != value {{\nobserver.{place} = value;\n}}\n}}\n"
+    )
 }
 
-fn prepare_filters(current: &Observer, observers: &[Observer]) -> Filters {
-    let id = &current.id;
-
-    let mut filters = Filters::default();
-    filters.with(Filter::With(id.to_string()));
-
-    if observers.len() == 1 {
-        return filters;
+///Assigns the fallbacks of the observers while the source does not exist
+fn absent_fallbacks(mode: BindingMode, observers: &[Observer]) -> String {
+    if mode == BindingMode::Write {
+        return String::new();
     }
 
     observers
         .iter()
-        .filter(|observer| observer.id != *id)
-        .for_each(|observer| {
-            let id = &observer.id;
-            filters.with(Filter::Without(id.to_string()));
-        });
+        .enumerate()
+        .filter_map(|(index, observer)| {
+            let assignments = observer
+                .bindings
+                .iter()
+                .filter_map(|binding| {
+                    let fallback = binding.fallback.as_ref()?;
+                    let place = &binding.field.place;
+                    Some(format!(
+                        "let value = {fallback};\nif observer.{place} != value {{\nobserver.{place} = value;\n}}\n"
+                    ))
+                })
+                .collect::<String>();
+            (!assignments.is_empty()).then(|| {
+                format!("for mut observer in &mut observer_{index} {{\n{assignments}}}\n")
+            })
+        })
+        .collect()
+}
 
-    filters
+///Copies the observer value back to the source
+fn write(binding: &RequiredBinding) -> String {
+    let place = &binding.field.place;
+    let value = match &binding.converter {
+        Some(converter) => format!(
+            "::bevy_declarative_ui::prelude::ConvertValue::convert_from(&observer.{place}, &{}::default())",
+            converter_path(converter)
+        ),
+        None => format!("Some(observer.{place}.clone())"),
+    };
+    let current = binding.path.to_rust("(*target)");
+    let target = binding.path.to_rust_mut("(*target)");
+    format!(
+        "if let Some(value) = {value} {{\nif {current}.is_some_and(|current| *current != value) {{\nif let Some(target) = {target} {{\n*target = value;\n}}\n}}\n}}\n"
+    )
 }
@@ -1,84 +1,22 @@
-use crate::codegen::{Access, Function, Struct};
-use crate::r#static::binding::{prepare_filters, Observer};
-use crate::utils::ToSnakeCase;
-use bevy_declarative_ui_parser::values::bindings::filter::Filter;
-
-pub(super) fn binding_resource_read_printer(name: &str, observers: &[Observer]) -> Function {
-    let mut function = Function::new(format!("resource_{}_binding_read", name.to_snake_case()));
-    function.resource_arg(name, "target");
-    function.push_line_to_body("if !target.is_changed() {\nreturn;\n}");
-
-    for (index, observer) in observers.iter().enumerate() {
-        let filters = prepare_filters(observer, observers);
-        let target = observer.target;
-        let set = &observer.set;
-        let get = &observer.get;
-
-        function.single_mut_arg(format!("observer_{index}"), target, &filters);
-        function.push_line_to_body(format!("observer_{index}.{set} = target.{get};"));
-    }
-
-    function
-}
-
-pub(super) fn binding_resource_write_printer(name: &str, components: &Vec<Observer>) -> Function {
-    let mut function = Function::new(format!("resource_{}_binding_write", name.to_snake_case()));
-    function.resource_mut(name, "observer");
-
-    for (index, component) in components.iter().enumerate() {
-        let mut filters = prepare_filters(component, components);
-        let target = component.target;
-        filters.with(Filter::Changed(target.to_string()));
-        let set = &component.set;
-        let get = &component.get;
-
-        function.single_ref_arg(format!("target_{index}"), target, &filters);
-        function.push_line_to_body(format!("observer.{set} = target.{get};"));
-    }
-
-    function
-}
-
-pub(super) fn binding_resource_read_write_printer(
-    name: &str,
-    observers: &Vec<Observer>,
-) -> (Struct, Function) {
-    let mut r#struct: Struct = Struct::new(format!("Resource{name}BindingReadWrite"));
-    r#struct.derive("Default");
-    r#struct.field(Access::None, "res", "bool");
-
-    let mut index = 0;
-    let mut function = Function::new(format!("resource_{}_binding_write", name.to_snake_case()));
-    function.resource_mut(name, "res");
-    function.push_line_to_body("if res.is_changed() && !local.res {");
-    for target in observers {
-        let set = &target.set;
-        let get = &target.get;
-        function.push_line_to_body(format!("cmp{index}.{set} = res.{get};"));
-        index += 1;
-    }
-    function.push_line_to_body("local.res = true;");
-    function.push_line_to_body("}\n");
-
-    index = 0;
-    for target in observers {
-        let set = &target.set;
-        let get = &target.get;
-        let filters = prepare_filters(target, observers);
-        let identifier = format!("cmp{index}");
-        r#struct.field(Access::None, &identifier, "bool");
-        function.single_mut_arg(&identifier, target.target, &filters);
+use crate::codegen::Function;
+use bevy_declarative_ui_parser::values::bindings::BindingMode;
+
+///The resource may not exist yet, the system assigns the fallbacks and waits for it
+pub(super) fn access_source(
+    function: &mut Function,
+    resource: &str,
+    mode: BindingMode,
+    absent: &str,
+) {
+    let mutable = mode != BindingMode::Read;
+    function.optional_resource_arg(resource, "target", mutable);
+    if mutable {
+        function.push_line_to_body(format!(
+            "let Some(mut target) = target else {{\n{absent}return;\n}};"
+        ));
+    } else {
         function.push_line_to_body(format!(
-            "if {identifier}.is_changed() && !local.{identifier} {{"
+            "let Some(target) = target else {{\n{absent}return;\n}};"
         ));
-        function.push_line_to_body(format!("res.{get} = {identifier}.{set};"));
-        function.push_line_to_body(format!("local.{identifier} = true;"));
-        function.push_line_to_body("}");
-        function.push_line_to_body(format!("else if local.{identifier} {{"));
-        function.push_line_to_body(format!("local.{identifier} = false;"));
-        function.push_line_to_body("}\n");
-        index += 1;
     }
-
-    (r#struct, function)
 }
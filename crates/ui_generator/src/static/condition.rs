@@ -0,0 +1,96 @@
+use crate::codegen::{Function, Module};
+use crate::r#static::binding::unique_name;
+use crate::r#static::converter_path;
+use crate::r#static::register_system;
+use crate::r#static::required::{Required, RequiredCondition, Source};
+use crate::utils::ToSnakeCase;
+use bevy_declarative_ui_parser::ConditionTest;
+use bevy_declarative_ui_parser::values::AttributeValue;
+use bevy_declarative_ui_parser::values::bindings::filter::{Filter, Filters};
+
+///One system per conditional container, the container is hidden with `Display::None`
+pub(super) fn condition_printer(
+    conditions: &[RequiredCondition],
+    systems: &mut Vec<String>,
+) -> Module {
+    let mut module = Module::new("conditions");
+    module.with_using("super::*");
+    module.with_using("bevy::prelude::*");
+    module.with_using("super::ids::*");
+
+    let mut names = vec![];
+    conditions.iter().for_each(|condition| {
+        let base = format!("{}_condition", condition.id.to_snake_case());
+        let mut function = Function::new(unique_name(base, &mut names));
+        print_condition(&mut function, condition);
+        register_system(&mut module, systems, function);
+    });
+
+    module
+}
+
+///Evaluates the test every frame, the container is only written when it must be shown or hidden
+fn print_condition(function: &mut Function, condition: &RequiredCondition) {
+    let mut sources = Required::default();
+    let (target, path, converter) = match &condition.condition.value {
+        AttributeValue::Resource(binding) => {
+            let source = Source::Resource(binding.base_params.target.clone());
+            let target = format!("{}.as_deref()", sources.source_variable(source));
+            let converter = binding.additional_params.converter.as_deref();
+            (target, &binding.base_params.path, converter)
+        }
+        AttributeValue::Component(binding) => {
+            let params = &binding.additional_params;
+            let source =
+                Source::Component(binding.base_params.target.clone(), params.filters.clone());
+            let target = sources.source_variable(source);
+            (
+                target,
+                &binding.base_params.path,
+                params.converter.as_deref(),
+            )
+        }
+        //Rejected by the spawn function
+        _ => unreachable!(),
+    };
+
+    let read = format!("{target}.and_then(|target| {})", path.to_rust("(*target)"));
+    let read = match converter {
+        Some(converter) => {
+            sources.asset_server = true;
+            format!(
+                "{read}.and_then(|value| ::bevy_declarative_ui::prelude::ConvertValue::convert_to(value, &{}::default(), &server))",
+                converter_path(converter)
+            )
+        }
+        None => read,
+    };
+    let value = match converter {
+        Some(_) => "value.as_ref()",
+        None => "value",
+    };
+    let shown = match &condition.condition.test {
+        ConditionTest::True => format!(
+            "{value}.is_some_and(|value| ::bevy_declarative_ui::prelude::condition_is_true(value))"
+        ),
+        ConditionTest::False => format!(
+            "!{value}.is_some_and(|value| ::bevy_declarative_ui::prelude::condition_is_true(value))"
+        ),
+        ConditionTest::Equals(equals) => format!(
+            "{value}.is_some_and(|value| ::bevy_declarative_ui::prelude::condition_equals(value, {equals:?}))"
+        ),
+    };
+
+    sources.take_arguments(function);
+    function.query_arg(
+        "observer",
+        "(&mut ::bevy::prelude::Node, &mut ::bevy_declarative_ui::prelude::ConditionalDisplay)",
+        &Filters::single(Filter::With(condition.id.clone())),
+        true,
+    );
+    function.push_line_to_body(format!("let value = {read};"));
+    function.push_line_to_body(format!("let shown = {shown};"));
+    function.push_line_to_body(
+        "for (mut node, mut display) in &mut observer {\nif display.is_shown() != shown {\ndisplay.show(&mut node.display, shown);\n}\n}",
+    );
+}
@@ -0,0 +1,97 @@
+use bevy_declarative_ui_parser::XmlLayoutError;
+use bevy_declarative_ui_parser::attribute::Attribute;
+use bevy_declarative_ui_parser::into::Tag;
+use bevy_declarative_ui_parser::position::Location;
+use std::fmt::{Display, Formatter};
+
+/// Error of [`generate_static_layout`](crate::generate_static_layout)
+#[derive(Debug)]
+pub enum GeneratorError {
+    /// The layout could not be parsed
+    Layout(XmlLayoutError),
+    /// The layout is valid, but a component can not be turned into code
+    Component {
+        file: String,
+        location: Location,
+        component: String,
+        message: String,
+    },
+    /// The layout is valid, but an attribute can not be turned into code
+    Attribute {
+        file: String,
+        //Boxed, the names and the message already take most of the error
+        location: Box<Location>,
+        component: String,
+        attribute: String,
+        message: String,
+    },
+}
+
+impl GeneratorError {
+    /// Error at the name of the component, the file is set by [`GeneratorError::in_file`]
+    pub(crate) fn component(component: &Tag, message: impl Into<String>) -> Self {
+        Self::Component {
+            file: String::new(),
+            location: component.span.location(),
+            component: component.name.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Error at the name of the attribute, the file is set by [`GeneratorError::in_file`]
+    pub(crate) fn attribute(
+        component: &Tag,
+        attribute: &Attribute,
+        message: impl Into<String>,
+    ) -> Self {
+        Self::Attribute {
+            file: String::new(),
+            location: Box::new(attribute.name_span.location()),
+            component: component.name.to_string(),
+            attribute: attribute.name.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn in_file(mut self, path: &str) -> Self {
+        match &mut self {
+            GeneratorError::Layout(_) => {}
+            GeneratorError::Component { file, .. } | GeneratorError::Attribute { file, .. } => {
+                *file = path.to_string()
+            }
+        }
+        self
+    }
+}
+
+impl From<XmlLayoutError> for GeneratorError {
+    fn from(error: XmlLayoutError) -> Self {
+        Self::Layout(error)
+    }
+}
+
+impl Display for GeneratorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GeneratorError::Layout(error) => write!(f, "{error}"),
+            GeneratorError::Component {
+                file,
+                location,
+                component,
+                message,
+            } => write!(f, "{file}:{location}: [{component}] {message}"),
+            GeneratorError::Attribute {
+                file,
+                location,
+                component,
+                attribute,
+                message,
+            } => write!(
+                f,
+                "{file}:{location}: [{component}] Attribute '{attribute}': {message}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GeneratorError {}
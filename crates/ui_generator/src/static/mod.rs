@@ -1,43 +1,88 @@
-use crate::codegen::RustFile;
+use crate::codegen::{Access, Function, Module, RustFile};
 use crate::r#static::binding::binding_printer;
+use crate::r#static::condition::condition_printer;
 use crate::r#static::ids::generate_ids;
 use crate::r#static::required::Required;
 use crate::r#static::spawn_function::print_spawn_function;
 use crate::r#static::template_function::print_template_functions;
-use bevy_declarative_ui_parser::LayoutReader;
+use bevy_declarative_ui_parser::{IncludedFiles, LayoutReader};
 
+pub use error::GeneratorError;
+
+mod attributes;
 mod binding;
+mod condition;
+mod error;
 mod ids;
 mod required;
 mod spawn_function;
 mod template_function;
-mod type_analyzer;
+
+pub struct StaticLayout {
+    ///Ids, bindings and templates modules with the spawn function
+    pub content: String,
+    ///Path of the system that spawns the document
+    pub spawn_system: String,
+    ///Paths of the systems that must run every frame
+    pub systems: Vec<String>,
+}
+
+pub fn generate_static_layout(path: &str, xml: &str) -> Result<StaticLayout, GeneratorError> {
+    generate_static_layout_with(path, xml, read_file)
+}
+
+///Same as [`generate_static_layout`], the files of the `<Include>` elements are read with `read`
+pub fn generate_static_layout_with(
+    path: &str,
+    xml: &str,
+    read: impl FnMut(&str) -> Result<String, String>,
+) -> Result<StaticLayout, GeneratorError> {
+    let (file, spawn_system, systems) = generate_file(path, xml, read)?;
+    Ok(StaticLayout {
+        content: file.into_string(false),
+        spawn_system,
+        systems,
+    })
+}
 
 #[allow(dead_code)]
 pub fn generate_file_content(path: &str, xml: &str) -> String {
+    let (file, _, _) = generate_file(path, xml, read_file).unwrap();
+    file.into_string(true)
+}
+
+fn read_file(path: &str) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|error| error.to_string())
+}
+
+fn generate_file(
+    path: &str,
+    xml: &str,
+    read: impl FnMut(&str) -> Result<String, String>,
+) -> Result<(RustFile, String, Vec<String>), GeneratorError> {
     let mut reader = LayoutReader::new(xml, path);
-    let result = reader.parse().unwrap();
+    let result = reader.parse()?;
+    let mut result = IncludedFiles::new(path, &result)
+        .read_with(read)
+        .resolve(path, result)?;
+    //Classes are resolved before printing, the components of the nodes are the styled ones
+    result.apply_styles();
 
     let mut required = Required::default();
-    let mut spawn_function = print_spawn_function(&mut required, &result.root_nodes);
-    let templates = print_template_functions(&result.templates, &mut required);
+    let in_file = |error: GeneratorError| error.in_file(path);
+    let spawn_function =
+        print_spawn_function(&mut required, &result.root_nodes).map_err(in_file)?;
+    let templates = print_template_functions(&result.templates, &mut required).map_err(in_file)?;
 
     let ids_module = generate_ids(&required.ids);
-    let bindings = binding_printer(&required.bindings);
-
-    required
-        .components
-        .iter()
-        .for_each(|(component, (arg_name, filters))| {
-            spawn_function.single_ref_arg(arg_name, component, filters);
-        });
-
-    required
-        .resources
-        .iter()
-        .for_each(|(resource_name, arg_name)| {
-            spawn_function.resource_arg(resource_name, arg_name);
-        });
+    let bindings = binding_printer(
+        &required.bindings,
+        &required.interpolations,
+        &mut required.systems,
+    );
+    let conditions = condition_printer(&required.conditions, &mut required.systems);
+
+    let spawn_system = spawn_function.name().to_string();
 
     let mut file = RustFile::default();
     result.usings.iter().for_each(|using| {
@@ -47,28 +92,433 @@ pub fn generate_file_content(path: &str, xml: &str) -> String {
     file.with_using("ids::*");
     file.with_module(ids_module);
     file.with_module(bindings);
+    file.with_module(conditions);
     file.with_module(templates);
     file.with_function(spawn_function);
 
-    file.into_string(true)
+    Ok((file, spawn_system, required.systems))
+}
+
+///Built-in converters are referenced through the runtime crate, the others must be in scope
+pub(crate) fn converter_path(name: &str) -> String {
+    match name {
+        "BoolToDisplay" | "BoolToVisibility" | "Percent" | "ToText" | "ImagePath" => {
+            format!("::bevy_declarative_ui::prelude::{name}")
+        }
+        _ => name.to_string(),
+    }
+}
+
+///Adds system to the module and makes it reachable from the plugin
+fn register_system(module: &mut Module, systems: &mut Vec<String>, mut function: Function) {
+    function.access(Access::Super);
+    systems.push(format!("{}::{}", module.name(), function.name()));
+    module.with_function(function);
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::r#static::generate_file_content;
+    use crate::r#static::{GeneratorError, generate_static_layout, generate_static_layout_with};
+
+    fn generate(xml: &str) -> String {
+        let layout = generate_static_layout("layout.xml", xml).unwrap();
+        syn::parse_file(&layout.content).expect("generated code is valid Rust");
+        layout.content
+    }
+
+    /// Message of the error, without the `file:line:column: ` prefix
+    fn generate_err(xml: &str) -> String {
+        match generate_static_layout("layout.xml", xml) {
+            Err(error @ (GeneratorError::Attribute { .. } | GeneratorError::Component { .. })) => {
+                let error = error.to_string();
+                error.split_once(": ").unwrap().1.to_string()
+            }
+            Err(error) => panic!("unexpected error: {error}"),
+            Ok(_) => panic!("layout was generated"),
+        }
+    }
 
     #[test]
-    fn test() {
-        let path =
-            "/home/irisu/bevy_declarative_ui/bevy_declarative_ui/assets/injection_count_10.xml";
-        let xml = std::fs::read_to_string(path).unwrap();
-        let _result = generate_file_content(path, &xml);
+    fn literals() {
+        let content = generate(
+            r#"
+            <Layout>
+                <Node width="10px" overflow_x="Clip" aspect_ratio="1.5"/>
+                <Container id="Title">
+                    <Text text="Hello"/>
+                    <ImageNode image="icons/exit.png"/>
+                    <Player type="Warrior"/>
+                </Container>
+            </Layout>
+            "#,
+        );
 
-        println!("{_result}");
+        assert!(content.contains("let mut component = ::bevy::prelude::Node::default();"));
+        assert!(content.contains(
+            r#"component.width = ::bevy_declarative_ui::prelude::parse_or_default("10px", "width");"#
+        ));
+        assert!(content.contains("component.overflow.x = "));
+        assert!(content.contains("component.aspect_ratio = Some("));
+        assert!(content.contains(r#"component.0 = "Hello".to_string();"#));
+        assert!(content.contains(r#"component.image = server.load("icons/exit.png");"#));
+        assert!(content.contains("component.r#type = "));
+        assert!(content.contains("server: Res<AssetServer>"));
+        assert!(content.contains("Title))"));
     }
-}
 
-//Make query
-//Get access
-//Put ref in local variable
-//Read value
+    #[test]
+    fn bindings() {
+        let content = generate(
+            r#"
+            <Layout>
+                <Node width="{Resource Globals, Path=width}" height="{Resource Globals, Path=height, Mode=Read}"/>
+                <Text text="{Component Player, Path=name, Mode=ReadWrite}"/>
+            </Layout>
+            "#,
+        );
+
+        //Read once by the spawn function
+        assert!(content.contains("source_0: Option<Res<Globals>>"));
+        assert!(content.contains(
+            "if let Some(value) = source_0.as_deref().and_then(|target| Some(&(*target).width)).cloned()"
+        ));
+
+        assert!(content.contains("fn resource_globals_binding_read("));
+        assert!(content.contains("target: Option<Res<Globals>>"));
+        assert!(content.contains("observer.height = value;"));
+        assert!(
+            content
+                .contains("fn component_player_binding_read_write(mut target: Query<&mut Player>")
+        );
+        assert!(content.contains("Query<&mut ::bevy::prelude::Text, With<Root>>"));
+        assert!(content.contains("if let Some(target) = Some(&mut (*target).name)"));
+    }
+
+    #[test]
+    fn filters() {
+        let content = generate(
+            r#"
+            <Layout>
+                <Text text="{Component Name, Path=0, Mode=Read, Filter={Player, Changed<Name>}}"/>
+                <Node width="{Component Size, Path=0, Filter=Or(Boss, !Minion)}"/>
+            </Layout>
+            "#,
+        );
+
+        assert!(content.contains("fn component_name_binding_read("));
+        assert!(content.contains("target: Query<Ref<Name>, (With<Player>, Changed<Name>)>"));
+        assert!(content.contains("Query<&Size, Or<(With<Boss>, Without<Minion>)>>"));
+    }
+
+    #[test]
+    fn fallbacks() {
+        let content = generate(
+            r#"
+            <Layout>
+                <Node width="{Resource Globals, Path=width, Fallback=10px}" height="{Resource Globals, Path=height, Mode=Read, Fallback=5px}"/>
+                <Text text="{Component Name, Path=0, Mode=Read, Filter=Changed<Name>, Fallback=Unknown}"/>
+            </Layout>
+            "#,
+        );
+
+        assert!(content.contains(
+            r#"component.width = source_0.as_deref().and_then(|target| Some(&(*target).width)).cloned().unwrap_or_else(|| ::bevy_declarative_ui::prelude::parse_or_default("10px", "width"));"#
+        ));
+        assert!(content.contains(
+            r#".cloned().or_else(|| Some(::bevy_declarative_ui::prelude::parse_or_default("5px", "height")))"#
+        ));
+        assert!(content.contains(
+            r#"let Some(target) = target else {
+for mut observer in &mut observer_0 {
+let value = ::bevy_declarative_ui::prelude::parse_or_default("5px", "height");"#
+        ));
+        assert!(content.contains("present: Query<(), With<Name>>"));
+        assert!(content.contains(
+            r#"let Ok(target) = target.single() else {
+if present.is_empty() {
+for mut observer in &mut observer_0 {
+let value = "Unknown".to_string();"#
+        ));
+    }
+
+    #[test]
+    fn namespaces() {
+        let content = generate(
+            r#"
+            <Layout xmlns:game="game::ui">
+                <Container>
+                    <game:Node width="wide"/>
+                    <Node width="10px"/>
+                </Container>
+            </Layout>
+            "#,
+        );
+
+        assert!(content.contains("let mut component = game::ui::Node::default();"));
+        assert!(content.contains(
+            r#"component.width = ::bevy_declarative_ui::prelude::parse_or_default("wide", "width");"#
+        ));
+        assert!(content.contains("let mut component = ::bevy::prelude::Node::default();"));
+        assert_eq!(
+            generate_err(
+                r#"<Layout xmlns:web="https://example.com"><Container><web:Bar/></Container></Layout>"#
+            ),
+            "[Bar] Namespace 'https://example.com' is not a module path"
+        );
+    }
+
+    #[test]
+    fn interpolation() {
+        let content = generate(
+            r#"
+            <Layout>
+                <Container id="Health">
+                    <Text text="HP: {Resource Stats, Path=health, Fallback=-} / {Component Player, Path=max}"/>
+                </Container>
+            </Layout>
+            "#,
+        );
+
+        assert!(content.contains("fn health_0_interpolation("));
+        assert!(content.contains("mut observer: Query<&mut ::bevy::prelude::Text, With<Health>>"));
+        assert!(content.contains(
+            r#"let Some(value) = (|| Some(format!("HP: {} / {}", source_0.as_deref().and_then(|target| Some(&(*target).health)).map(|value| value.to_string()).unwrap_or_else(|| "-".to_string()), source_1.and_then(|target| Some(&(*target).max)).map(|value| value.to_string())?)))() else {"#
+        ));
+        assert!(content.contains("observer.0 = value.clone();"));
+    }
+
+    #[test]
+    fn template_fields() {
+        let content = generate(
+            r#"
+            <Layout>
+                <Container id="List">
+                    <ItemTemplate source="{Resource Roster, Path=players}">
+                        <Container>
+                            <Node width="{Item width, Mode=Read, Fallback=10px}"/>
+                            <Player type="{Item kind, Mode=Read}"/>
+                        </Container>
+                    </ItemTemplate>
+                </Container>
+            </Layout>
+            "#,
+        );
+
+        assert!(content.contains(
+            r#".unwrap_or_else(|| ::bevy_declarative_ui::prelude::parse_or_default("10px", "width"));"#
+        ));
+        assert!(content.contains(".r#type = value;"));
+        assert!(content.contains("&mut ::bevy::prelude::Node"));
+        assert!(!content.contains(".unwrap()"));
+    }
+
+    #[test]
+    fn deterministic() {
+        let xml = r#"
+            <Layout>
+                <Use>game::Globals</Use>
+                <Use>game::Player</Use>
+                <Use>game::Score</Use>
+                <Node width="{Resource Globals, Path=width, Mode=Read}"/>
+                <Text text="{Component Player, Path=name, Mode=Read}"/>
+            </Layout>
+        "#;
+        let first = generate_static_layout("layout.xml", xml).unwrap().content;
+        let second = generate_static_layout("layout.xml", xml).unwrap().content;
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn invalid_attributes() {
+        assert_eq!(
+            generate_err(r#"<Layout><Node width="tenpx"/></Layout>"#),
+            "[Node] Attribute 'width': Invalid number in value: tenpx"
+        );
+        assert_eq!(
+            generate_err(r#"<Layout><Node colour="Red"/></Layout>"#),
+            "[Node] Attribute 'colour': Unknown attribute 'colour'"
+        );
+        assert_eq!(
+            generate_err(r#"<Layout><Player self="1"/></Layout>"#),
+            "[Player] Attribute 'self': 'self' can not be used as a field name"
+        );
+        assert_eq!(
+            generate_err("<Layout>\n<Text text=\"{Item name, Mode=Read}\"/></Layout>"),
+            "[Text] Attribute 'text': Item bindings are only allowed inside an ItemTemplate"
+        );
+        assert_eq!(
+            generate_err(r#"<Layout><Node width="{Resource Globals, Path=width}px"/></Layout>"#),
+            "[Node] Attribute 'width': Interpolated values can only be assigned to text attributes"
+        );
+        assert_eq!(
+            generate_err(
+                r#"<Layout><Node width="{Resource Globals, Path=width, Fallback=wide}"/></Layout>"#
+            ),
+            "[Node] Attribute 'width': Invalid fallback: Unknown value: wide"
+        );
+        assert_eq!(
+            generate_err(
+                r#"
+                <Layout>
+                    <Container>
+                        <ItemTemplate source="{Resource Roster, Path=players}">
+                            <Container><Player self="{Item Path=kind}"/></Container>
+                        </ItemTemplate>
+                    </Container>
+                </Layout>
+                "#
+            ),
+            "[Player] Attribute 'self': 'self' can not be used as a field name"
+        );
+        assert_eq!(
+            generate_err(
+                r#"
+                <Layout>
+                    <Container>
+                        <ItemTemplate source="{Resource Roster, Path=players}">
+                            <Container><Node width="{Item Path=width, Fallback=wide}"/></Container>
+                        </ItemTemplate>
+                    </Container>
+                </Layout>
+                "#
+            ),
+            "[Node] Attribute 'width': Invalid fallback: Unknown value: wide"
+        );
+        assert_eq!(
+            generate_err(r#"<Layout><Text text="{Count}"/></Layout>"#),
+            "[Text] Attribute 'text': Layout properties are only supported by the runtime loader, use a Resource binding"
+        );
+    }
+
+    #[test]
+    fn error_locations() {
+        let error = |xml: &str| generate_static_layout("layout.xml", xml).err().unwrap();
+
+        let attribute = error(
+            "<Layout>\n    <Container>\n        <Node width=\"tenpx\"/>\n    </Container>\n</Layout>",
+        );
+        assert!(matches!(attribute, GeneratorError::Attribute { .. }));
+        assert!(
+            attribute
+                .to_string()
+                .starts_with("layout.xml:3:15: [Node] Attribute 'width'")
+        );
+
+        let component =
+            error("<Layout xmlns:web=\"https://example.com\">\n    <web:Bar/>\n</Layout>");
+        assert!(matches!(component, GeneratorError::Component { .. }));
+        assert!(component.to_string().starts_with("layout.xml:2:6: [Bar]"));
+    }
+
+    #[test]
+    fn includes() {
+        let mut read = vec![];
+        let layout = generate_static_layout_with(
+            "ui/layout.xml",
+            r#"<Layout><Include path="widgets/title.xml" id="Title"/></Layout>"#,
+            |path| {
+                read.push(path.to_string());
+                Ok(r#"<Layout><Text text="Hello"/><Container id="Icon"><Node/></Container></Layout>"#.to_string())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(read, ["ui/widgets/title.xml"]);
+        assert!(
+            layout
+                .content
+                .contains(r#"component.0 = "Hello".to_string();"#)
+        );
+        assert!(layout.content.contains("Title))"));
+        assert!(layout.content.contains("TitleIcon))"));
+    }
+
+    #[test]
+    fn conditions() {
+        let content = generate(
+            r#"
+            <Layout>
+                <If id="Paused" test="{Resource Game, Path=paused}">
+                    <Text text="Paused"/>
+                </If>
+                <Else id="Running"/>
+                <Switch value="{Component Screen, Path=0, Filter=Player}">
+                    <Case id="Menu" equals="Menu"/>
+                </Switch>
+            </Layout>
+            "#,
+        );
+
+        assert!(
+            content.contains(
+                "::bevy_declarative_ui::prelude::ConditionalDisplay::default(), Paused))"
+            )
+        );
+        assert!(content.contains("fn paused_condition("));
+        assert!(content.contains("fn running_condition("));
+        assert!(content.contains("source_0: Option<Res<Game>>"));
+        assert!(content.contains(
+            "let shown = !value.is_some_and(|value| ::bevy_declarative_ui::prelude::condition_is_true(value));"
+        ));
+        assert!(content.contains("fn menu_condition("));
+        assert!(content.contains("source_0: Query<&Screen, With<Player>>"));
+        assert!(content.contains(r#"condition_equals(value, "Menu")"#));
+        assert!(content.contains("display.show(&mut node.display, shown);"));
+
+        assert_eq!(
+            generate_err(
+                r#"<Layout><Container><ItemTemplate source="{Resource Roster, Path=players}"><If test="{Item ready}"><Node/></If></ItemTemplate></Container></Layout>"#
+            ),
+            "[If] Only Resource and Component conditions are supported, use the runtime loader for the others"
+        );
+    }
+
+    #[test]
+    fn defines() {
+        let content = generate(
+            r#"
+            <Layout>
+                <Define name="Badge" params="icon">
+                    <Container id="Icon">
+                        <ImageNode image="{icon}"/>
+                    </Container>
+                </Define>
+                <Badge icon="ui/a.png"/>
+                <Badge icon="ui/b.png"/>
+            </Layout>
+            "#,
+        );
+
+        assert!(content.contains("pub struct Badge0Icon;"));
+        assert!(content.contains("pub struct Badge1Icon;"));
+        assert!(content.contains(r#"component.image = server.load("ui/a.png");"#));
+        assert!(content.contains(r#"component.image = server.load("ui/b.png");"#));
+    }
+
+    #[test]
+    fn styles() {
+        let content = generate(
+            r#"
+            <Layout>
+                <Styles>
+                    <Style class="panel">
+                        <Node width="100%" height="50px"/>
+                    </Style>
+                    <Style class="wide">
+                        <Node width="80%"/>
+                    </Style>
+                </Styles>
+                <Container id="Panel" class="panel wide">
+                    <Node height="10px"/>
+                </Container>
+            </Layout>
+            "#,
+        );
+
+        //One `Node`, the later class and the inline attribute win
+        assert_eq!(content.matches("component.width =").count(), 1);
+        assert!(content.contains(r#"parse_or_default("80%", "width")"#));
+        assert!(content.contains(r#"parse_or_default("10px", "height")"#));
+        assert!(!content.contains("50px"));
+    }
+}
@@ -1,26 +1,105 @@
-use bevy_declarative_ui_parser::values::bindings::BindingKind;
+use crate::codegen::Function;
+use crate::r#static::attributes::Field;
+use bevy_declarative_ui_parser::Condition;
+use bevy_declarative_ui_parser::values::InterpolationPart;
+use bevy_declarative_ui_parser::values::bindings::BindingMode;
 use bevy_declarative_ui_parser::values::bindings::filter::Filters;
-use std::collections::HashMap;
+use bevy_declarative_ui_parser::values::bindings::path::BindingPath;
 
 #[derive(Default)]
 pub struct Required {
     pub ids: Vec<String>,
 
-    ///Resource name, argument name
-    pub resources: HashMap<String, String>,
+    ///Sources read once by the spawn function, the index is the name of the argument
+    pub sources: Vec<Source>,
 
-    ///Component name, argument name, filters
-    pub components: HashMap<String, (String, Filters)>,
+    ///Bindings synchronized by the systems of the `bindings` module
+    pub bindings: Vec<RequiredBinding>,
 
-    ///Id, bindings
-    pub bindings: HashMap<String, Vec<RequiredBinding>>,
+    ///Interpolated attributes, every attribute is rendered by its own system
+    pub interpolations: Vec<RequiredInterpolation>,
+
+    ///Conditional containers, every container is shown or hidden by its own system
+    pub conditions: Vec<RequiredCondition>,
 
     ///Indicates that function requires asset server
     pub asset_server: bool,
+
+    ///Paths of the systems that must run every frame
+    pub systems: Vec<String>,
+}
+
+impl Required {
+    ///Name of the variable which holds the source in the spawn function
+    pub fn source_variable(&mut self, source: Source) -> String {
+        let index = match self.sources.iter().position(|other| *other == source) {
+            Some(index) => index,
+            None => {
+                self.sources.push(source);
+                self.sources.len() - 1
+            }
+        };
+        format!("source_{index}")
+    }
+
+    ///Adds the arguments of the read once sources and the asset server, then forgets them
+    pub fn take_arguments(&mut self, function: &mut Function) {
+        if std::mem::take(&mut self.asset_server) {
+            function.asset_server_arg();
+        }
+
+        std::mem::take(&mut self.sources)
+            .into_iter()
+            .enumerate()
+            .for_each(|(index, source)| {
+                let variable = format!("source_{index}");
+                match source {
+                    Source::Resource(resource) => {
+                        function.optional_resource_arg(resource, variable, false);
+                    }
+                    Source::Component(component, filters) => {
+                        function.query_arg(&variable, &format!("&{component}"), &filters, false);
+                        function.push_line_to_body(format!(
+                            "let {variable} = {variable}.single().ok();"
+                        ));
+                    }
+                }
+            });
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    Resource(String),
+    ///Component and the filters of the entity which holds it
+    Component(String, Filters),
 }
 
 pub struct RequiredBinding {
-    pub inner: BindingKind,
+    pub source: Source,
+    pub mode: BindingMode,
+    pub path: BindingPath,
+    pub converter: Option<String>,
+    ///Expression assigned while the source or the path is missing
+    pub fallback: Option<String>,
+    ///Marker of the observing node
+    pub id: String,
+    ///Path of the observing component
+    pub component: String,
+    pub field: Field,
+}
+
+pub struct RequiredInterpolation {
+    pub parts: Vec<InterpolationPart>,
+    ///Marker of the observing node
+    pub id: String,
+    ///Path of the observing component
     pub component: String,
-    pub field_name: String,
+    pub field: Field,
+}
+
+pub struct RequiredCondition {
+    pub condition: Condition,
+    ///Marker of the conditional container
+    pub id: String,
 }
@@ -1,152 +1,306 @@
 use crate::codegen::{Access, Function};
-use crate::r#static::required::{Required, RequiredBinding};
+use crate::r#static::attributes::{Field, ValueKind, resolve_tag_field, tag_path};
+use crate::r#static::converter_path;
+use crate::r#static::error::GeneratorError;
+use crate::r#static::required::{
+    Required, RequiredBinding, RequiredCondition, RequiredInterpolation, Source,
+};
+use bevy_declarative_ui_parser::UiNode;
 use bevy_declarative_ui_parser::into::Tag;
-use bevy_declarative_ui_parser::utils::GetOrInsertEmpty;
-use bevy_declarative_ui_parser::values::AttributeValue;
-use bevy_declarative_ui_parser::values::bindings::BindingKind;
-use bevy_declarative_ui_parser::{Id, UiNode};
-use std::sync::atomic::AtomicU64;
-
-pub fn print_spawn_function(required: &mut Required, nodes: &[UiNode]) -> Function {
-    let mut function = Function::new("spawn_document");
-    function
-        .access(Access::Public)
-        .commands_arg()
-        .push_line_to_body("let mut root = commands.spawn_empty();")
-        .push_line_to_body("root.with_children(|p| {");
+use bevy_declarative_ui_parser::values::bindings::BindingMode;
+use bevy_declarative_ui_parser::values::bindings::path::BindingPath;
+use bevy_declarative_ui_parser::values::{AttributeValue, InterpolationPart};
 
+pub fn print_spawn_function(
+    required: &mut Required,
+    nodes: &[UiNode],
+) -> Result<Function, GeneratorError> {
+    let mut body = String::new();
     nodes
         .iter()
-        .for_each(|node| print_node(&mut function, required, node));
-
-    if required.asset_server {
-        function.asset_server_arg();
-    }
+        .try_for_each(|node| print_node(&mut body, required, node, false))?;
 
-    function.push_line_to_body("});");
+    let mut function = Function::new("spawn_document");
+    function.access(Access::Public).commands_arg();
+    required.take_arguments(&mut function);
     function
+        .push_line_to_body("let mut root = commands.spawn_empty();")
+        .push_line_to_body("root.with_children(|p| {")
+        .push_to_body(body)
+        .push_line_to_body("});");
+    Ok(function)
 }
 
-fn print_node(function: &mut Function, required: &mut Required, node: &UiNode) {
-    let mut fields: Vec<String> = node
+///Spawns the node and its children, template nodes are marked with `Runtime` ids
+pub(crate) fn print_node(
+    body: &mut String,
+    required: &mut Required,
+    node: &UiNode,
+    template: bool,
+) -> Result<(), GeneratorError> {
+    let id = if template {
+        format!("Runtime{}", node.id)
+    } else {
+        node.id.to_string()
+    };
+    //Ids scoped by an `<Include>` or a `<Define>` instance, like `Header.Close`, are joined into
+    //the type `HeaderClose`
+    let id = id.replace('.', "");
+
+    let mut fields = node
         .components
         .iter()
-        .map(|c| format_component(required, &node.id, c))
-        .collect();
-    fields.push(format!("{}", node.id));
-    required.ids.push(node.id.to_string());
+        .map(|component| format_component(required, &id, component, template))
+        .collect::<Result<Vec<_>, _>>()?;
+    if let Some(condition) = &node.condition {
+        if !matches!(
+            condition.value,
+            AttributeValue::Resource(_) | AttributeValue::Component(_)
+        ) {
+            return Err(GeneratorError::component(
+                &node.tag,
+                "Only Resource and Component conditions are supported, use the runtime loader for the others",
+            ));
+        }
+        fields.push("::bevy_declarative_ui::prelude::ConditionalDisplay::default()".to_string());
+        required.conditions.push(RequiredCondition {
+            condition: condition.clone(),
+            id: id.clone(),
+        });
+    }
+    fields.push(id.clone());
+    required.ids.push(id);
 
     if fields.len() > 1 {
-        function.push_line_to_body("p.spawn((");
-        function.push_to_body(fields.join(", "));
-        function.push_to_body("))");
+        body.push_str(&format!("p.spawn(({}))", fields.join(", ")));
     } else {
-        function.push_line_to_body("p.spawn(");
-        function.push_line_to_body(fields.get(0).unwrap());
-        function.push_to_body(")");
+        body.push_str(&format!("p.spawn({})", fields[0]));
     }
 
     if node.children.is_empty() {
-        function.push_to_body(';');
+        body.push_str(";\n");
     } else {
-        function.push_line_to_body(".with_children(|p| {");
+        body.push_str(".with_children(|p| {\n");
         node.children
             .iter()
-            .for_each(|child| print_node(function, required, child));
-        function.push_line_to_body("});");
+            .try_for_each(|child| print_node(body, required, child, template))?;
+        body.push_str("});\n");
     }
+    Ok(())
 }
 
-static RES_ID: AtomicU64 = AtomicU64::new(0);
-static COMP_ID: AtomicU64 = AtomicU64::new(0);
-
-pub(crate) fn format_component(required: &mut Required, id: &Id, tag: &Tag) -> String {
-    if tag.attributes.is_empty() {
-        return format!("{}::default()", tag.name);
-    }
-
+///Expression which builds the component, read once bindings are read from the spawn function arguments
+pub(crate) fn format_component(
+    required: &mut Required,
+    id: &str,
+    tag: &Tag,
+    template: bool,
+) -> Result<String, GeneratorError> {
+    let path = tag_path(tag).map_err(|message| GeneratorError::component(tag, message))?;
     let mut fields = String::new();
 
-    tag.attributes.iter().for_each(|attr| {
-        /*
-        let value: Option<String> = match &attr.value {
-            AttributeValue::Value(value) => Some(value.to_string()),
+    for attribute in &tag.attributes {
+        let error = |message: String| GeneratorError::attribute(tag, attribute, message);
+        let field = resolve_tag_field(tag, &attribute.name).map_err(error)?;
+        let place = &field.place;
+
+        match &attribute.value {
+            AttributeValue::Value(value) => {
+                if let Some(kind) = field.kind {
+                    kind.validate(value).map_err(error)?;
+                    required.asset_server |= kind == ValueKind::Asset;
+                }
+                let value = ValueKind::literal(field.kind, value, &attribute.name);
+                fields.push_str(&format!("component.{place} = {value};\n"));
+            }
             AttributeValue::Asset(asset) => {
                 required.asset_server = true;
-                Some(format!("server.load(\"{}\")", asset.path))
-            },
-            //TODO fix
-            AttributeValue::Item(item) => Some(format!("target.{}", item.path)),
-            AttributeValue::Binding(binding) => prepare_binding(required, id, &tag.name, &attr.name, binding),
-            AttributeValue::Function { .. } => todo!(),
-        };
-
-        if let Some(value) = value {
-            fields.push_str(&format!("    {}: {},\n", attr.name, value));
+                fields.push_str(&format!(
+                    "component.{place} = server.load({:?});\n",
+                    asset.path
+                ));
+            }
+            AttributeValue::Resource(binding) => {
+                let params = &binding.additional_params;
+                let source = Source::Resource(binding.base_params.target.clone());
+                let binding = Bound {
+                    source,
+                    path: &binding.base_params.path,
+                    mode: params.mode,
+                    converter: params.converter.as_deref(),
+                    fallback: fallback(&field, params.fallback.as_deref(), &attribute.name)
+                        .map_err(error)?,
+                };
+                bind(required, id, &path, field, binding, &mut fields);
+            }
+            AttributeValue::Component(binding) => {
+                let params = &binding.additional_params;
+                let source =
+                    Source::Component(binding.base_params.target.clone(), params.filters.clone());
+                let binding = Bound {
+                    source,
+                    path: &binding.base_params.path,
+                    mode: params.mode,
+                    converter: params.converter.as_deref(),
+                    fallback: fallback(&field, params.fallback.as_deref(), &attribute.name)
+                        .map_err(error)?,
+                };
+                bind(required, id, &path, field, binding, &mut fields);
+            }
+            //Written by the template binding system
+            AttributeValue::Item(_) if template => {}
+            AttributeValue::Interpolated(parts) if template && parts.iter().all(is_item_part) => {}
+            AttributeValue::Item(_) => {
+                return Err(error(
+                    "Item bindings are only allowed inside an ItemTemplate".to_string(),
+                ));
+            }
+            AttributeValue::Interpolated(_) if template => {
+                return Err(error(
+                    "Only Item bindings can be interpolated inside an ItemTemplate".to_string(),
+                ));
+            }
+            AttributeValue::Interpolated(parts) => {
+                if field.kind.is_some_and(|kind| kind != ValueKind::String) {
+                    return Err(error(
+                        "Interpolated values can only be assigned to text attributes".to_string(),
+                    ));
+                }
+                if !parts.iter().all(is_source_part) {
+                    return Err(error(
+                        "Item bindings are only allowed inside an ItemTemplate".to_string(),
+                    ));
+                }
+                required.interpolations.push(RequiredInterpolation {
+                    parts: parts.clone(),
+                    id: id.to_string(),
+                    component: path.clone(),
+                    field,
+                });
+            }
+            AttributeValue::Property(_) => {
+                return Err(error(
+                    "Layout properties are only supported by the runtime loader, use a Resource binding"
+                        .to_string(),
+                ));
+            }
+            AttributeValue::Template(_) => {
+                return Err(error(
+                    "Template bindings are only allowed in the ItemTemplate source".to_string(),
+                ));
+            }
         }
-        */
-    });
+    }
 
     if fields.is_empty() {
-        format!("{}::default()", tag.name)
+        Ok(format!("{path}::default()"))
     } else {
-        let mut result = format!("{} {{\n", tag.name);
-        result.push_str(&fields);
-        result.push_str("    ..default()\n");
-        result.push('}');
-        result
+        Ok(format!(
+            "{{\nlet mut component = {path}::default();\n{fields}component\n}}"
+        ))
     }
 }
 
-fn create_required_binding(
-    required: &mut Required,
-    id: String,
-    binding: BindingKind,
-    component: String,
-    field_name: String,
-) {
-    let bindings = required.bindings.get_or_insert_empty(id);
-    bindings.push(RequiredBinding {
-        inner: binding,
-        component,
-        field_name,
-    });
+///Text parts and `Item` bindings can be rendered from the template item
+pub(crate) fn is_item_part(part: &InterpolationPart) -> bool {
+    match part {
+        InterpolationPart::Text(_) => true,
+        InterpolationPart::Binding(value) => matches!(value, AttributeValue::Item(_)),
+    }
+}
+
+///Text parts and `Resource` or `Component` bindings can be rendered by a system
+fn is_source_part(part: &InterpolationPart) -> bool {
+    match part {
+        InterpolationPart::Text(_) => true,
+        InterpolationPart::Binding(value) => matches!(
+            value,
+            AttributeValue::Resource(_) | AttributeValue::Component(_)
+        ),
+    }
 }
 
-fn prepare_binding(
+///Expression of the `Fallback` parameter, checked like a literal value of the field
+pub(crate) fn fallback(
+    field: &Field,
+    fallback: Option<&str>,
+    attribute: &str,
+) -> Result<Option<String>, String> {
+    let Some(fallback) = fallback else {
+        return Ok(None);
+    };
+    if let Some(kind) = field.kind {
+        kind.validate(fallback)
+            .map_err(|error| format!("Invalid fallback: {error}"))?;
+    }
+    Ok(Some(ValueKind::literal(field.kind, fallback, attribute)))
+}
+
+///Resource or component binding of an attribute
+struct Bound<'a> {
+    source: Source,
+    path: &'a BindingPath,
+    mode: BindingMode,
+    converter: Option<&'a str>,
+    ///Value assigned while the source or the path is missing
+    fallback: Option<String>,
+}
+
+///`ReadOnce` bindings are read by the spawn function, the others are synchronized by systems
+fn bind(
     required: &mut Required,
-    id: &Id,
-    component: &String,
-    attribute: &String,
-    binding: &BindingKind,
-) -> Option<String> {
-    //TODO
-    /*
-    match binding {
-        Binding::Resource { params } => {
-            if BindingMode::ReadOnce == params.mode {
-                if !required.resources.contains_key(&params.target) {
-                    let arg_id = RES_ID.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                    let arg_id = format!("res{arg_id}");
-                    required.resources.insert(params.target.clone(), arg_id);
-                }
-                let id = &required.resources.get(&params.target).unwrap();
-                return Some(format!("{id}.{}", params.path.to_string()));
-            }
+    id: &str,
+    component: &str,
+    field: Field,
+    binding: Bound,
+    fields: &mut String,
+) {
+    if binding.mode != BindingMode::ReadOnce {
+        required.bindings.push(RequiredBinding {
+            source: binding.source,
+            mode: binding.mode,
+            path: binding.path.clone(),
+            converter: binding.converter.map(str::to_string),
+            fallback: binding.fallback,
+            id: id.to_string(),
+            component: component.to_string(),
+            field,
+        });
+        return;
+    }
+
+    let target = match &binding.source {
+        Source::Resource(_) => format!(
+            "{}.as_deref()",
+            required.source_variable(binding.source.clone())
+        ),
+        Source::Component(..) => required.source_variable(binding.source.clone()),
+    };
+    let value = format!(
+        "{target}.and_then(|target| {})",
+        binding.path.to_rust("(*target)")
+    );
+    let value = match binding.converter {
+        Some(converter) => {
+            required.asset_server = true;
+            format!(
+                "{value}.and_then(|value| ::bevy_declarative_ui::prelude::ConvertValue::convert_to(value, &{}::default(), &server))",
+                converter_path(converter)
+            )
         }
-        Binding::Component { params } => {
-            if BindingMode::ReadOnce == params.base.mode {
-                if !required.components.contains_key(&params.base.target) {
-                    let arg_id = COMP_ID.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                    let arg_id = format!("cmp{arg_id}");
-                    required.components.insert(params.base.target.clone(), (arg_id, params.filters.clone()));
-                }
-                let (id, _) = &required.components.get(&params.base.target).unwrap();
-                return Some(format!("{id}.{}", params.base.path.clone()));
-            }
+        None => format!("{value}.cloned()"),
+    };
+
+    let place = &field.place;
+    match binding.fallback {
+        Some(fallback) => {
+            required.asset_server |= field.kind == Some(ValueKind::Asset);
+            fields.push_str(&format!(
+                "component.{place} = {value}.unwrap_or_else(|| {fallback});\n"
+            ));
         }
+        None => fields.push_str(&format!(
+            "if let Some(value) = {value} {{\ncomponent.{place} = value;\n}}\n"
+        )),
     }
-    create_required_binding(required, id.to_string(), binding.clone(), component.clone(), attribute.clone());
-    */
-    None
 }
@@ -1,9 +1,16 @@
 use crate::codegen::{Argument, Function, Module, Ownership};
+use crate::r#static::attributes::{Field, ValueKind, resolve_tag_field, tag_path};
+use crate::r#static::error::GeneratorError;
 use crate::r#static::required::Required;
+use crate::r#static::spawn_function::{fallback, is_item_part, print_node};
+use crate::r#static::{converter_path, register_system};
 use crate::utils::ToSnakeCase;
 use bevy_declarative_ui_parser::utils::GetOrInsertEmpty;
+use bevy_declarative_ui_parser::values::bindings::Binding;
 use bevy_declarative_ui_parser::values::bindings::filter::{Filter, Filters};
-use bevy_declarative_ui_parser::values::{AttributeValue, TemplateBinding};
+use bevy_declarative_ui_parser::values::bindings::params::{AdditionalParams, ItemBaseParams};
+use bevy_declarative_ui_parser::values::bindings::path::BindingPath;
+use bevy_declarative_ui_parser::values::{AttributeValue, InterpolationPart, TemplateBinding};
 use bevy_declarative_ui_parser::{ItemTemplate, UiNode};
 use itertools::Itertools;
 use std::collections::HashMap;
@@ -12,8 +19,7 @@ fn get_access_to_target(function: &mut Function, binding: &TemplateBinding) {
     match binding {
         TemplateBinding::Resource(binding) => {
             function.resource_arg(&binding.base_params.target, "target");
-            function
-                .push_line_to_body(format!("let target = target.{};", binding.base_params.path));
+            function.push_line_to_body(read_source(&binding.base_params.path));
         }
         TemplateBinding::Component(binding) => {
             function.single_ref_arg(
@@ -21,23 +27,38 @@ fn get_access_to_target(function: &mut Function, binding: &TemplateBinding) {
                 &binding.base_params.target,
                 &binding.additional_params.filters,
             );
-            function
-                .push_line_to_body(format!("let target = target.{};", binding.base_params.path));
+            function.push_line_to_body(read_source(&binding.base_params.path));
         }
     }
 }
 
+///Replaces `target` with the item source, returns if the path can not be read
+fn read_source(path: &BindingPath) -> String {
+    format!(
+        "let Some(target) = {} else {{\nreturn;\n}};",
+        path.to_rust("target")
+    )
+}
+
 pub fn print_template_functions(
     templates: &[ItemTemplate],
     global_required: &mut Required,
-) -> Module {
-    let mut required = Required::default();
-
+) -> Result<Module, GeneratorError> {
     let mut module = Module::new("templates");
+    module.with_using("std::cmp::Ordering");
+    module.with_using("super::*");
+    module.with_using("bevy::prelude::*");
+    module.with_using("super::ids::*");
 
-    templates.iter().for_each(|template| {
-        required.ids.push(template.id.to_string());
-        template_workload(template, &mut module);
+    for template in templates {
+        global_required.ids.push(template.id.to_string());
+        template_workload(template, &mut module, &mut global_required.systems)?;
+
+        let mut body = String::new();
+        template
+            .nodes
+            .iter()
+            .try_for_each(|node| print_node(&mut body, global_required, node, true))?;
 
         let mut function =
             Function::new(format!("spawn_{}", template.id.to_string().to_snake_case()));
@@ -48,65 +69,26 @@ pub fn print_template_functions(
             "Entity",
             &Filters::single(Filter::With(template.owner.to_string())),
         );
-        function.push_line_to_body("let mut container = commands.entity(container);");
+        global_required.take_arguments(&mut function);
+        function.push_line_to_body("let mut container = commands.entity(*container);");
         function.push_line_to_body("container.with_children(|p| {");
-
-        template
-            .nodes
-            .iter()
-            .for_each(|node| print_node(&mut function, &mut required, node));
-
+        function.push_to_body(body);
         function.push_line_to_body("});");
-        if required.asset_server {
-            function.asset_server_arg();
-            required.asset_server = false;
-        }
 
         module.with_function(function);
-    });
-
-    global_required.ids.extend(required.ids);
-
-    module
-}
-
-fn print_node(function: &mut Function, required: &mut Required, node: &UiNode) {
-    let mut fields: Vec<String> = node
-        .components
-        .iter()
-        .map(|c| crate::r#static::spawn_function::format_component(required, &node.id, c))
-        .collect();
-    let runtime_id = format!("Runtime{}", node.id);
-    fields.push(runtime_id.clone());
-    required.ids.push(runtime_id);
-
-    if fields.len() > 1 {
-        function.push_line_to_body("p.spawn((");
-        function.push_to_body(fields.join(", "));
-        function.push_to_body("))");
-    } else {
-        function.push_line_to_body("p.spawn(");
-        function.push_line_to_body(fields.first().unwrap());
-        function.push_to_body(")");
     }
 
-    if node.children.is_empty() {
-        function.push_to_body(';');
-    } else {
-        function.push_line_to_body(".with_children(|p| {");
-        node.children
-            .iter()
-            .for_each(|container| print_node(function, required, container));
-        function.push_line_to_body("});");
-    }
+    Ok(module)
 }
 
-fn template_workload(template: &ItemTemplate, module: &mut Module) {
-    module.with_using("std::cmp::Ordering");
-    module.with_using("bevy::prelude::*");
-    module.with_using("super::ids::*");
-    module.with_function(template_instance_limiter(template));
-    module.with_function(template_binding(template));
+fn template_workload(
+    template: &ItemTemplate,
+    module: &mut Module,
+    systems: &mut Vec<String>,
+) -> Result<(), GeneratorError> {
+    register_system(module, systems, template_instance_limiter(template));
+    register_system(module, systems, template_binding(template)?);
+    Ok(())
 }
 
 fn template_instance_limiter(template: &ItemTemplate) -> Function {
@@ -148,7 +130,7 @@ fn template_instance_limiter(template: &ItemTemplate) -> Function {
     function
 }
 
-fn template_binding(template: &ItemTemplate) -> Function {
+fn template_binding(template: &ItemTemplate) -> Result<Function, GeneratorError> {
     let mut function: Function = Function::new(format!(
         "{}_binding",
         template.id.to_string().to_snake_case()
@@ -158,29 +140,31 @@ fn template_binding(template: &ItemTemplate) -> Function {
         TemplateBinding::Resource(binding) => {
             function.resource_arg(&binding.base_params.target, "target");
             function.push_line_to_body("if !target.is_changed() {{\n return;\n}}");
-            function.push_line_to_body(format!(
-                "let target = &target.{};",
-                &binding.base_params.path
-            ));
+            function.push_line_to_body(read_source(&binding.base_params.path));
         }
         TemplateBinding::Component(binding) => {
             let mut filters = binding.additional_params.filters.clone();
             let filters = filters.with(Filter::Changed(binding.base_params.target.clone()));
             function.single_ref_arg("target", &binding.base_params.target, filters);
-            function.push_line_to_body(format!(
-                "let target = &target.{};",
-                &binding.base_params.path
-            ));
+            function.push_line_to_body(read_source(&binding.base_params.path));
         }
     }
 
     let mut observers = HashMap::new();
-    create_observers(&mut 0, &mut observers, &template.nodes);
+    create_observers(&mut 0, &mut observers, &template.nodes)?;
     let mut observers = observers.into_iter().collect_vec();
     observers.sort_by(|((_, ident_a), _), ((_, ident_b), _)| ident_a.cmp(ident_b));
     prepare_arguments(&mut function, &observers);
 
-    if observers.len() > 1 {
+    let loads = observers
+        .iter()
+        .flat_map(|(_, components)| components.values().flatten())
+        .any(Observer::loads);
+    if loads {
+        function.asset_server_arg();
+    }
+
+    if !observers.is_empty() {
         let mut cmp_binds = Vec::new();
         let mut zip_calls = String::new();
         let mut setters = String::new();
@@ -190,7 +174,7 @@ fn template_binding(template: &ItemTemplate) -> Function {
         });
 
         observers.into_iter().for_each(|((_, ident), components)| {
-            cmp_binds.push(ident.clone());
+            cmp_binds.push(format!("mut {ident}"));
             let components_in_bundle = components.len();
 
             components
@@ -198,18 +182,11 @@ fn template_binding(template: &ItemTemplate) -> Function {
                 .enumerate()
                 .for_each(|(i, (_, observers))| {
                     observers.iter().for_each(|observer| {
+                        let field = &observer.field.place;
                         if components_in_bundle > 1 {
-                            setters.push_str(&format!(
-                                "{ident}.{i}.{field} = target.{path};\n",
-                                field = observer.field,
-                                path = observer.path
-                            ));
+                            setters.push_str(&observer.setter(&format!("{ident}.{i}.{field}")));
                         } else {
-                            setters.push_str(&format!(
-                                "{ident}.{field} = target.{path};\n",
-                                field = observer.field,
-                                path = observer.path
-                            ));
+                            setters.push_str(&observer.setter(&format!("{ident}.{field}")));
                         }
                     });
                 });
@@ -218,36 +195,55 @@ fn template_binding(template: &ItemTemplate) -> Function {
         let cmp_binds = nested_join(cmp_binds);
         function.push_line_to_body(format!(
             r#"
-        cmp0.iter()
+        cmp0.iter_mut()
             {zip_calls}
             .enumerate()
             .for_each(|(i, {cmp_binds})| {{
-                let target = target.get(i).unwrap();
+                let target = target.get(i);
                 {setters}
             }});
         "#
         ));
     }
-    function
+    Ok(function)
 }
 
 fn create_observers<'a>(
     i: &mut u32,
     observers: &mut HashMap<(String, String), HashMap<String, Vec<Observer<'a>>>>, //(Owner, Ident), Component, Observer
     nodes: &'a [UiNode],
-) {
+) -> Result<(), GeneratorError> {
     for node in nodes {
         let mut components = HashMap::new();
         for component in &node.components {
             for attribute in &component.attributes {
-                if let AttributeValue::Item(item) = &attribute.value {
-                    let observers: &mut Vec<Observer> =
-                        components.get_or_insert_empty(component.name.clone());
-                    observers.push(Observer {
-                        field: &attribute.name,
-                        path: &item.base_params.path,
-                    });
-                }
+                let value = match &attribute.value {
+                    AttributeValue::Item(item) => ObservedValue::Item(item),
+                    AttributeValue::Interpolated(parts) if parts.iter().all(is_item_part) => {
+                        ObservedValue::Interpolated(parts)
+                    }
+                    _ => continue,
+                };
+                let error =
+                    |message: String| GeneratorError::attribute(component, attribute, message);
+                let field = resolve_tag_field(component, &attribute.name).map_err(error)?;
+                let fallback = match value {
+                    ObservedValue::Item(item) => fallback(
+                        &field,
+                        item.additional_params.fallback.as_deref(),
+                        &attribute.name,
+                    )
+                    .map_err(error)?,
+                    ObservedValue::Interpolated(_) => None,
+                };
+                let path = tag_path(component)
+                    .map_err(|message| GeneratorError::component(component, message))?;
+                let observers: &mut Vec<Observer> = components.get_or_insert_empty(path);
+                observers.push(Observer {
+                    field,
+                    value,
+                    fallback,
+                });
             }
         }
         observers.insert(
@@ -255,13 +251,91 @@ fn create_observers<'a>(
             components,
         );
         *i += 1;
-        create_observers(i, observers, &node.children);
+        create_observers(i, observers, &node.children)?;
     }
+    Ok(())
 }
 
 struct Observer<'a> {
-    field: &'a str,
-    path: &'a str,
+    field: Field,
+    value: ObservedValue<'a>,
+    ///Expression assigned while the item value is missing
+    fallback: Option<String>,
+}
+
+enum ObservedValue<'a> {
+    Item(&'a Binding<ItemBaseParams, AdditionalParams>),
+    Interpolated(&'a [InterpolationPart]),
+}
+
+impl Observer<'_> {
+    ///Converters and asset fallbacks need the asset server
+    fn loads(&self) -> bool {
+        if self.fallback.is_some() && self.field.kind == Some(ValueKind::Asset) {
+            return true;
+        }
+
+        match self.value {
+            ObservedValue::Item(item) => item.additional_params.converter.is_some(),
+            ObservedValue::Interpolated(parts) => parts.iter().any(|part| {
+                matches!(part, InterpolationPart::Binding(AttributeValue::Item(item)) if item.additional_params.converter.is_some())
+            }),
+        }
+    }
+
+    ///Expression of type `Option<T>` which reads the bound value from the template item
+    fn getter(&self) -> String {
+        match self.value {
+            ObservedValue::Item(item) => item_getter(item),
+            ObservedValue::Interpolated(parts) => {
+                let mut format = String::new();
+                let mut arguments = String::new();
+                parts.iter().for_each(|part| match part {
+                    InterpolationPart::Text(text) => {
+                        format.push_str(&text.replace('{', "{{").replace('}', "}}"))
+                    }
+                    InterpolationPart::Binding(AttributeValue::Item(item)) => {
+                        format.push_str("{}");
+                        let value = format!("{}.map(|value| value.to_string())", item_getter(item));
+                        match &item.additional_params.fallback {
+                            Some(fallback) => arguments.push_str(&format!(
+                                ", {value}.unwrap_or_else(|| {fallback:?}.to_string())"
+                            )),
+                            None => arguments.push_str(&format!(", {value}?")),
+                        }
+                    }
+                    InterpolationPart::Binding(_) => unreachable!(),
+                });
+                format!("(|| Some(format!({format:?}{arguments})))()")
+            }
+        }
+    }
+
+    ///Assigns the bound value, or the fallback checked at expansion if the value is missing
+    fn setter(&self, field: &str) -> String {
+        let getter = self.getter();
+        match &self.fallback {
+            Some(fallback) => format!("{field} = {getter}.unwrap_or_else(|| {fallback});\n"),
+            None => format!("if let Some(value) = {getter} {{\n{field} = value;\n}}\n"),
+        }
+    }
+}
+
+///Expression of type `Option<T>` which reads the item binding from the template item
+fn item_getter(item: &Binding<ItemBaseParams, AdditionalParams>) -> String {
+    let value = format!(
+        "target.and_then(|target| {})",
+        item.base_params.path.to_rust("target")
+    );
+    match &item.additional_params.converter {
+        Some(converter) => {
+            format!(
+                "{value}.and_then(|value| ::bevy_declarative_ui::prelude::ConvertValue::convert_to(value, &{}::default(), &server))",
+                converter_path(converter)
+            )
+        }
+        None => format!("{value}.cloned()"),
+    }
 }
 
 fn nested_join(items: Vec<String>) -> String {
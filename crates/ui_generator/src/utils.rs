@@ -1,19 +1,5 @@
 use std::collections::HashSet;
 
-pub(super) fn to_pascal_case(input: &str) -> String {
-    input
-        .split('_')
-        .filter(|part| !part.is_empty())
-        .map(|word| {
-            let mut chars = word.chars();
-            match chars.next() {
-                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
-                None => String::new(),
-            }
-        })
-        .collect::<String>()
-}
-
 pub(crate) trait ToSnakeCase {
     fn to_snake_case(&self) -> String;
 }